@@ -0,0 +1,59 @@
+// By-value operators against their in-place counterparts, run with `cargo bench`.
+// Every by-value step moves the whole List (8 KiB at N = 1024) in and out of the operator.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use linear_algebra::chapters::c3::matrix::Matrix;
+use linear_algebra::prelude::*;
+
+const N: usize = 1024;
+const ITERATIONS: u32 = 10_000;
+
+fn time(name: &str, mut f: impl FnMut()) -> Duration {
+    // Warm up caches and branch predictors before measuring
+    for _ in 0..ITERATIONS / 10 {
+        f();
+    }
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let per_iteration = start.elapsed() / ITERATIONS;
+    println!("{name:<40} {per_iteration:>10.2?}");
+    per_iteration
+}
+
+fn main() {
+    let x = List::<f64, N>::from_fn(|i| i as f64 * 0.5);
+    let mut y = List::<f64, N>::from_fn(|i| 1.0 - i as f64);
+
+    time("list: y = y + x", || {
+        y = black_box(y) + black_box(x);
+    });
+    time("list: y.add_assign_ref(&x)", || {
+        black_box(&mut y).add_assign_ref(black_box(&x));
+    });
+    time("list: y = 2 x + y", || {
+        y = MulScalar::mul(black_box(x), 2.0) + black_box(y);
+    });
+    time("list: y.axpy(2, &x)", || {
+        black_box(&mut y).axpy(2.0, black_box(&x));
+    });
+    time("list: y = -y", || {
+        y = -black_box(y);
+    });
+    time("list: y.neg_mut()", || {
+        black_box(&mut y).neg_mut();
+    });
+
+    let a = Matrix::<f64, 32, 32>::from_fn(|i, j| (i + j) as f64 * 0.01);
+    let b = Matrix::<f64, 32, 32>::from_fn(|i, j| i as f64 - j as f64);
+    let mut out = Matrix::ZERO;
+    time("matrix 32x32: out = a * b", || {
+        out = black_box(a) * black_box(b);
+    });
+    time("matrix 32x32: a.mul_into(&b, &mut out)", || {
+        black_box(&a).mul_into(black_box(&b), black_box(&mut out));
+    });
+    black_box((y, out));
+}
@@ -0,0 +1,37 @@
+// Summing every column of a matrix in both layouts, run with `cargo bench`.
+// Row major has to gather each column from all rows, column major reads it in one piece.
+use std::hint::black_box;
+use std::time::Instant;
+
+use linear_algebra::chapters::c3::matrix::{ColMajor, Matrix};
+
+const N: usize = 128;
+const ITERATIONS: u32 = 1_000;
+
+fn time(name: &str, mut f: impl FnMut() -> f64) {
+    let mut total = 0.0;
+    for _ in 0..ITERATIONS / 10 {
+        total += f();
+    }
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        total += f();
+    }
+    let per_iteration = start.elapsed() / ITERATIONS;
+    println!("{name:<32} {per_iteration:>10.2?}");
+    black_box(total);
+}
+
+fn main() {
+    let rows = Matrix::<f64, N, N>::from_fn(|i, j| (i * N + j) as f64 * 1e-3);
+    let cols = rows.to_layout::<ColMajor>();
+
+    time("column sums, row major", || {
+        let m = black_box(&rows);
+        (0..N).map(|j| (0..N).map(|i| m[(i, j)]).sum::<f64>()).sum()
+    });
+    time("column sums, column major", || {
+        let m = black_box(&cols);
+        (0..N).map(|j| m.col(j).iter().sum::<f64>()).sum()
+    });
+}
@@ -0,0 +1,361 @@
+use std::ops::{Add, Mul, Neg};
+
+use super::definitions::{Complex, List, MulScalar, One, Zero};
+
+// The marker traits in definitions only promise the axioms, these functions check them
+// on sample inputs and report the first axiom that fails together with its inputs.
+
+// Equality up to rounding errors
+pub trait ApproxEq {
+    fn approx_eq(&self, other: &Self) -> bool;
+}
+
+// Relative tolerance for large values, absolute for values below 1
+const EPSILON: f64 = 1e-9;
+
+impl ApproxEq for f64 {
+    fn approx_eq(&self, other: &Self) -> bool {
+        (self - other).abs() <= EPSILON * self.abs().max(other.abs()).max(1.0)
+    }
+}
+
+impl ApproxEq for Complex<f64> {
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.re().approx_eq(other.re()) && self.im().approx_eq(other.im())
+    }
+}
+
+impl<T, const N: usize> ApproxEq for List<T, N>
+where
+    T: ApproxEq,
+{
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.iter().zip(other.iter()).all(|(a, b)| a.approx_eq(b))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AxiomViolation<V, F> {
+    // u + v != v + u
+    Commutativity { u: V, v: V },
+    // (u + v) + w != u + (v + w)
+    Associativity { u: V, v: V, w: V },
+    // v + 0 != v
+    AdditiveIdentity { v: V },
+    // v + (-v) != 0
+    AdditiveInverse { v: V },
+    // 1v != v
+    MultiplicativeIdentity { v: V },
+    // (ab)v != a(bv)
+    ScalarAssociativity { a: F, b: F, v: V },
+    // a(u + v) != au + av
+    VectorDistributivity { a: F, u: V, v: V },
+    // (a + b)v != av + bv
+    ScalarDistributivity { a: F, b: F, v: V },
+}
+
+pub fn check_commutativity<V, F>(samples: &[V]) -> Result<(), AxiomViolation<V, F>>
+where
+    V: Add<Output = V> + ApproxEq + Clone,
+{
+    for u in samples {
+        for v in samples {
+            if !(u.clone() + v.clone()).approx_eq(&(v.clone() + u.clone())) {
+                return Err(AxiomViolation::Commutativity {
+                    u: u.clone(),
+                    v: v.clone(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn check_associativity<V, F>(samples: &[V]) -> Result<(), AxiomViolation<V, F>>
+where
+    V: Add<Output = V> + ApproxEq + Clone,
+{
+    for u in samples {
+        for v in samples {
+            for w in samples {
+                let left = (u.clone() + v.clone()) + w.clone();
+                let right = u.clone() + (v.clone() + w.clone());
+                if !left.approx_eq(&right) {
+                    return Err(AxiomViolation::Associativity {
+                        u: u.clone(),
+                        v: v.clone(),
+                        w: w.clone(),
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn check_additive_identity<V, F>(samples: &[V]) -> Result<(), AxiomViolation<V, F>>
+where
+    V: Add<Output = V> + Zero + ApproxEq + Clone,
+{
+    for v in samples {
+        if !(v.clone() + V::ZERO).approx_eq(v) {
+            return Err(AxiomViolation::AdditiveIdentity { v: v.clone() });
+        }
+    }
+    Ok(())
+}
+
+pub fn check_additive_inverse<V, F>(samples: &[V]) -> Result<(), AxiomViolation<V, F>>
+where
+    V: Add<Output = V> + Neg<Output = V> + Zero + ApproxEq + Clone,
+{
+    for v in samples {
+        if !(v.clone() + -v.clone()).approx_eq(&V::ZERO) {
+            return Err(AxiomViolation::AdditiveInverse { v: v.clone() });
+        }
+    }
+    Ok(())
+}
+
+pub fn check_multiplicative_identity<V, F>(samples: &[V]) -> Result<(), AxiomViolation<V, F>>
+where
+    V: MulScalar<F> + ApproxEq + Clone,
+    F: One,
+{
+    for v in samples {
+        if !v.clone().mul(F::ONE).approx_eq(v) {
+            return Err(AxiomViolation::MultiplicativeIdentity { v: v.clone() });
+        }
+    }
+    Ok(())
+}
+
+pub fn check_scalar_associativity<V, F>(
+    vectors: &[V],
+    scalars: &[F],
+) -> Result<(), AxiomViolation<V, F>>
+where
+    V: MulScalar<F> + ApproxEq + Clone,
+    F: Mul<Output = F> + Clone,
+{
+    for a in scalars {
+        for b in scalars {
+            for v in vectors {
+                let left = v.clone().mul(a.clone() * b.clone());
+                let right = v.clone().mul(b.clone()).mul(a.clone());
+                if !left.approx_eq(&right) {
+                    return Err(AxiomViolation::ScalarAssociativity {
+                        a: a.clone(),
+                        b: b.clone(),
+                        v: v.clone(),
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// Both distributive laws, a(u + v) = au + av and (a + b)v = av + bv
+pub fn check_distributivity<V, F>(vectors: &[V], scalars: &[F]) -> Result<(), AxiomViolation<V, F>>
+where
+    V: Add<Output = V> + MulScalar<F> + ApproxEq + Clone,
+    F: Add<Output = F> + Clone,
+{
+    for a in scalars {
+        for u in vectors {
+            for v in vectors {
+                let left = (u.clone() + v.clone()).mul(a.clone());
+                let right = u.clone().mul(a.clone()) + v.clone().mul(a.clone());
+                if !left.approx_eq(&right) {
+                    return Err(AxiomViolation::VectorDistributivity {
+                        a: a.clone(),
+                        u: u.clone(),
+                        v: v.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    for a in scalars {
+        for b in scalars {
+            for v in vectors {
+                let left = v.clone().mul(a.clone() + b.clone());
+                let right = v.clone().mul(a.clone()) + v.clone().mul(b.clone());
+                if !left.approx_eq(&right) {
+                    return Err(AxiomViolation::ScalarDistributivity {
+                        a: a.clone(),
+                        b: b.clone(),
+                        v: v.clone(),
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// All of the vector space axioms, in the order of the definition
+pub fn verify_vector_space<V, F>(vectors: &[V], scalars: &[F]) -> Result<(), AxiomViolation<V, F>>
+where
+    V: Add<Output = V> + Neg<Output = V> + Zero + MulScalar<F> + ApproxEq + Clone,
+    F: Add<Output = F> + Mul<Output = F> + One + Clone,
+{
+    check_commutativity(vectors)?;
+    check_associativity(vectors)?;
+    check_additive_identity(vectors)?;
+    check_additive_inverse(vectors)?;
+    check_multiplicative_identity(vectors)?;
+    check_scalar_associativity(vectors, scalars)?;
+    check_distributivity(vectors, scalars)
+}
+
+#[cfg(test)]
+mod test {
+    use std::ops::{Add, Neg};
+
+    use super::{ApproxEq, AxiomViolation, check_commutativity, verify_vector_space};
+    use crate::chapters::c1::definitions::{
+        Associative, Commutative, Complex, Distributive, Identity, Inverse, List, MulIdent,
+        MulScalar, VectorSpace, Zero,
+    };
+
+    fn accept_field<T: VectorSpace<X>, X>() {}
+
+    #[test]
+    fn real_lists() {
+        let vectors = [
+            List::new([1.0, 2.0, 3.0]),
+            List::new([-0.5, 0.0, 4.0]),
+            List::new([1e3, -1e-3, 0.1]),
+            List::ZERO,
+        ];
+        let scalars = [0.0, 1.0, -2.5, 0.1, 1e4];
+        assert_eq!(verify_vector_space(&vectors, &scalars), Ok(()));
+    }
+
+    #[test]
+    fn complex_numbers() {
+        let samples = [
+            Complex::new(1.0, 2.0),
+            Complex::new(-0.5, 0.25),
+            Complex::new(0.0, -3.0),
+            Complex::ZERO,
+        ];
+        assert_eq!(verify_vector_space(&samples, &samples), Ok(()));
+    }
+
+    #[test]
+    fn approx_eq() {
+        assert!(1.0.approx_eq(&(1.0 + 1e-12)));
+        assert!(!1.0.approx_eq(&1.001));
+        assert!(1e12.approx_eq(&(1e12 + 1.0)));
+        assert!(!List::new([1.0, 2.0]).approx_eq(&List::new([1.0, 2.1])));
+    }
+
+    // Adds the second argument twice, but claims to be a vector space
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Lopsided(f64);
+
+    impl Add for Lopsided {
+        type Output = Self;
+
+        fn add(self, rhs: Self) -> Self::Output {
+            Lopsided(self.0 + 2.0 * rhs.0)
+        }
+    }
+
+    impl Neg for Lopsided {
+        type Output = Self;
+
+        fn neg(self) -> Self::Output {
+            Lopsided(-self.0)
+        }
+    }
+
+    impl Zero for Lopsided {
+        const ZERO: Self = Lopsided(0.0);
+    }
+
+    impl MulScalar<f64> for Lopsided {
+        fn mul(self, rhs: f64) -> Self {
+            Lopsided(self.0 * rhs)
+        }
+    }
+
+    impl ApproxEq for Lopsided {
+        fn approx_eq(&self, other: &Self) -> bool {
+            self.0.approx_eq(&other.0)
+        }
+    }
+
+    impl Commutative for Lopsided {}
+    impl Associative for Lopsided {}
+    impl Identity for Lopsided {}
+    impl Inverse for Lopsided {}
+    impl MulIdent<f64> for Lopsided {}
+    impl Distributive<f64> for Lopsided {}
+
+    // Scales by the square of the scalar, only (a + b)v = av + bv fails
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Squared(f64);
+
+    impl Add for Squared {
+        type Output = Self;
+
+        fn add(self, rhs: Self) -> Self::Output {
+            Squared(self.0 + rhs.0)
+        }
+    }
+
+    impl Neg for Squared {
+        type Output = Self;
+
+        fn neg(self) -> Self::Output {
+            Squared(-self.0)
+        }
+    }
+
+    impl Zero for Squared {
+        const ZERO: Self = Squared(0.0);
+    }
+
+    impl MulScalar<f64> for Squared {
+        fn mul(self, rhs: f64) -> Self {
+            Squared(self.0 * rhs * rhs)
+        }
+    }
+
+    impl ApproxEq for Squared {
+        fn approx_eq(&self, other: &Self) -> bool {
+            self.0.approx_eq(&other.0)
+        }
+    }
+
+    #[test]
+    fn rejects_broken_types() {
+        // The marker traits alone can't tell
+        accept_field::<Lopsided, f64>();
+
+        let samples = [Lopsided(1.0), Lopsided(2.0)];
+        assert_eq!(
+            verify_vector_space(&samples, &[1.0, 2.0]),
+            Err(AxiomViolation::Commutativity {
+                u: Lopsided(1.0),
+                v: Lopsided(2.0)
+            })
+        );
+        assert!(check_commutativity::<_, f64>(&[Lopsided(0.0)]).is_ok());
+
+        let samples = [Squared(1.0), Squared(-3.0)];
+        assert_eq!(
+            verify_vector_space(&samples, &[1.0, 2.0]),
+            Err(AxiomViolation::ScalarDistributivity {
+                a: 1.0,
+                b: 1.0,
+                v: Squared(1.0)
+            })
+        );
+    }
+}
@@ -1,13 +1,109 @@
 use std::{
-    fmt::{Debug, Display}, ops::{Add, AddAssign, Div, Index, Mul, Neg, Sub}, process::Output, slice::SliceIndex
+    fmt::{Alignment, Debug, Display, Formatter, Write}, mem::MaybeUninit, num::Wrapping, ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign}, slice::SliceIndex
 };
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq)]
 pub struct Complex<T> {
     a: T,
     b: T,
 }
 
+impl<T> Complex<T> {
+    pub const fn new(re: T, im: T) -> Self {
+        Self { a: re, b: im }
+    }
+
+    pub fn re(&self) -> &T {
+        &self.a
+    }
+
+    pub fn im(&self) -> &T {
+        &self.b
+    }
+}
+
+// Polar form and powers, all angles are principal values in (-pi, pi]
+impl Complex<f64> {
+    pub fn from_polar(r: f64, theta: f64) -> Self {
+        Self::new(r * theta.cos(), r * theta.sin())
+    }
+
+    pub fn abs(self) -> f64 {
+        self.a.hypot(self.b)
+    }
+
+    // The negative real axis gives pi for +0i and -pi for -0i
+    pub fn arg(self) -> f64 {
+        self.b.atan2(self.a)
+    }
+
+    // e^(a + bi) = e^a (cos b + i sin b)
+    pub fn exp(self) -> Self {
+        Self::from_polar(self.a.exp(), self.b)
+    }
+
+    // Principal logarithm ln|z| + i arg z, the branch cut is the negative real axis
+    pub fn ln(self) -> Self {
+        Self::new(self.abs().ln(), self.arg())
+    }
+
+    // Principal root, computed without going through the angle so that
+    // the roots of negative reals come out as exact imaginary numbers.
+    // Of sqrt((r + a) / 2) and sqrt((r - a) / 2) only the one with |a| doesn't cancel,
+    // the other component follows from 2 re im = b.
+    pub fn sqrt(self) -> Self {
+        let t = ((self.abs() + self.a.abs()) / 2.0).sqrt();
+        if t == 0.0 {
+            return Self::new(0.0, self.b);
+        }
+
+        let other = self.b / (2.0 * t);
+        if self.a >= 0.0 {
+            Self::new(t, other)
+        } else if self.b.is_sign_negative() {
+            Self::new(other.abs(), -t)
+        } else {
+            Self::new(other.abs(), t)
+        }
+    }
+
+    // Exponentiation by squaring, negative powers go through the inverse
+    pub fn powi(self, n: i32) -> Self {
+        let mut base = if n < 0 { self.inverse() } else { self };
+        let mut exp = n.unsigned_abs();
+        let mut result = Self::ONE;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+        result
+    }
+
+    // |z|^x (cos(x arg z) + i sin(x arg z))
+    pub fn powf(self, x: f64) -> Self {
+        Self::from_polar(self.abs().powf(x), self.arg() * x)
+    }
+}
+
+impl<T> From<(T, T)> for Complex<T> {
+    fn from((re, im): (T, T)) -> Self {
+        Self::new(re, im)
+    }
+}
+
+// A real number as x + 0i
+impl<T> From<T> for Complex<T>
+where
+    T: Zero,
+{
+    fn from(re: T) -> Self {
+        Self::new(re, T::ZERO)
+    }
+}
+
 impl<T> Debug for Complex<T>
 where
     T: Debug,
@@ -40,6 +136,29 @@ where
     }
 }
 
+impl<T> AddAssign for Complex<T>
+where
+    T: Add<Output = T> + Copy,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T> Sub for Complex<T>
+where
+    T: Sub<Output = T>,
+{
+    type Output = Complex<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Complex {
+            a: self.a - rhs.a,
+            b: self.b - rhs.b,
+        }
+    }
+}
+
 impl<T> Mul for Complex<T>
 where
     T: Mul<Output = T>,
@@ -57,87 +176,664 @@ where
     }
 }
 
-impl<T> Neg for Complex<T>
+impl<T> Neg for Complex<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Complex<T>;
+
+    fn neg(self) -> Self::Output {
+        Self::Output {
+            a: -self.a,
+            b: -self.b,
+        }
+    }
+}
+
+impl<T> Complex<T>
+where
+    T: Copy,
+{
+    // a - bi
+    pub fn conjugate(self) -> Self
+    where
+        T: Neg<Output = T>,
+    {
+        Self {
+            a: self.a,
+            b: -self.b,
+        }
+    }
+
+    // |z|^2 = z * conj(z) = a^2 + b^2
+    pub fn norm_squared(self) -> T
+    where
+        T: Mul<Output = T> + Add<Output = T>,
+    {
+        self.a * self.a + self.b * self.b
+    }
+}
+
+pub trait AddInverse {
+    fn negate(self) -> Self;
+}
+
+impl AddInverse for f64 {
+    fn negate(self) -> Self {
+        -self
+    }
+}
+
+impl AddInverse for i128 {
+    fn negate(self) -> Self {
+        -self
+    }
+}
+
+impl AddInverse for f32 {
+    fn negate(self) -> Self {
+        -self
+    }
+}
+
+impl AddInverse for i8 {
+    fn negate(self) -> Self {
+        -self
+    }
+}
+
+impl AddInverse for i16 {
+    fn negate(self) -> Self {
+        -self
+    }
+}
+
+impl AddInverse for i32 {
+    fn negate(self) -> Self {
+        -self
+    }
+}
+
+impl AddInverse for i64 {
+    fn negate(self) -> Self {
+        -self
+    }
+}
+
+impl AddInverse for isize {
+    fn negate(self) -> Self {
+        -self
+    }
+}
+
+impl AddInverse for Wrapping<i32> {
+    fn negate(self) -> Self {
+        -self
+    }
+}
+
+impl AddInverse for Wrapping<i64> {
+    fn negate(self) -> Self {
+        -self
+    }
+}
+
+impl<T> AddInverse for Complex<T>
+where
+    T: AddInverse,
+{
+    fn negate(self) -> Self {
+        Self {
+            a: self.a.negate(),
+            b: self.b.negate(),
+        }
+    }
+}
+
+pub trait MulInverse {
+    fn inverse(self) -> Self;
+}
+
+impl MulInverse for f64 {
+    fn inverse(self) -> Self {
+        1.0 / self
+    }
+}
+
+impl MulInverse for f32 {
+    fn inverse(self) -> Self {
+        1.0 / self
+    }
+}
+
+// 1 / (a + bi) = (a - bi) / (a^2 + b^2)
+// The inverse of zero divides by zero in T, giving NaNs for floats, see checked_inverse.
+// T needs inverses itself, integer division would truncate 1 / 2 to 0
+impl<T> MulInverse for Complex<T>
+where
+    T: Mul<Output = T> + Add<Output = T> + Div<Output = T> + Neg<Output = T> + MulInverse + Copy,
+{
+    fn inverse(self) -> Self {
+        let norm_squared = self.norm_squared();
+        let conjugate = self.conjugate();
+        Self {
+            a: conjugate.a / norm_squared,
+            b: conjugate.b / norm_squared,
+        }
+    }
+}
+
+impl<T> Complex<T>
+where
+    T: Mul<Output = T> + Add<Output = T> + Div<Output = T> + Neg<Output = T> + MulInverse + Copy,
+{
+    // None for zero instead of dividing by zero
+    pub fn checked_inverse(self) -> Option<Self>
+    where
+        T: Zero + PartialEq,
+    {
+        if self.norm_squared() == T::ZERO {
+            None
+        } else {
+            Some(self.inverse())
+        }
+    }
+}
+
+impl<T> Div for Complex<T>
+where
+    T: Mul<Output = T>
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + MulInverse
+        + Copy,
+{
+    type Output = Complex<T>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.divide(rhs)
+    }
+}
+
+impl<T> SubAssign for Complex<T>
+where
+    T: Sub<Output = T> + Copy,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T> MulAssign for Complex<T>
+where
+    T: Mul<Output = T> + Sub<Output = T> + Add<Output = T> + Copy,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> DivAssign for Complex<T>
+where
+    T: Mul<Output = T>
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + MulInverse
+        + Copy,
+{
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+// Scaling by a real number, C as a vector space over R
+impl Mul<f64> for Complex<f64> {
+    type Output = Complex<f64>;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Complex {
+            a: self.a * rhs,
+            b: self.b * rhs,
+        }
+    }
+}
+
+impl Mul<Complex<f64>> for f64 {
+    type Output = Complex<f64>;
+
+    fn mul(self, rhs: Complex<f64>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Div<f64> for Complex<f64> {
+    type Output = Complex<f64>;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        Complex {
+            a: self.a / rhs,
+            b: self.b / rhs,
+        }
+    }
+}
+
+
+// Lists
+#[derive(Clone, Copy, PartialEq)]
+pub struct List<T, const N: usize> {
+    elems: [T; N],
+}
+
+impl<T, const N: usize> List<T, N> {
+    pub const fn new(elems: [T; N]) -> Self {
+        Self { elems }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.elems
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.elems
+    }
+
+    pub fn into_array(self) -> [T; N] {
+        self.elems
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.elems.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.elems.iter_mut()
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for List<T, N> {
+    fn from(elems: [T; N]) -> Self {
+        Self::new(elems)
+    }
+}
+
+impl<T, const N: usize, Idx> Index<Idx> for List<T, N>
+where
+    Idx: SliceIndex<[T], Output = T>,
+{
+    type Output = T;
+
+    fn index(&self, index: Idx) -> &Self::Output {
+        self.elems.index(index)
+    }
+}
+
+impl<T, const N: usize, Idx> IndexMut<Idx> for List<T, N>
+where
+    Idx: SliceIndex<[T], Output = T>,
+{
+    fn index_mut(&mut self, index: Idx) -> &mut Self::Output {
+        self.elems.index_mut(index)
+    }
+}
+
+impl<T, const N: usize> Debug for List<T, N>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(")?;
+        for (i, elem) in self.elems.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:?}", elem)?;
+        }
+        write!(f, ")")
+    }
+}
+
+// Precision and the + flag go to every element, width, fill and alignment to the list as a whole.
+// {:#} prints [x1, x2, ...] so the output can be pasted into Python
+impl<T, const N: usize> Display for List<T, N>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (open, close) = if f.alternate() { ('[', ']') } else { ('(', ')') };
+
+        let mut out = String::new();
+        out.push(open);
+        for (i, elem) in self.elems.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            write_forwarded(&mut out, elem, f)?;
+        }
+        out.push(close);
+
+        pad_composite(f, &out)
+    }
+}
+
+// Writes x with the precision and sign flags of f
+pub(crate) fn write_forwarded<T: Display>(
+    out: &mut String,
+    x: &T,
+    f: &Formatter<'_>,
+) -> std::fmt::Result {
+    match (f.precision(), f.sign_plus()) {
+        (Some(p), true) => write!(out, "{x:+.p$}"),
+        (Some(p), false) => write!(out, "{x:.p$}"),
+        (None, true) => write!(out, "{x:+}"),
+        (None, false) => write!(out, "{x}"),
+    }
+}
+
+// Like Formatter::pad, but without truncating to the precision
+pub(crate) fn pad_composite(f: &mut Formatter<'_>, s: &str) -> std::fmt::Result {
+    let len = s.chars().count();
+    let padding = f.width().unwrap_or(0).saturating_sub(len);
+    let (before, after) = match f.align() {
+        None | Some(Alignment::Left) => (0, padding),
+        Some(Alignment::Right) => (padding, 0),
+        Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+    };
+
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    f.write_str(s)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+// Constructors, these don't need T to be Copy or const constructible
+impl<T, const N: usize> List<T, N> {
+    pub fn from_fn(f: impl FnMut(usize) -> T) -> Self {
+        Self {
+            elems: core::array::from_fn(f),
+        }
+    }
+
+    pub fn repeat_with(mut f: impl FnMut() -> T) -> Self {
+        Self::from_fn(|_| f())
+    }
+
+    // Stops at the first error, dropping the elements built so far
+    pub fn try_from_fn<E>(mut f: impl FnMut(usize) -> Result<T, E>) -> Result<Self, E> {
+        // Drops the initialized prefix when f returns an error or panics
+        struct Guard<'a, T, const N: usize> {
+            elems: &'a mut [MaybeUninit<T>; N],
+            initialized: usize,
+        }
+
+        impl<T, const N: usize> Drop for Guard<'_, T, N> {
+            fn drop(&mut self) {
+                for elem in &mut self.elems[..self.initialized] {
+                    // SAFETY: the first `initialized` elements have been written
+                    unsafe { elem.assume_init_drop() };
+                }
+            }
+        }
+
+        let mut elems = [const { MaybeUninit::uninit() }; N];
+        let mut guard = Guard {
+            elems: &mut elems,
+            initialized: 0,
+        };
+        while guard.initialized < N {
+            let elem = f(guard.initialized)?;
+            guard.elems[guard.initialized].write(elem);
+            guard.initialized += 1;
+        }
+        core::mem::forget(guard);
+
+        Ok(Self {
+            // SAFETY: the loop above wrote all N elements
+            elems: elems.map(|elem| unsafe { elem.assume_init() }),
+        })
+    }
+
+    // Takes exactly N elements, anything else is an error. Stops at the first element too many,
+    // so an unbounded iterator is rejected instead of being drained
+    pub fn try_from_iter(iter: impl IntoIterator<Item = T>) -> Result<Self, WrongLength> {
+        let mut iter = iter.into_iter();
+        let list = Self::try_from_fn(|i| {
+            iter.next().ok_or(WrongLength {
+                expected: N,
+                found: i,
+            })
+        })?;
+        match iter.next() {
+            None => Ok(list),
+            Some(_) => Err(WrongLength {
+                expected: N,
+                found: N + 1,
+            }),
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> List<U, N> {
+        List {
+            elems: self.elems.map(f),
+        }
+    }
+
+    // Combines the elements pairwise
+    pub fn zip_with<U, R>(self, rhs: List<U, N>, mut f: impl FnMut(T, U) -> R) -> List<R, N> {
+        let mut pairs = self.elems.into_iter().zip(rhs.elems);
+        List::from_fn(|_| {
+            let (a, b) = pairs.next().unwrap();
+            f(a, b)
+        })
+    }
+
+    pub fn fold<B>(self, init: B, f: impl FnMut(B, T) -> B) -> B {
+        self.elems.into_iter().fold(init, f)
+    }
+}
+
+// An iterator or Vec of the wrong length was turned into a List.
+// When there were too many elements found is only a lower bound, iterators are not consumed
+// past the first extra element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrongLength {
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl Display for WrongLength {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.found > self.expected {
+            write!(f, "expected {} elements, found at least {}", self.expected, self.found)
+        } else {
+            write!(f, "expected {} elements, found {}", self.expected, self.found)
+        }
+    }
+}
+
+impl std::error::Error for WrongLength {}
+
+impl<T, const N: usize> TryFrom<Vec<T>> for List<T, N> {
+    type Error = WrongLength;
+
+    fn try_from(vec: Vec<T>) -> Result<Self, Self::Error> {
+        let found = vec.len();
+        <[T; N]>::try_from(vec)
+            .map(Self::new)
+            .map_err(|_| WrongLength { expected: N, found })
+    }
+}
+
+impl<T, const N: usize> IntoIterator for List<T, N> {
+    type Item = T;
+    type IntoIter = std::array::IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elems.into_iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a List<T, N> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elems.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut List<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elems.iter_mut()
+    }
+}
+
+// In place versions of the operators, these avoid copying the whole array around for large N
+impl<T, const N: usize> List<T, N>
+where
+    T: Copy,
+{
+    pub fn add_assign_ref(&mut self, rhs: &Self)
+    where
+        T: Add<Output = T>,
+    {
+        for (a, b) in self.elems.iter_mut().zip(rhs.elems.iter()) {
+            *a = *a + *b;
+        }
+    }
+
+    pub fn scale_mut(&mut self, rhs: T)
+    where
+        T: Mul<Output = T>,
+    {
+        for a in self.elems.iter_mut() {
+            *a = *a * rhs;
+        }
+    }
+
+    pub fn neg_mut(&mut self)
+    where
+        T: Neg<Output = T>,
+    {
+        for a in self.elems.iter_mut() {
+            *a = -*a;
+        }
+    }
+
+    // self = a * x + self
+    pub fn axpy(&mut self, a: T, x: &Self)
+    where
+        T: Add<Output = T> + Mul<Output = T>,
+    {
+        for (y, x) in self.elems.iter_mut().zip(x.elems.iter()) {
+            *y = a * *x + *y;
+        }
+    }
+
+    // sum_i self_i rhs_i with every entry converted to W before multiplying, e.g. W = i128
+    // for i64 lists, so neither the products nor the sum overflow
+    pub fn dot_wide<W>(&self, rhs: &Self) -> W
+    where
+        T: Into<W>,
+        W: Add<Output = W> + Mul<Output = W> + Zero,
+    {
+        let products = self.elems.iter().zip(rhs.elems.iter());
+        products.fold(W::ZERO, |acc, (&a, &b)| acc + a.into() * b.into())
+    }
+}
+
+impl<T, const N: usize> Add for List<T, N>
 where
-    T: Neg<Output = T>,
+    T: Add<Output = T>,
 {
-    type Output = Complex<T>;
+    type Output = Self;
 
-    fn neg(self) -> Self::Output {
-        Self::Output {
-            a: -self.a,
-            b: -self.b,
-        }
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a + b)
     }
 }
 
-pub trait AddInverse {
-    fn negate(self) -> Self;
-}
-
-impl AddInverse for f64 {
-    fn negate(self) -> Self {
-        -self
+impl<T, const N: usize> AddAssign for List<T, N>
+where
+    T: Add<Output = T> + Copy,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.add_assign_ref(&rhs);
     }
 }
 
-impl AddInverse for Complex<f64> {
-    fn negate(self) -> Self {
-        Self {
-            a: -self.a,
-            b: -self.b,
+impl<T, const N: usize> SubAssign for List<T, N>
+where
+    T: Sub<Output = T> + Copy,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        for (a, b) in self.elems.iter_mut().zip(rhs.elems) {
+            *a = *a - b;
         }
     }
 }
 
-pub trait MulInverse {
-    fn inverse(self) -> Self;
-}
+// Scalar multiplication as an operator, v * a
+impl<T, const N: usize> Mul<T> for List<T, N>
+where
+    T: Mul<Output = T> + Copy,
+{
+    type Output = Self;
 
-impl MulInverse for f64 {
-    fn inverse(self) -> Self {
-        1.0 / self
+    fn mul(self, rhs: T) -> Self::Output {
+        self.map(|a| a * rhs)
     }
 }
 
-impl MulInverse for Complex<f64> {
-    fn inverse(self) -> Self {
-        Self {
-            a: 1.0 / self.a,
-            b: 1.0 / self.b,
-        }
+impl<T, const N: usize> MulAssign<T> for List<T, N>
+where
+    T: Mul<Output = T> + Copy,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        self.scale_mut(rhs);
     }
 }
 
+// a * v, only for concrete scalars since the orphan rule forbids impl<T> Mul<List<T, N>> for T
+impl<const N: usize> Mul<List<f64, N>> for f64 {
+    type Output = List<f64, N>;
 
-// Lists
-#[derive(Clone, Copy)]
-pub struct List<T, const N: usize> {
-    elems: [T; N],
+    fn mul(self, rhs: List<f64, N>) -> Self::Output {
+        rhs * self
+    }
 }
 
-impl<T, const N: usize, Idx> Index<Idx> for List<T, N>
+impl<T, const N: usize> Div<T> for List<T, N>
 where
-    Idx: SliceIndex<[T], Output = T>,
+    T: Div<Output = T> + Copy,
 {
-    type Output = T;
+    type Output = Self;
 
-    fn index(&self, index: Idx) -> &Self::Output {
-        self.elems.index(index)
+    fn div(mut self, rhs: T) -> Self::Output {
+        self /= rhs;
+        self
     }
 }
 
-impl<T, const N: usize> Add for List<T, N>
+impl<T, const N: usize> DivAssign<T> for List<T, N>
 where
-    T: Add<Output = T> + Copy,
+    T: Div<Output = T> + Copy,
 {
-    type Output = Self;
-
-    fn add(mut self, rhs: Self) -> Self::Output {
-        for (a, b) in self.elems.iter_mut().zip(rhs.elems.into_iter()) {
-            *a = *a + b;
+    fn div_assign(&mut self, rhs: T) {
+        for a in self.elems.iter_mut() {
+            *a = *a / rhs;
         }
-        self
     }
 }
 
@@ -174,10 +870,46 @@ impl Zero for f64 {
     const ZERO: Self = 0.0;
 }
 
+impl Zero for f32 {
+    const ZERO: Self = 0.0;
+}
+
 impl Zero for isize {
     const ZERO: Self = 0;
 }
 
+impl Zero for i8 {
+    const ZERO: Self = 0;
+}
+
+impl Zero for i16 {
+    const ZERO: Self = 0;
+}
+
+impl Zero for i32 {
+    const ZERO: Self = 0;
+}
+
+impl Zero for i64 {
+    const ZERO: Self = 0;
+}
+
+impl Zero for i128 {
+    const ZERO: Self = 0;
+}
+
+impl Zero for u128 {
+    const ZERO: Self = 0;
+}
+
+impl Zero for Wrapping<i32> {
+    const ZERO: Self = Wrapping(0);
+}
+
+impl Zero for Wrapping<i64> {
+    const ZERO: Self = Wrapping(0);
+}
+
 impl<T> Zero for Complex<T>
 where
     T: Zero,
@@ -209,17 +941,53 @@ impl One for isize {
     const ONE: Self = 1;
 }
 
+impl One for i8 {
+    const ONE: Self = 1;
+}
+
+impl One for i16 {
+    const ONE: Self = 1;
+}
+
+impl One for i32 {
+    const ONE: Self = 1;
+}
+
+impl One for i64 {
+    const ONE: Self = 1;
+}
+
 impl One for f64 {
     const ONE: Self = 1.0;
 }
 
+impl One for f32 {
+    const ONE: Self = 1.0;
+}
+
+impl One for i128 {
+    const ONE: Self = 1;
+}
+
+impl One for u128 {
+    const ONE: Self = 1;
+}
+
+impl One for Wrapping<i32> {
+    const ONE: Self = Wrapping(1);
+}
+
+impl One for Wrapping<i64> {
+    const ONE: Self = Wrapping(1);
+}
+
 impl<T> One for Complex<T>
 where
-    T: One,
+    T: One + Zero,
 {
     const ONE: Self = Complex {
         a: T::ONE,
-        b: T::ONE,
+        b: T::ZERO,
     };
 }
 
@@ -237,9 +1005,7 @@ where
     type Output = List<T, N>;
 
     fn neg(self) -> Self::Output {
-        Self {
-            elems: self.elems.map(|x| -x),
-        }
+        self.map(|a| -a)
     }
 }
 
@@ -259,9 +1025,13 @@ impl<T> MulScalar<T> for T where T: Mul<T, Output = T> {
 
 impl<T, const N: usize> MulScalar<T> for List<T, N> where T: Mul<Output = T> + Copy {
     fn mul(self, rhs: T) -> Self {
-        Self { 
-            elems: self.elems.map(|x| x * rhs)
-        }
+        self * rhs
+    }
+}
+
+impl MulScalar<f64> for Complex<f64> {
+    fn mul(self, rhs: f64) -> Self {
+        self * rhs
     }
 }
 
@@ -272,24 +1042,63 @@ pub trait Commutative : Add<Output = Self> + Sized {}
 impl<T> Commutative for Complex<T> where T: Commutative {}
 impl<T, const N: usize> Commutative for List<T, N> where T: Commutative, T: Copy {}
 impl Commutative for f64 {}
+impl Commutative for i128 {}
+impl Commutative for Wrapping<i32> {}
+impl Commutative for Wrapping<i64> {}
+impl Commutative for u128 {}
+impl Commutative for f32 {}
+impl Commutative for i8 {}
+impl Commutative for i16 {}
+impl Commutative for i32 {}
+impl Commutative for i64 {}
+impl Commutative for isize {}
 
 // (u + v) + w = u + (v + w)
 pub trait Associative : Add<Output = Self> + Sized {}
 impl<T> Associative for Complex<T> where T: Associative {}
 impl<T, const N: usize> Associative for List<T, N> where T: Associative, T: Copy {}
 impl Associative for f64 {}
+impl Associative for i128 {}
+impl Associative for Wrapping<i32> {}
+impl Associative for Wrapping<i64> {}
+impl Associative for u128 {}
+impl Associative for f32 {}
+impl Associative for i8 {}
+impl Associative for i16 {}
+impl Associative for i32 {}
+impl Associative for i64 {}
+impl Associative for isize {}
 
 // there exists an element 0 so that v+0 is v for all v in V
 pub trait Identity : Add<Output = Self> + Zero + Sized {}
 impl<T> Identity for Complex<T> where T: Identity {}
 impl<T, const N: usize> Identity for List<T, N> where T: Identity + Copy {}
 impl Identity for f64 {}
+impl Identity for i128 {}
+impl Identity for Wrapping<i32> {}
+impl Identity for Wrapping<i64> {}
+impl Identity for u128 {}
+impl Identity for f32 {}
+impl Identity for i8 {}
+impl Identity for i16 {}
+impl Identity for i32 {}
+impl Identity for i64 {}
+impl Identity for isize {}
 
 // For every v there exists a w so that v + w is 0
 pub trait Inverse : Add<Output = Self> + Neg<Output = Self> + Sized {}
 impl<T> Inverse for Complex<T> where T: Inverse {}
 impl<T, const N: usize> Inverse for List<T, N> where T: Inverse + Copy {}
 impl Inverse for f64 {}
+impl Inverse for i128 {}
+impl Inverse for Wrapping<i32> {}
+impl Inverse for Wrapping<i64> {}
+impl Inverse for f32 {}
+impl Inverse for i8 {}
+impl Inverse for i16 {}
+impl Inverse for i32 {}
+impl Inverse for i64 {}
+impl Inverse for isize {}
 
 // 1 * v = v for all v
 pub trait MulIdent<X> : MulScalar<X> + Sized where X: One {
@@ -305,10 +1114,49 @@ impl<T, const N: usize> MulIdent<T> for List<T, N> where T: One, Self: MulScalar
 // This one is hard to implement
 pub trait Distributive<X>  where Self: Sized, Self: MulScalar<X>  {}
 impl Distributive<f64> for f64 {}
+impl Distributive<i128> for i128 {}
+impl Distributive<u128> for u128 {}
+impl Distributive<Wrapping<i32>> for Wrapping<i32> {}
+impl Distributive<Wrapping<i64>> for Wrapping<i64> {}
+impl Distributive<f32> for f32 {}
+impl Distributive<i8> for i8 {}
+impl Distributive<i16> for i16 {}
+impl Distributive<i32> for i32 {}
+impl Distributive<i64> for i64 {}
+impl Distributive<isize> for isize {}
 impl<T, X> Distributive<X> for Complex<T> where Self: MulScalar<X> {}
 impl<T, X, const N: usize> Distributive<X> for List<T, N> where Self: MulScalar<X> {}
 
 // A Vector Space V over F
+// F has to be a field, so every non zero scalar needs a multiplicative inverse.
+// The integers mod 2^k satisfy all the other axioms, but they only make a ring:
+/// ```compile_fail
+/// use std::num::Wrapping;
+/// use linear_algebra::chapters::c1::definitions::{List, VectorSpace};
+///
+/// fn accept_field<T: VectorSpace<X>, X>() {}
+/// accept_field::<List<Wrapping<i32>, 3>, Wrapping<i32>>();
+/// ```
+// The same goes for the Gaussian integers, Z[i] has no inverse of 2:
+/// ```compile_fail
+/// use linear_algebra::chapters::c1::definitions::{Complex, VectorSpace};
+///
+/// fn accept_field<T: VectorSpace<X>, X>() {}
+/// accept_field::<Complex<i64>, Complex<i64>>();
+/// ```
+/// ```compile_fail
+/// use std::num::Wrapping;
+/// use linear_algebra::chapters::c1::definitions::{Complex, VectorSpace};
+///
+/// fn accept_field<T: VectorSpace<X>, X>() {}
+/// accept_field::<Complex<Wrapping<i64>>, Complex<Wrapping<i64>>>();
+/// ```
+/// ```compile_fail
+/// use linear_algebra::chapters::c1::definitions::{Complex, List, VectorSpace};
+///
+/// fn accept_field<T: VectorSpace<X>, X>() {}
+/// accept_field::<List<Complex<i32>, 2>, Complex<i32>>();
+/// ```
 pub trait VectorSpace<F> {}
 impl<V, F> VectorSpace<F> for V where 
     V: Commutative,
@@ -317,22 +1165,541 @@ impl<V, F> VectorSpace<F> for V where
     V: Inverse,
     V: MulIdent<F>,
     V: Distributive<F>,
-    F: One {}
+    F: One + MulInverse {}
 
 
 #[cfg(test)]
 mod test {
-    use super::{Complex, List, MulIdent, MulInverse, MulScalar, VectorSpace};
-    use super::Divide;
+    use std::{
+        cell::Cell,
+        num::Wrapping,
+        panic::{self, AssertUnwindSafe},
+    };
+
+    use crate::chapters::c4::polynomial::Polynomial;
+
+    use super::{
+        Associative, Commutative, Complex, Distributive, Divide, Identity, Inverse, List, MulIdent,
+        MulInverse, MulScalar, One, VectorSpace, WrongLength, Zero,
+    };
 
     #[test]
     fn scratch_pad() {
         accept_field::<List<f64, 3>, f64>(); // Oke
         accept_field::<super::Complex<f64>, super::Complex<f64>>(); // C is a Vector space over C
         accept_field::<f64, f64>(); // R is a Vector space over R
-        // accept_field::<super::Complex<f64>, f64>(); // C is not a vector space over R, no multiplicative identity (or maybe there is?)
+        accept_field::<Polynomial<f64>, f64>(); // P(R) is a Vector space over R
+        accept_field::<super::Complex<f64>, f64>(); // C is a Vector space over R as well
+    }
+
+    #[test]
+    fn wide_integers() {
+        accept_ring::<List<i128, 3>, i128>();
+        accept_ring::<Complex<i128>, Complex<i128>>();
+        accept_ring::<i128, i128>();
+    }
+
+    #[test]
+    fn wide_dot_product() {
+        let u = List::new([i64::MAX, i64::MAX, -3]);
+        let v = List::new([2, i64::MAX, 5]);
+        let expected = 2 * i64::MAX as i128 + (i64::MAX as i128).pow(2) - 15;
+        assert_eq!(u.dot_wide::<i128>(&v), expected);
+
+        let small = List::new([1i8, -2, 3]);
+        assert_eq!(small.dot_wide::<i64>(&small), 14);
+        assert_eq!(List::<i32, 0>::new([]).dot_wide::<i64>(&List::new([])), 0);
+    }
+
+    #[test]
+    fn narrow_integers() {
+        accept_ring::<List<i8, 3>, i8>();
+        accept_ring::<List<i32, 2>, i32>();
+        accept_ring::<Complex<i64>, Complex<i64>>();
+        accept_ring::<isize, isize>();
+        accept_field::<List<f32, 3>, f32>();
+    }
+
+    #[test]
+    fn gaussian_integers_past_i64() {
+        // (m + mi)(m - mi) = 2m^2, which wraps in i64 but not in i128
+        let m = i64::MAX as i128;
+        let z = Complex { a: m, b: m };
+        let w = Complex { a: m, b: -m };
+
+        let p = z * w;
+        assert_eq!(p.a, 2 * m * m);
+        assert_eq!(p.b, 0);
+
+        let q = z * z;
+        assert_eq!(q.a, 0);
+        assert_eq!(q.b, 2 * m * m);
+        assert!(q.b > i64::MAX as i128);
+    }
+
+    #[test]
+    fn wrapping_integers() {
+        // Z mod 2^k only fails the field axioms, see the compile_fail example on VectorSpace
+        accept_ring::<List<Wrapping<i32>, 3>, Wrapping<i32>>();
+        accept_ring::<Complex<Wrapping<i64>>, Complex<Wrapping<i64>>>();
+        accept_ring::<Wrapping<i64>, Wrapping<i64>>();
+    }
+
+    #[test]
+    fn wrapping_vector_addition() {
+        let u = List {
+            elems: [Wrapping(i32::MAX), Wrapping(i32::MIN), Wrapping(7)],
+        };
+        let v = List {
+            elems: [Wrapping(1), Wrapping(-1), Wrapping(-7)],
+        };
+
+        let w = u + v;
+        assert_eq!(w[0], Wrapping(i32::MIN));
+        assert_eq!(w[1], Wrapping(i32::MAX));
+        assert_eq!(w[2], Wrapping(0));
+
+        let n = -List {
+            elems: [Wrapping(i32::MIN), Wrapping(1), Wrapping(0)],
+        };
+        assert_eq!(n[0], Wrapping(i32::MIN));
+        assert_eq!(n[1], Wrapping(-1));
+        assert_eq!(n[2], Wrapping(0));
+    }
+
+    #[test]
+    fn wrapping_complex_multiplication() {
+        // (2^32 i)(2^32 i) = -2^64, which is 0 mod 2^64
+        let z = Complex {
+            a: Wrapping(0i64),
+            b: Wrapping(1i64 << 32),
+        };
+        let p = z * z;
+        assert_eq!(p.a, Wrapping(0));
+        assert_eq!(p.b, Wrapping(0));
+
+        let w = Complex {
+            a: Wrapping(i64::MAX),
+            b: Wrapping(1),
+        };
+        let q = w + Complex {
+            a: Wrapping(1),
+            b: Wrapping(0),
+        };
+        assert_eq!(q.a, Wrapping(i64::MIN));
+        assert_eq!(q.b, Wrapping(1));
+    }
+
+    #[test]
+    fn in_place_matches_by_value() {
+        let u = List {
+            elems: core::array::from_fn::<f64, 64, _>(|i| i as f64 * 0.5 - 3.0),
+        };
+        let v = List {
+            elems: core::array::from_fn::<f64, 64, _>(|i| (i as f64).sin()),
+        };
+
+        let mut sum = u;
+        sum.add_assign_ref(&v);
+        assert_eq!(sum.elems, (u + v).elems);
+
+        let mut scaled = u;
+        scaled.scale_mut(-1.25);
+        assert_eq!(scaled.elems, MulScalar::mul(u, -1.25).elems);
+
+        let mut negated = u;
+        negated.neg_mut();
+        assert_eq!(negated.elems, (-u).elems);
+
+        let mut axpy = v;
+        axpy.axpy(3.0, &u);
+        assert_eq!(axpy.elems, (MulScalar::mul(u, 3.0) + v).elems);
+    }
+
+    #[test]
+    fn scalar_operators() {
+        let a = List::new([1.5, -2.0, 0.25]);
+        let b = List::new([4.0, 0.5, -8.0]);
+        let c = -3.0;
+        assert_eq!((a + b) * c, a * c + b * c);
+        assert_eq!(c * a, a * c);
+        assert_eq!(a * 2.0 / 2.0, a);
+        assert_eq!(MulScalar::mul(a, c), a * c);
+
+        let mut v = a;
+        v *= 4.0;
+        v /= 2.0;
+        v += b;
+        v -= a;
+        assert_eq!(v, a + b);
+    }
+
+    #[test]
+    fn mixed_real_complex() {
+        let z = Complex::new(1.5, -2.0);
+        let x = -0.75;
+        assert_eq!(z * x, z * Complex::new(x, 0.0));
+        assert_eq!(x * z, Complex::new(x, 0.0) * z);
+        assert_eq!(z / 2.0, z / Complex::new(2.0, 0.0));
+
+        let w = Complex::new(0.5, 3.0);
+        let mut u = z;
+        u *= w;
+        assert_eq!(u, z * w);
+        u /= w;
+        assert_close(u, z);
+        u -= w;
+        assert_eq!(u, z + -w);
+    }
+
+    #[test]
+    fn iteration() {
+        let mut v = List::new([3.0, 1.0, 4.0, 1.0, 5.0]);
+        assert_eq!(v.iter().copied().sum::<f64>(), 14.0);
+        assert_eq!(v.into_iter().collect::<Vec<_>>(), vec![3.0, 1.0, 4.0, 1.0, 5.0]);
+
+        for x in &mut v {
+            *x *= 2.0;
+        }
+        let mut seen = Vec::new();
+        for x in &v {
+            seen.push(*x);
+        }
+        assert_eq!(seen, vec![6.0, 2.0, 8.0, 2.0, 10.0]);
+        assert_eq!(v.fold(0.0, |acc, x| acc * 10.0 + x), 6.0 * 1e4 + 2e3 + 8e2 + 2e1 + 10.0);
+    }
+
+    #[test]
+    fn from_iterators() {
+        assert_eq!(List::try_from_iter(1..=3), Ok(List::new([1, 2, 3])));
+        assert_eq!(
+            List::<i32, 3>::try_from_iter(1..=2),
+            Err(WrongLength { expected: 3, found: 2 })
+        );
+        assert_eq!(
+            List::<i32, 3>::try_from_iter(1..=7),
+            Err(WrongLength { expected: 3, found: 4 })
+        );
+        assert_eq!(
+            List::<u8, 3>::try_from_iter(std::iter::repeat(1)),
+            Err(WrongLength { expected: 3, found: 4 })
+        );
+        assert_eq!(List::try_from(vec![0.5, 1.5]), Ok(List::new([0.5, 1.5])));
+        assert_eq!(
+            List::<f64, 2>::try_from(vec![0.5]),
+            Err(WrongLength { expected: 2, found: 1 })
+        );
+        assert_eq!(
+            WrongLength { expected: 2, found: 1 }.to_string(),
+            "expected 2 elements, found 1"
+        );
+        assert_eq!(
+            List::<f64, 1>::try_from(vec![0.5; 3]).unwrap_err().to_string(),
+            "expected 1 elements, found at least 3"
+        );
+    }
+
+    #[test]
+    fn combinators() {
+        let re = List::new([1.0, -2.0, 0.5]);
+        let im = List::new([0.0, 3.0, -1.0]);
+        let z: List<Complex<f64>, 3> = re.map(Complex::from);
+        assert_eq!(z, List::new([1.0, -2.0, 0.5].map(Complex::from)));
+        assert_eq!(
+            re.zip_with(im, Complex::new),
+            List::new([Complex::new(1.0, 0.0), Complex::new(-2.0, 3.0), Complex::new(0.5, -1.0)])
+        );
+
+        // Add and Neg work without Copy now
+        let words = List::new([String::from("a"), String::from("b")]);
+        let lengths = words.map(|w| w.len());
+        assert_eq!(lengths + lengths, List::new([2, 2]));
+    }
+
+    // Counts how many times it has been dropped
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn construct_non_copy() {
+        let words = List::<String, 3>::from_fn(|i| "ab".repeat(i));
+        assert_eq!(words[0], "");
+        assert_eq!(words[2], "abab");
+
+        let mut next = 0;
+        let counted = List::<Vec<usize>, 4>::repeat_with(|| {
+            next += 1;
+            vec![next; next]
+        });
+        assert_eq!(counted[3], vec![4; 4]);
+
+        let parsed = List::<u32, 3>::try_from_fn(|i| ["1", "20", "300"][i].parse());
+        assert_eq!(parsed.unwrap().elems, [1, 20, 300]);
+
+        let failed = List::<u32, 3>::try_from_fn(|i| ["1", "x", "300"][i].parse::<u32>());
+        assert!(failed.is_err());
+    }
+
+    #[test]
+    fn try_from_fn_drops_on_error() {
+        let drops = Cell::new(0);
+        let list = List::<DropCounter, 6>::try_from_fn(|i| {
+            if i == 3 {
+                Err(i)
+            } else {
+                Ok(DropCounter(&drops))
+            }
+        });
+        assert_eq!(list.err(), Some(3));
+        assert_eq!(drops.get(), 3);
+
+        let drops = Cell::new(0);
+        let list = List::<DropCounter, 6>::try_from_fn(|_| Ok::<_, ()>(DropCounter(&drops)));
+        assert_eq!(drops.get(), 0);
+        drop(list);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn try_from_fn_drops_on_panic() {
+        let drops = Cell::new(0);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            List::<DropCounter, 6>::try_from_fn(|i| {
+                if i == 4 {
+                    panic!("constructor failed");
+                }
+                Ok::<_, ()>(DropCounter(&drops))
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn display_flags() {
+        let v = List {
+            elems: [1.0, -2.5, 1.0 / 3.0],
+        };
+
+        assert_eq!(format!("{v}"), "(1, -2.5, 0.3333333333333333)");
+        assert_eq!(format!("{v:.2}"), "(1.00, -2.50, 0.33)");
+        assert_eq!(format!("{v:+.1}"), "(+1.0, -2.5, +0.3)");
+        assert_eq!(format!("{v:#.3}"), "[1.000, -2.500, 0.333]");
+        assert_eq!(format!("{v:>21.1}"), "     (1.0, -2.5, 0.3)");
+        assert_eq!(format!("{v:*^21.1}"), "**(1.0, -2.5, 0.3)***");
+        assert_eq!(format!("{v:<19.1}|"), "(1.0, -2.5, 0.3)   |");
+
+        let ints = List { elems: [-3, 0, 7] };
+        assert_eq!(format!("{ints:+}"), "(-3, +0, +7)");
+        assert_eq!(format!("{ints:#}"), "[-3, 0, 7]");
+        assert_eq!(format!("{:4}|", List { elems: [1] }), "(1) |");
+
+        let empty: List<f64, 0> = List { elems: [] };
+        assert_eq!(format!("{empty:#}"), "[]");
+    }
+
+    fn assert_close(z: Complex<f64>, w: Complex<f64>) {
+        assert!(
+            (z.a - w.a).abs() < 1e-12 && (z.b - w.b).abs() < 1e-12,
+            "{z} != {w}"
+        );
+    }
+
+    #[test]
+    fn complex_inverse() {
+        let values = [
+            (3.0, 4.0),
+            (-2.0, 0.5),
+            (0.25, -7.0),
+            (-1e-3, -1e3),
+            (5.0, 0.0),
+            (0.0, -2.0),
+        ];
+        for (a, b) in values {
+            let z = Complex { a, b };
+            assert_close(z * z.inverse(), Complex::ONE);
+            assert_close(z.inverse() * z, Complex::ONE);
+            assert_close(z / z, Complex::ONE);
+            assert_close(z.inverse().inverse(), z);
+        }
+
+        // 1 / (3 + 4i) = (3 - 4i) / 25
+        assert_close(Complex { a: 3.0, b: 4.0 }.inverse(), Complex { a: 0.12, b: -0.16 });
+        // i^-1 = -i
+        assert_close(Complex { a: 0.0, b: 1.0 }.inverse(), Complex { a: 0.0, b: -1.0 });
+    }
+
+    #[test]
+    fn complex_division() {
+        // (1 + 2i) / (3 - i) = (1 + 7i) / 10
+        let z = Complex { a: 1.0, b: 2.0 } / Complex { a: 3.0, b: -1.0 };
+        assert_close(z, Complex { a: 0.1, b: 0.7 });
+
+        let w = Complex { a: -4.0, b: 2.5 };
+        let v = Complex { a: 0.5, b: -1.5 };
+        assert_close((w / v) * v, w);
+        assert_close(w.divide(v), w / v);
+    }
+
+    #[test]
+    fn conjugate_and_norm() {
+        let z = Complex { a: 3.0, b: -4.0 };
+        assert_eq!(z.conjugate().b, 4.0);
+        assert_eq!(z.norm_squared(), 25.0);
+        assert_close(z * z.conjugate(), Complex { a: 25.0, b: 0.0 });
+    }
+
+    #[test]
+    fn zero_inverse() {
+        let zero = Complex::<f64>::ZERO;
+        assert!(zero.checked_inverse().is_none());
+        assert!(zero.inverse().a.is_nan() && zero.inverse().b.is_nan());
+        assert_close(
+            Complex { a: 0.0, b: 2.0 }.checked_inverse().unwrap(),
+            Complex { a: 0.0, b: -0.5 },
+        );
+    }
+
+    #[test]
+    fn complex_construction() {
+        let z = Complex::new(1.5, -2.0);
+        assert_eq!(*z.re(), 1.5);
+        assert_eq!(*z.im(), -2.0);
+        assert_eq!(z, Complex::from((1.5, -2.0)));
+        assert_eq!(Complex::from(3.0), Complex::new(3.0, 0.0));
+        assert_ne!(z, z.conjugate());
+        assert_eq!(Complex::<f64>::ONE, Complex::new(1.0, 0.0));
+    }
+
+    #[test]
+    fn list_debug() {
+        assert_eq!(format!("{:?}", List::new([1.0, -0.5])), "(1.0, -0.5)");
+        assert_eq!(
+            format!("{:?}", List::new([Complex::new(1, 2)])),
+            "((1 + 2i))"
+        );
+    }
+
+    #[test]
+    fn list_construction() {
+        let mut v = List::new([1.0, 2.0, 3.0]);
+        assert_eq!(v, List::from([1.0, 2.0, 3.0]));
+        assert_eq!(v.as_slice(), &[1.0, 2.0, 3.0]);
+        assert_eq!(v.iter().sum::<f64>(), 6.0);
+
+        v[1] = -4.0;
+        for x in v.iter_mut() {
+            *x *= 2.0;
+        }
+        assert_eq!(v, List::new([2.0, -8.0, 6.0]));
+        assert_ne!(v, List::ZERO);
+        assert_eq!(v + List::new([-2.0, 8.0, -6.0]), List::ZERO);
+    }
+
+    #[test]
+    fn complex_list_construction() {
+        let mut v = List::new([Complex::new(1.0, 1.0), Complex::from(2.0)]);
+        assert_eq!(v[0], Complex::new(1.0, 1.0));
+
+        v[1] *= Complex::new(0.0, 1.0);
+        assert_eq!(v[1], Complex::new(0.0, 2.0));
+        assert_eq!(
+            -v,
+            List::from([Complex::new(-1.0, -1.0), Complex::new(-0.0, -2.0)])
+        );
+        assert_eq!(v + List::ZERO, v);
+    }
+
+    #[test]
+    fn de_moivre() {
+        for z in [
+            Complex::new(1.5, -0.5),
+            Complex::new(-2.0, 3.0),
+            Complex::new(0.0, 1.0),
+            Complex::new(-0.7, -0.1),
+        ] {
+            assert_eq!(z.powi(0), Complex::ONE);
+            assert_eq!(z.powi(1), z);
+            assert_close(z.powi(3), z * z * z);
+            assert_close(z.powi(-2), (z * z).inverse());
+            assert_close(z.powi(7) * z.powi(-7), Complex::ONE);
+            assert_close(z.powf(3.0), z * z * z);
+            assert_close(z.powf(0.5), z.sqrt());
+
+            // (r e^(i theta))^n = r^n e^(i n theta)
+            let (r, theta) = (z.abs(), z.arg());
+            assert_close(z.powi(5), Complex::from_polar(r.powi(5), 5.0 * theta));
+        }
+        assert_close(Complex::new(0.0, 1.0).powi(2), Complex::new(-1.0, 0.0));
+    }
+
+    #[test]
+    fn polar_round_trip() {
+        for z in [
+            Complex::new(3.0, 4.0),
+            Complex::new(-3.0, 4.0),
+            Complex::new(-3.0, -4.0),
+            Complex::new(0.5, -1e-3),
+        ] {
+            assert_close(Complex::from_polar(z.abs(), z.arg()), z);
+            assert_close(z.ln().exp(), z);
+            assert_close(z.sqrt() * z.sqrt(), z);
+            assert!(z.sqrt().a >= 0.0);
+        }
+        assert_eq!(Complex::new(3.0, 4.0).abs(), 5.0);
+    }
+
+    #[test]
+    fn sqrt_small_imaginary_part() {
+        // Close to the real axis the naive half-angle formulas cancel to exactly 0
+        let z = Complex::new(1.0, 1e-10).sqrt();
+        assert_eq!(*z.re(), 1.0);
+        assert!((z.im() - 5e-11).abs() < 1e-25, "{z}");
+
+        let z = Complex::new(-1.0, 1e-10).sqrt();
+        assert!((z.re() - 5e-11).abs() < 1e-25, "{z}");
+        assert_eq!(*z.im(), 1.0);
+
+        let z = Complex::new(-1.0, -1e-10).sqrt();
+        assert!((z.re() - 5e-11).abs() < 1e-25, "{z}");
+        assert_eq!(*z.im(), -1.0);
+
+        let z = Complex::new(4.0, -1e-300).sqrt();
+        assert_eq!(*z.re(), 2.0);
+        assert!((z.im() + 2.5e-301).abs() < 1e-315, "{z}");
+
+        assert_eq!(Complex::new(0.0, 0.0).sqrt(), Complex::new(0.0, 0.0));
+        assert_close(Complex::new(0.0, 2.0).sqrt(), Complex::new(1.0, 1.0));
+    }
+
+    #[test]
+    fn branch_cut() {
+        let pi = std::f64::consts::PI;
+        assert_eq!(Complex::new(-1.0, 0.0).arg(), pi);
+        assert_eq!(Complex::new(-1.0, -0.0).arg(), -pi);
+        assert_close(Complex::new(-1.0, 0.0).ln(), Complex::new(0.0, pi));
+        assert_close(Complex::new(-1.0, -0.0).ln(), Complex::new(0.0, -pi));
+        assert_close(Complex::new(-std::f64::consts::E, 0.0).ln(), Complex::new(1.0, pi));
+
+        assert_eq!(Complex::new(-4.0, 0.0).sqrt(), Complex::new(0.0, 2.0));
+        assert_eq!(Complex::new(-4.0, -0.0).sqrt(), Complex::new(0.0, -2.0));
+        assert_eq!(Complex::new(9.0, 0.0).sqrt(), Complex::new(3.0, 0.0));
+        assert_close(Complex::new(0.0, pi).exp(), Complex::new(-1.0, 0.0));
     }
 
     fn accept_field<T: VectorSpace<X>, X>() {}
+
+    // Everything a vector space needs except for a multiplicative inverse in the scalars
+    fn accept_ring<T, X>()
+    where
+        T: Commutative + Associative + Identity + Inverse + MulIdent<X> + Distributive<X>,
+        X: One,
+    {
+    }
 }
 
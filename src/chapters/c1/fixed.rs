@@ -0,0 +1,224 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use super::definitions::{
+    AddInverse, Associative, Commutative, Distributive, Identity, Inverse, MulInverse, One, Zero,
+};
+
+// Q16.16 fixed point number, 16 integer bits (including sign) and 16 fractional bits.
+//
+// Overflow saturates at MIN / MAX instead of wrapping, so a result that is out of range
+// ends up at the nearest representable value rather than on the other side of the number line.
+// Multiplication and division round to the nearest representable value.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed32(i32);
+
+impl Fixed32 {
+    pub const FRAC_BITS: u32 = 16;
+    pub const MIN: Self = Self(i32::MIN);
+    pub const MAX: Self = Self(i32::MAX);
+    // Smallest positive value, 2^-16
+    pub const EPSILON: Self = Self(1);
+
+    pub const fn from_bits(bits: i32) -> Self {
+        Self(bits)
+    }
+
+    pub const fn to_bits(self) -> i32 {
+        self.0
+    }
+
+    pub const fn from_int(x: i16) -> Self {
+        Self((x as i32) << Self::FRAC_BITS)
+    }
+
+    // Rounds to the nearest representable value, saturating outside of the range (NaN becomes 0)
+    pub fn from_f64(x: f64) -> Self {
+        Self((x * (1u32 << Self::FRAC_BITS) as f64).round() as i32)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / (1u32 << Self::FRAC_BITS) as f64
+    }
+
+    fn saturate(x: i64) -> Self {
+        Self(x.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+    }
+}
+
+impl Debug for Fixed32 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.to_f64(), f)
+    }
+}
+
+impl Display for Fixed32 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.to_f64(), f)
+    }
+}
+
+impl Add for Fixed32 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Sub for Fixed32 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Mul for Fixed32 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        // Adding half an ulp before shifting rounds to nearest, ties towards +inf
+        let product = self.0 as i64 * rhs.0 as i64;
+        Self::saturate((product + (1 << (Self::FRAC_BITS - 1))) >> Self::FRAC_BITS)
+    }
+}
+
+impl Div for Fixed32 {
+    type Output = Self;
+
+    // Panics when dividing by zero, like the integer types
+    fn div(self, rhs: Self) -> Self::Output {
+        let num = (self.0 as i64) << Self::FRAC_BITS;
+        let den = rhs.0 as i64;
+        if den == 0 {
+            panic!("attempt to divide a Fixed32 by zero");
+        }
+
+        // Round half away from zero
+        let quotient = (num.abs() + den.abs() / 2) / den.abs();
+        if (num < 0) == (den < 0) {
+            Self::saturate(quotient)
+        } else {
+            Self::saturate(-quotient)
+        }
+    }
+}
+
+impl Neg for Fixed32 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(self.0.saturating_neg())
+    }
+}
+
+impl Zero for Fixed32 {
+    const ZERO: Self = Self(0);
+}
+
+impl One for Fixed32 {
+    const ONE: Self = Self(1 << Self::FRAC_BITS);
+}
+
+impl AddInverse for Fixed32 {
+    fn negate(self) -> Self {
+        -self
+    }
+}
+
+impl MulInverse for Fixed32 {
+    fn inverse(self) -> Self {
+        Self::ONE / self
+    }
+}
+
+// Note that saturation breaks associativity close to MIN / MAX, (MAX + 1) - 1 != MAX + (1 - 1)
+impl Commutative for Fixed32 {}
+impl Associative for Fixed32 {}
+impl Identity for Fixed32 {}
+impl Inverse for Fixed32 {}
+impl Distributive<Fixed32> for Fixed32 {}
+
+#[cfg(test)]
+mod test {
+    use super::Fixed32;
+    use crate::chapters::c1::definitions::{List, One, VectorSpace, Zero};
+
+    fn accept_field<T: VectorSpace<X>, X>() {}
+
+    #[test]
+    fn vector_space() {
+        accept_field::<Fixed32, Fixed32>();
+        accept_field::<List<Fixed32, 3>, Fixed32>();
+    }
+
+    #[test]
+    fn f64_round_trip() {
+        let quantization = Fixed32::EPSILON.to_f64() / 2.0;
+        for x in [0.0, 1.0, -1.0, 0.1, -2.71, 1234.5678, -32767.99, 1e-5] {
+            let y = Fixed32::from_f64(x).to_f64();
+            assert!((x - y).abs() <= quantization, "{x} came back as {y}");
+        }
+        assert_eq!(Fixed32::from_f64(1e9), Fixed32::MAX);
+        assert_eq!(Fixed32::from_f64(-1e9), Fixed32::MIN);
+        assert_eq!(Fixed32::from_int(-7).to_f64(), -7.0);
+    }
+
+    #[test]
+    fn dot_product() {
+        let u = [1.5, -2.0, 0.25].map(Fixed32::from_f64);
+        let v = [4.0, 0.5, 8.0].map(Fixed32::from_f64);
+
+        let dot = u
+            .iter()
+            .zip(v.iter())
+            .fold(Fixed32::ZERO, |acc, (&x, &y)| acc + x * y);
+        assert_eq!(dot, Fixed32::from_int(7));
+
+        let norm_sqr = u.iter().fold(Fixed32::ZERO, |acc, &x| acc + x * x);
+        assert_eq!(norm_sqr, Fixed32::from_f64(6.3125));
+    }
+
+    #[test]
+    fn mul_rounding() {
+        let half = Fixed32::from_f64(0.5);
+        let quarter = Fixed32::from_f64(0.25);
+
+        // 2^-16 * 0.5 is exactly half an ulp and rounds up, 2^-16 * 0.25 rounds down to zero
+        assert_eq!(Fixed32::EPSILON * half, Fixed32::EPSILON);
+        assert_eq!(Fixed32::EPSILON * quarter, Fixed32::ZERO);
+        assert_eq!(Fixed32::EPSILON * Fixed32::EPSILON, Fixed32::ZERO);
+        assert_eq!(-Fixed32::EPSILON * half, Fixed32::ZERO);
+        assert_eq!(Fixed32::ONE * Fixed32::EPSILON, Fixed32::EPSILON);
+    }
+
+    #[test]
+    fn div_rounding() {
+        let three = Fixed32::from_int(3);
+        assert_eq!(Fixed32::ONE / three, Fixed32::from_bits(21845));
+        assert_eq!(Fixed32::from_int(2) / three, Fixed32::from_bits(43691));
+        assert_eq!(-Fixed32::from_int(2) / three, Fixed32::from_bits(-43691));
+        assert_eq!(-Fixed32::from_int(2) / -three, Fixed32::from_bits(43691));
+        assert_eq!(Fixed32::from_int(6) / three, Fixed32::from_int(2));
+        assert_eq!(super::MulInverse::inverse(Fixed32::from_int(4)), Fixed32::from_f64(0.25));
+    }
+
+    #[test]
+    #[should_panic]
+    fn div_by_zero() {
+        let _ = Fixed32::ONE / Fixed32::ZERO;
+    }
+
+    #[test]
+    fn saturating_overflow() {
+        assert_eq!(Fixed32::MAX + Fixed32::EPSILON, Fixed32::MAX);
+        assert_eq!(Fixed32::MIN - Fixed32::EPSILON, Fixed32::MIN);
+        assert_eq!(-Fixed32::MIN, Fixed32::MAX);
+        assert_eq!(Fixed32::MAX * Fixed32::from_int(2), Fixed32::MAX);
+        assert_eq!(Fixed32::MAX * Fixed32::from_int(-2), Fixed32::MIN);
+        assert_eq!(Fixed32::MAX / Fixed32::from_f64(0.5), Fixed32::MAX);
+    }
+}
@@ -0,0 +1,388 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use super::definitions::{AddInverse, Associative, Commutative, Identity, MulInverse, One, Zero};
+
+// Closed interval [lo, hi] of reals.
+//
+// Every operation rounds its lower bound down and its upper bound up, so the exact result of
+// applying the operation to any reals inside the operands is always inside the result.
+// The rounding error of each bound is computed exactly (two-sum and fma), so a bound is only
+// moved to the next float when the float result was actually inexact.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Interval {
+    lo: f64,
+    hi: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntervalError {
+    // lo > hi or one of the bounds is NaN
+    InvalidBounds,
+    // The denominator of a division contains zero
+    DivisionByZero,
+}
+
+impl Interval {
+    pub fn new(lo: f64, hi: f64) -> Result<Self, IntervalError> {
+        if lo <= hi {
+            Ok(Self { lo, hi })
+        } else {
+            Err(IntervalError::InvalidBounds)
+        }
+    }
+
+    // The degenerate interval [x, x]
+    pub fn point(x: f64) -> Self {
+        Self { lo: x, hi: x }
+    }
+
+    pub fn lo(&self) -> f64 {
+        self.lo
+    }
+
+    pub fn hi(&self) -> f64 {
+        self.hi
+    }
+
+    pub fn width(&self) -> f64 {
+        self.hi - self.lo
+    }
+
+    pub fn midpoint(&self) -> f64 {
+        self.lo + (self.hi - self.lo) / 2.0
+    }
+
+    pub fn contains(&self, x: f64) -> bool {
+        self.lo <= x && x <= self.hi
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Result<Self, IntervalError> {
+        if rhs.contains(0.0) {
+            return Err(IntervalError::DivisionByZero);
+        }
+
+        let quotients = [
+            (self.lo, rhs.lo),
+            (self.lo, rhs.hi),
+            (self.hi, rhs.lo),
+            (self.hi, rhs.hi),
+        ];
+        Ok(Self {
+            lo: quotients
+                .iter()
+                .map(|&(a, b)| div_down(a, b))
+                .fold(f64::INFINITY, f64::min),
+            hi: quotients
+                .iter()
+                .map(|&(a, b)| div_up(a, b))
+                .fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+}
+
+// A float result together with the sign of (exact result - float result)
+fn round_down(result: f64, error: f64, exact_finite: bool) -> f64 {
+    if result == f64::INFINITY && exact_finite {
+        f64::MAX
+    } else if error < 0.0 || error.is_nan() {
+        result.next_down()
+    } else {
+        result
+    }
+}
+
+fn round_up(result: f64, error: f64, exact_finite: bool) -> f64 {
+    if result == f64::NEG_INFINITY && exact_finite {
+        f64::MIN
+    } else if error > 0.0 || error.is_nan() {
+        result.next_up()
+    } else {
+        result
+    }
+}
+
+// Knuth's two-sum, returns the exact error of a + b
+fn sum_error(a: f64, b: f64, s: f64) -> f64 {
+    let bb = s - a;
+    (a - (s - bb)) + (b - bb)
+}
+
+fn add_down(a: f64, b: f64) -> f64 {
+    let s = a + b;
+    round_down(s, sum_error(a, b, s), a.is_finite() && b.is_finite())
+}
+
+fn add_up(a: f64, b: f64) -> f64 {
+    let s = a + b;
+    round_up(s, sum_error(a, b, s), a.is_finite() && b.is_finite())
+}
+
+// The fma error terms below are only exact while they don't underflow, which is guaranteed
+// for results of at least 2^53 times the smallest normal number. Closer to zero the error
+// may have been flushed to 0, so a bound is always moved one step outward there.
+const EXACT_ERROR_BOUND: f64 = f64::MIN_POSITIVE * (1u64 << 53) as f64;
+
+// An error of NaN means unknown, both roundings then move the result
+fn mul_error(a: f64, b: f64, p: f64) -> f64 {
+    if p.abs() < EXACT_ERROR_BOUND && a != 0.0 && b != 0.0 {
+        f64::NAN
+    } else {
+        a.mul_add(b, -p)
+    }
+}
+
+fn mul_down(a: f64, b: f64) -> f64 {
+    let p = a * b;
+    round_down(p, mul_error(a, b, p), a.is_finite() && b.is_finite())
+}
+
+fn mul_up(a: f64, b: f64) -> f64 {
+    let p = a * b;
+    round_up(p, mul_error(a, b, p), a.is_finite() && b.is_finite())
+}
+
+// a / b - q has the sign of (a - q * b) / b, and a - q * b is exact with an fma
+fn div_error(a: f64, b: f64, q: f64) -> f64 {
+    if (q.abs() < EXACT_ERROR_BOUND || a.abs() < EXACT_ERROR_BOUND) && a != 0.0 {
+        f64::NAN
+    } else {
+        -q.mul_add(b, -a) * b.signum()
+    }
+}
+
+fn div_down(a: f64, b: f64) -> f64 {
+    let q = a / b;
+    round_down(q, div_error(a, b, q), a.is_finite())
+}
+
+fn div_up(a: f64, b: f64) -> f64 {
+    let q = a / b;
+    round_up(q, div_error(a, b, q), a.is_finite())
+}
+
+impl Debug for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{:?}, {:?}]", self.lo, self.hi)
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {}]", self.lo, self.hi)
+    }
+}
+
+impl Add for Interval {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            lo: add_down(self.lo, rhs.lo),
+            hi: add_up(self.hi, rhs.hi),
+        }
+    }
+}
+
+impl Sub for Interval {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for Interval {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let products = [
+            (self.lo, rhs.lo),
+            (self.lo, rhs.hi),
+            (self.hi, rhs.lo),
+            (self.hi, rhs.hi),
+        ];
+        Self {
+            lo: products
+                .iter()
+                .map(|&(a, b)| mul_down(a, b))
+                .fold(f64::INFINITY, f64::min),
+            hi: products
+                .iter()
+                .map(|&(a, b)| mul_up(a, b))
+                .fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+}
+
+impl Div for Interval {
+    type Output = Self;
+
+    // Panics when rhs contains zero, use checked_div to handle that case
+    fn div(self, rhs: Self) -> Self::Output {
+        match self.checked_div(rhs) {
+            Ok(quotient) => quotient,
+            Err(_) => panic!("attempt to divide by an interval containing zero"),
+        }
+    }
+}
+
+impl Neg for Interval {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            lo: -self.hi,
+            hi: -self.lo,
+        }
+    }
+}
+
+impl Zero for Interval {
+    const ZERO: Self = Self { lo: 0.0, hi: 0.0 };
+}
+
+impl One for Interval {
+    const ONE: Self = Self { lo: 1.0, hi: 1.0 };
+}
+
+impl AddInverse for Interval {
+    fn negate(self) -> Self {
+        -self
+    }
+}
+
+impl MulInverse for Interval {
+    fn inverse(self) -> Self {
+        Self::ONE / self
+    }
+}
+
+// Intervals are not a vector space: x - x contains 0 but is not [0, 0] and
+// multiplication is only subdistributive, so Inverse and Distributive are left out.
+impl Commutative for Interval {}
+impl Associative for Interval {}
+impl Identity for Interval {}
+
+#[cfg(test)]
+mod test {
+    use super::{Interval, IntervalError};
+    use crate::chapters::c1::definitions::{Complex, List, One, Zero};
+
+    #[test]
+    fn invalid_bounds() {
+        assert_eq!(Interval::new(1.0, 0.0), Err(IntervalError::InvalidBounds));
+        assert_eq!(Interval::new(f64::NAN, 0.0), Err(IntervalError::InvalidBounds));
+        assert!(Interval::new(0.0, 0.0).is_ok());
+    }
+
+    #[test]
+    fn outward_rounding() {
+        // The exact sum of the floats 0.1 and 0.2 lies strictly between two floats
+        let sum = Interval::point(0.1) + Interval::point(0.2);
+        assert_eq!(sum.hi(), 0.1 + 0.2);
+        assert_eq!(sum.lo(), (0.1f64 + 0.2).next_down());
+
+        // Exact operations don't widen the interval
+        let exact = Interval::point(0.5) * Interval::point(3.0) + Interval::point(0.25);
+        assert_eq!(exact, Interval::point(1.75));
+        assert_eq!(Interval::point(1.0) / Interval::point(4.0), Interval::point(0.25));
+
+        let third = Interval::ONE / Interval::point(3.0);
+        // The float 1/3 is slightly below the real 1/3
+        assert_eq!(third.lo(), 1.0 / 3.0);
+        assert_eq!(third.hi(), (1.0f64 / 3.0).next_up());
+    }
+
+    #[test]
+    fn contains_float_computation() {
+        // Accumulating 1/k both ways, the float result has to stay inside the bounds
+        let mut naive = 0.0;
+        let mut bounded = Interval::ZERO;
+        for k in 1..=200 {
+            let x = 1.0 / k as f64;
+            naive += x * x - x / 3.0;
+            let i = Interval::ONE / Interval::point(k as f64);
+            bounded = bounded + (i * i - i / Interval::point(3.0));
+            assert!(bounded.contains(naive), "{naive} not in {bounded:?}");
+        }
+        assert!(bounded.width() > 0.0);
+        assert!(bounded.width() < 1e-12);
+    }
+
+    #[test]
+    fn width_grows() {
+        let mut x = Interval::new(0.9, 1.1).unwrap();
+        let mut width = x.width();
+        for _ in 0..20 {
+            x = (x * Interval::point(1.1) - Interval::point(0.1)) / Interval::point(1.01);
+            assert!(x.width() >= width);
+            width = x.width();
+        }
+    }
+
+    #[test]
+    fn subnormal_products() {
+        // The exact product is about 2.1e-329, below the smallest subnormal
+        let x = Interval::point(3e-170) * Interval::point(7e-160);
+        assert!(x.lo() <= 0.0 && x.hi() > 0.0, "{x:?}");
+
+        // Subnormal results keep their sign and stay enclosed
+        let y = Interval::point(-3e-160) * Interval::point(1e-160);
+        assert!(y.lo() < -2.9e-320 && y.hi() > -3.1e-320 && y.hi() < 0.0, "{y:?}");
+        assert!(y.lo() < y.hi());
+        // Tripling a subnormal is exact, the enclosure has to contain it
+        let z = Interval::point(1.5e-310) * Interval::point(3.0);
+        let exact = 1.5e-310 * 3.0;
+        assert!(z.lo() <= exact && exact <= z.hi(), "{z:?}");
+
+        let q = Interval::point(1e-308) / Interval::point(3e10);
+        assert!(q.lo() < 1e-308 / 3e10 && q.hi() > 1e-308 / 3e10, "{q:?}");
+        let r = Interval::point(1e-320) / Interval::point(1e10);
+        assert!(r.lo() <= 0.0 && r.hi() > 0.0, "{r:?}");
+
+        // Products with an exact zero stay exact
+        assert_eq!(Interval::point(0.0) * Interval::point(1e-300), Interval::point(0.0));
+    }
+
+    #[test]
+    fn mixed_sign_mul() {
+        let x = Interval::new(-2.0, 3.0).unwrap();
+        let y = Interval::new(-5.0, 4.0).unwrap();
+        assert_eq!(x * y, Interval::new(-15.0, 12.0).unwrap());
+        assert_eq!(-x, Interval::new(-3.0, 2.0).unwrap());
+    }
+
+    #[test]
+    fn division_by_zero() {
+        let x = Interval::new(1.0, 2.0).unwrap();
+        let y = Interval::new(-1.0, 1.0).unwrap();
+        assert_eq!(x.checked_div(y), Err(IntervalError::DivisionByZero));
+        assert_eq!(
+            x.checked_div(Interval::ZERO),
+            Err(IntervalError::DivisionByZero)
+        );
+        assert_eq!(
+            x.checked_div(Interval::new(2.0, 4.0).unwrap()),
+            Interval::new(0.25, 1.0)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn div_operator_panics_on_zero() {
+        let _ = Interval::ONE / Interval::new(0.0, 1.0).unwrap();
+    }
+
+    #[test]
+    fn list_and_complex() {
+        let u = List::<Interval, 3>::ONE + List::ONE;
+        let dot = u[0] * u[0] + u[1] * u[1] + u[2] * u[2];
+        assert_eq!(dot, Interval::point(12.0));
+
+        let z = Complex::<Interval>::ONE * Complex::ONE;
+        assert_eq!(format!("{z:?}"), "([1.0, 1.0] + [0.0, 0.0]i)");
+    }
+}
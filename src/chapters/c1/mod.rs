@@ -1,2 +1,7 @@
+pub mod axioms;
 pub mod definitions;
-pub mod exercizes;
\ No newline at end of file
+pub mod exercizes;
+pub mod fixed;
+pub mod interval;
+pub mod modular;
+pub mod rational;
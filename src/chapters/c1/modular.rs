@@ -0,0 +1,206 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use super::definitions::{
+    AddInverse, Associative, Commutative, Distributive, Divide, Identity, Inverse, MulInverse, One,
+    Zero,
+};
+
+// The integers modulo a prime P, the finite field Z/P.
+//
+// Stored as the representative in 0..P, so the derived PartialEq is correct. Products are
+// computed in u64 before reducing, which never overflows for a u32 modulus. Every non zero
+// element has an inverse because P is prime, which new checks at compile time.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Zp<const P: u32>(u32);
+
+// Trial division, fine for the small moduli used in examples
+const fn is_prime(p: u32) -> bool {
+    if p < 2 {
+        return false;
+    }
+    let mut d = 2u64;
+    while d * d <= p as u64 {
+        if (p as u64).is_multiple_of(d) {
+            return false;
+        }
+        d += 1;
+    }
+    true
+}
+
+// A modulus that is not a prime fails to compile:
+/// ```compile_fail
+/// use linear_algebra::chapters::c1::modular::Zp;
+///
+/// let x = Zp::<8>::new(3);
+/// ```
+impl<const P: u32> Zp<P> {
+    // x mod P, negative numbers included
+    pub fn new(x: i64) -> Self {
+        const { assert!(is_prime(P), "the modulus must be a prime") };
+        Self(x.rem_euclid(P as i64) as u32)
+    }
+
+    // The representative in 0..P
+    pub const fn value(self) -> u32 {
+        self.0
+    }
+
+    // Exponentiation by squaring
+    pub fn pow(self, mut exp: u64) -> Self {
+        let mut base = self;
+        let mut result = Self::ONE;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+        result
+    }
+}
+
+impl<const P: u32> From<i64> for Zp<P> {
+    fn from(x: i64) -> Self {
+        Self::new(x)
+    }
+}
+
+impl<const P: u32> Debug for Zp<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (mod {P})", self.0)
+    }
+}
+
+impl<const P: u32> Display for Zp<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u32> Add for Zp<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(((self.0 as u64 + rhs.0 as u64) % P as u64) as u32)
+    }
+}
+
+impl<const P: u32> Sub for Zp<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<const P: u32> Mul for Zp<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(((self.0 as u64 * rhs.0 as u64) % P as u64) as u32)
+    }
+}
+
+impl<const P: u32> Div for Zp<P> {
+    type Output = Self;
+
+    // Panics when dividing by zero
+    fn div(self, rhs: Self) -> Self::Output {
+        self.divide(rhs)
+    }
+}
+
+impl<const P: u32> Neg for Zp<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        if self.0 == 0 { self } else { Self(P - self.0) }
+    }
+}
+
+impl<const P: u32> Zero for Zp<P> {
+    const ZERO: Self = Self(0);
+}
+
+impl<const P: u32> One for Zp<P> {
+    const ONE: Self = Self(1 % P);
+}
+
+impl<const P: u32> AddInverse for Zp<P> {
+    fn negate(self) -> Self {
+        -self
+    }
+}
+
+// Fermat: x^(P - 1) = 1, so x^(P - 2) is the inverse
+impl<const P: u32> MulInverse for Zp<P> {
+    fn inverse(self) -> Self {
+        const { assert!(is_prime(P), "the modulus must be a prime") };
+        if self.0 == 0 {
+            panic!("attempt to invert zero");
+        }
+        self.pow(P as u64 - 2)
+    }
+}
+
+impl<const P: u32> Commutative for Zp<P> {}
+impl<const P: u32> Associative for Zp<P> {}
+impl<const P: u32> Identity for Zp<P> {}
+impl<const P: u32> Inverse for Zp<P> {}
+impl<const P: u32> Distributive<Zp<P>> for Zp<P> {}
+
+#[cfg(test)]
+mod test {
+    use super::{Zp, is_prime};
+    use crate::chapters::c1::definitions::{List, MulInverse, One, VectorSpace, Zero};
+
+    type Z7 = Zp<7>;
+
+    fn accept_field<T: VectorSpace<X>, X>() {}
+
+    #[test]
+    fn vector_space() {
+        accept_field::<Z7, Z7>();
+        accept_field::<List<Z7, 3>, Z7>();
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(Z7::new(-1), Z7::new(6));
+        assert_eq!(Z7::new(23).value(), 2);
+        assert_eq!(Z7::new(5) + Z7::new(4), Z7::new(2));
+        assert_eq!(Z7::new(2) - Z7::new(5), Z7::new(4));
+        assert_eq!(Z7::new(3) * Z7::new(5), Z7::ONE);
+        assert_eq!(Z7::new(1) / Z7::new(3), Z7::new(5));
+        assert_eq!(-Z7::ZERO, Z7::ZERO);
+
+        // Every non zero element is invertible
+        for x in 1..7 {
+            let x = Z7::new(x);
+            assert_eq!(x * x.inverse(), Z7::ONE);
+        }
+
+        // Large moduli don't overflow
+        let big = Zp::<4_294_967_291>::new(-2);
+        assert_eq!((big * big).value(), 4);
+        assert_eq!(big * big.inverse(), Zp::ONE);
+    }
+
+    #[test]
+    fn primes() {
+        let primes: Vec<u32> = (0..30).filter(|&p| is_prime(p)).collect();
+        assert_eq!(primes, [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert!(is_prime(4_294_967_291));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to invert zero")]
+    fn zero_has_no_inverse() {
+        Z7::ZERO.inverse();
+    }
+}
@@ -0,0 +1,237 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use super::definitions::{
+    AddInverse, Associative, Commutative, Distributive, Divide, Identity, Inverse, MulInverse, One,
+    Zero,
+};
+
+// Exact fraction num / den.
+//
+// Always stored in lowest terms with a positive denominator, so equal values have equal fields
+// and the derived PartialEq is correct. Intermediate results are computed in i128 and reduced
+// before being narrowed back, an operation only panics when the reduced result does not fit an i64.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i64,
+    den: i64,
+}
+
+impl Rational {
+    // Panics when den is zero
+    pub fn new(num: i64, den: i64) -> Self {
+        Self::reduce(num as i128, den as i128)
+    }
+
+    pub const fn numer(self) -> i64 {
+        self.num
+    }
+
+    pub const fn denom(self) -> i64 {
+        self.den
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    fn reduce(num: i128, den: i128) -> Self {
+        if den == 0 {
+            panic!("attempt to create a Rational with a zero denominator");
+        }
+
+        let divisor = gcd(num, den) * den.signum();
+        let narrow = |x: i128| i64::try_from(x).expect("Rational overflowed an i64");
+        Self {
+            num: narrow(num / divisor),
+            den: narrow(den / divisor),
+        }
+    }
+}
+
+// Euclid, gcd(0, 0) is never needed since the denominator is non zero
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+impl From<i64> for Rational {
+    fn from(num: i64) -> Self {
+        Self { num, den: 1 }
+    }
+}
+
+impl Debug for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::reduce(
+            self.num as i128 * rhs.den as i128 + rhs.num as i128 * self.den as i128,
+            self.den as i128 * rhs.den as i128,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::reduce(
+            self.num as i128 * rhs.den as i128 - rhs.num as i128 * self.den as i128,
+            self.den as i128 * rhs.den as i128,
+        )
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::reduce(
+            self.num as i128 * rhs.num as i128,
+            self.den as i128 * rhs.den as i128,
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    // Panics when dividing by zero
+    fn div(self, rhs: Self) -> Self::Output {
+        self.divide(rhs)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::reduce(-(self.num as i128), self.den as i128)
+    }
+}
+
+impl Zero for Rational {
+    const ZERO: Self = Self { num: 0, den: 1 };
+}
+
+impl One for Rational {
+    const ONE: Self = Self { num: 1, den: 1 };
+}
+
+impl AddInverse for Rational {
+    fn negate(self) -> Self {
+        -self
+    }
+}
+
+impl MulInverse for Rational {
+    fn inverse(self) -> Self {
+        Self::reduce(self.den as i128, self.num as i128)
+    }
+}
+
+impl Commutative for Rational {}
+impl Associative for Rational {}
+impl Identity for Rational {}
+impl Inverse for Rational {}
+impl Distributive<Rational> for Rational {}
+
+#[cfg(test)]
+mod test {
+    use super::Rational;
+    use crate::chapters::c1::definitions::{
+        Complex, List, MulInverse, MulScalar, One, VectorSpace, Zero,
+    };
+
+    fn accept_field<T: VectorSpace<X>, X>() {}
+
+    #[test]
+    fn vector_space() {
+        accept_field::<Rational, Rational>();
+        accept_field::<List<Rational, 4>, Rational>();
+        accept_field::<Complex<f32>, Complex<f32>>();
+        accept_field::<Complex<Rational>, Complex<Rational>>();
+        accept_field::<List<f32, 3>, f32>();
+    }
+
+    #[test]
+    fn normalized() {
+        let half = Rational::new(1, 2);
+        assert_eq!(Rational::new(3, 6), half);
+        assert_eq!(Rational::new(-2, -4), half);
+        assert_eq!(Rational::new(2, -4), -half);
+        assert_eq!(Rational::new(2, -4).numer(), -1);
+        assert_eq!(Rational::new(2, -4).denom(), 2);
+        assert_eq!(Rational::new(0, -7), Rational::ZERO);
+        assert_eq!(Rational::new(i64::MIN, i64::MIN), Rational::ONE);
+    }
+
+    #[test]
+    fn exact_arithmetic() {
+        let third = Rational::new(1, 3);
+        let sixth = Rational::new(1, 6);
+        assert_eq!(third + sixth, Rational::new(1, 2));
+        assert_eq!(third - sixth, sixth);
+        assert_eq!(third * sixth, Rational::new(1, 18));
+        assert_eq!(third / sixth, Rational::from(2));
+        assert_eq!(Rational::new(-3, 4).inverse(), Rational::new(-4, 3));
+
+        // 0.1 + 0.2 != 0.3 in f64, but it is here
+        let tenth = Rational::new(1, 10);
+        assert_eq!(tenth + Rational::new(2, 10), Rational::new(3, 10));
+
+        let v = List::new([third, sixth, Rational::ONE]);
+        assert_eq!(
+            MulScalar::mul(v, Rational::from(6)),
+            List::new([2, 1, 6].map(Rational::from))
+        );
+    }
+
+    #[test]
+    fn reduces_before_overflowing() {
+        // The products do not fit an i64 but the reduced results do
+        let big = Rational::new(i64::MAX, 2);
+        assert_eq!(big * Rational::new(2, i64::MAX), Rational::ONE);
+        assert_eq!(big - big, Rational::ZERO);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Rational::new(6, -4).to_string(), "-3/2");
+        assert_eq!(Rational::new(8, 4).to_string(), "2");
+        assert_eq!(Rational::new(1, 4).to_f64(), 0.25);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_denominator() {
+        let _ = Rational::new(1, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn divide_by_zero() {
+        let _ = Rational::ONE / Rational::ZERO;
+    }
+}
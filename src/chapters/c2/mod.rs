@@ -0,0 +1 @@
+pub mod span;
\ No newline at end of file
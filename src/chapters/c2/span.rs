@@ -0,0 +1,278 @@
+use std::ops::{Add, Mul, Neg};
+
+use crate::chapters::c1::definitions::{Divide, List, MulScalar, One, Zero};
+use crate::chapters::c6::inner_product::Modulus;
+
+// Row echelon form of a list of vectors, built up one vector at a time.
+// Each row is stored with its pivot column, and every row is zero in the pivot columns of
+// the rows before it, so reducing a vector against the rows in order clears all pivots.
+struct Echelon<T, const N: usize> {
+    rows: Vec<(usize, List<T, N>)>,
+}
+
+impl<T, const N: usize> Echelon<T, N>
+where
+    T: Add<Output = T> + Neg<Output = T> + Mul<Output = T> + Divide + Modulus + Copy,
+{
+    fn new() -> Self {
+        Self { rows: Vec::new() }
+    }
+
+    // v minus its components along the rows
+    fn reduce(&self, mut v: List<T, N>) -> List<T, N> {
+        for (pivot, row) in &self.rows {
+            let factor = v[*pivot].divide(row[*pivot]);
+            v += -MulScalar::mul(*row, factor);
+        }
+        v
+    }
+
+    // Adds v if it is not in the span of the rows (entries up to eps count as zero),
+    // returns whether it was added
+    fn insert(&mut self, v: List<T, N>, eps: f64) -> bool {
+        let remainder = self.reduce(v);
+        let pivot =
+            (0..N).max_by(|&i, &j| remainder[i].modulus().total_cmp(&remainder[j].modulus()));
+        match pivot {
+            Some(pivot) if remainder[pivot].modulus() > eps => {
+                self.rows.push((pivot, remainder));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn contains(&self, v: List<T, N>, eps: f64) -> bool {
+        self.reduce(v).iter().all(|x| x.modulus() <= eps)
+    }
+}
+
+// span(v_1, ..., v_m), the set of all linear combinations of the vectors.
+// All tests treat entries with a modulus up to eps as zero during the elimination.
+pub struct Span<T, const N: usize> {
+    vectors: Vec<List<T, N>>,
+}
+
+impl<T, const N: usize> Span<T, N>
+where
+    T: Add<Output = T> + Neg<Output = T> + Mul<Output = T> + Divide + Modulus + Copy,
+{
+    pub fn new(vectors: &[List<T, N>]) -> Self {
+        Self {
+            vectors: vectors.to_vec(),
+        }
+    }
+
+    pub fn vectors(&self) -> &[List<T, N>] {
+        &self.vectors
+    }
+
+    // Is v a linear combination of the vectors
+    pub fn contains(&self, v: &List<T, N>, eps: f64) -> bool {
+        echelon(&self.vectors, eps).contains(*v, eps)
+    }
+
+    pub fn dim(&self, eps: f64) -> usize {
+        rank(&self.vectors, eps)
+    }
+}
+
+fn echelon<T, const N: usize>(vectors: &[List<T, N>], eps: f64) -> Echelon<T, N>
+where
+    T: Add<Output = T> + Neg<Output = T> + Mul<Output = T> + Divide + Modulus + Copy,
+{
+    let mut echelon = Echelon::new();
+    for v in vectors {
+        echelon.insert(*v, eps);
+    }
+    echelon
+}
+
+// The dimension of the span
+pub fn rank<T, const N: usize>(vectors: &[List<T, N>], eps: f64) -> usize
+where
+    T: Add<Output = T> + Neg<Output = T> + Mul<Output = T> + Divide + Modulus + Copy,
+{
+    echelon(vectors, eps).rows.len()
+}
+
+// The empty list is independent, a list containing 0 or more than N vectors never is
+pub fn are_linearly_independent<T, const N: usize>(vectors: &[List<T, N>], eps: f64) -> bool
+where
+    T: Add<Output = T> + Neg<Output = T> + Mul<Output = T> + Divide + Modulus + Copy,
+{
+    let mut echelon = Echelon::new();
+    vectors.iter().all(|v| echelon.insert(*v, eps))
+}
+
+// Removes every vector that is in the span of the ones before it, the result
+// is a basis of the span (2.31 in the book)
+pub fn reduce_to_basis<T, const N: usize>(vectors: &[List<T, N>], eps: f64) -> Vec<List<T, N>>
+where
+    T: Add<Output = T> + Neg<Output = T> + Mul<Output = T> + Divide + Modulus + Copy,
+{
+    let mut echelon = Echelon::new();
+    vectors
+        .iter()
+        .filter(|v| echelon.insert(**v, eps))
+        .copied()
+        .collect()
+}
+
+// The independent vectors of the list followed by the standard basis vectors
+// needed to make a basis of F^N (2.33 in the book)
+pub fn extend_to_basis<T, const N: usize>(vectors: &[List<T, N>], eps: f64) -> Vec<List<T, N>>
+where
+    T: Add<Output = T> + Neg<Output = T> + Mul<Output = T> + Divide + Modulus + Zero + One + Copy,
+{
+    let standard = (0..N).map(|i| List::from_fn(|j| if i == j { T::ONE } else { T::ZERO }));
+
+    let mut echelon = Echelon::new();
+    vectors
+        .iter()
+        .copied()
+        .chain(standard)
+        .filter(|v| echelon.insert(*v, eps))
+        .collect()
+}
+
+// A basis of the x with v[0] x[0] + ... + v[N - 1] x[N - 1] = 0 for every vector v, the
+// null space of the matrix with the vectors as rows. Every non-pivot column of the echelon form
+// gives one basis vector: that variable is set to 1, the other free ones to 0, and the pivot
+// variables follow by back substitution from the last row up.
+pub fn null_space<T, const N: usize>(vectors: &[List<T, N>], eps: f64) -> Vec<List<T, N>>
+where
+    T: Add<Output = T> + Neg<Output = T> + Mul<Output = T> + Divide + Modulus + Zero + One + Copy,
+{
+    let rows = echelon(vectors, eps).rows;
+    (0..N)
+        .filter(|col| rows.iter().all(|(pivot, _)| pivot != col))
+        .map(|free| {
+            let mut x = List::ZERO;
+            x[free] = T::ONE;
+            for (k, (pivot, row)) in rows.iter().enumerate().rev() {
+                // Row k is zero in the pivot columns of the rows before it
+                let sum = (0..N)
+                    .filter(|j| rows[..=k].iter().all(|(p, _)| p != j))
+                    .fold(T::ZERO, |acc, j| acc + row[j] * x[j]);
+                x[*pivot] = -sum.divide(row[*pivot]);
+            }
+            x
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Span, are_linearly_independent, extend_to_basis, null_space, rank, reduce_to_basis,
+    };
+    use crate::chapters::c1::definitions::{Complex, List, Zero};
+
+    const EPS: f64 = 1e-9;
+
+    #[test]
+    fn empty_set() {
+        let empty: [List<f64, 3>; 0] = [];
+        assert!(are_linearly_independent(&empty, EPS));
+        assert_eq!(rank(&empty, EPS), 0);
+        assert!(Span::new(&empty).contains(&List::ZERO, EPS));
+        assert!(!Span::new(&empty).contains(&List::new([1.0, 0.0, 0.0]), EPS));
+    }
+
+    #[test]
+    fn zero_vector() {
+        let vectors = [List::new([1.0, 2.0]), List::ZERO];
+        assert!(!are_linearly_independent(&vectors, EPS));
+        assert!(!are_linearly_independent(&[List::<f64, 2>::ZERO], 0.0));
+        assert_eq!(reduce_to_basis(&vectors, EPS), vec![List::new([1.0, 2.0])]);
+    }
+
+    #[test]
+    fn too_many_vectors() {
+        let vectors = [
+            List::new([1.0, 0.0]),
+            List::new([3.0, 7.0]),
+            List::new([-2.0, 0.5]),
+        ];
+        assert!(!are_linearly_independent(&vectors, EPS));
+        assert!(are_linearly_independent(&vectors[..2], EPS));
+        assert_eq!(rank(&vectors, EPS), 2);
+    }
+
+    #[test]
+    fn span_membership() {
+        let span = Span::new(&[List::new([1.0, 0.0, 1.0]), List::new([0.0, 1.0, 1.0])]);
+        assert!(span.contains(&List::new([2.0, -3.0, -1.0]), EPS));
+        assert!(!span.contains(&List::new([0.0, 0.0, 1.0]), EPS));
+        assert_eq!(span.dim(EPS), 2);
+    }
+
+    #[test]
+    fn bases() {
+        let vectors = [
+            List::new([1.0, 2.0, 3.0]),
+            List::new([2.0, 4.0, 6.0]),
+            List::new([0.0, 1.0, 0.0]),
+            List::new([1.0, 3.0, 3.0]),
+        ];
+        let basis = reduce_to_basis(&vectors, EPS);
+        assert_eq!(basis, vec![vectors[0], vectors[2]]);
+
+        let extended = extend_to_basis(&vectors, EPS);
+        assert_eq!(
+            extended,
+            vec![vectors[0], vectors[2], List::new([1.0, 0.0, 0.0])]
+        );
+        assert!(are_linearly_independent(&extended, EPS));
+    }
+
+    #[test]
+    fn near_degenerate() {
+        let vectors = [List::new([1.0, 1.0]), List::new([1.0, 1.0 + 1e-12])];
+        assert!(!are_linearly_independent(&vectors, 1e-9));
+        assert!(are_linearly_independent(&vectors, 1e-15));
+        assert_eq!(rank(&vectors, 1e-9), 1);
+        assert_eq!(rank(&vectors, 1e-15), 2);
+    }
+
+    #[test]
+    fn null_spaces() {
+        let vectors = [List::new([1.0, 2.0, 3.0]), List::new([2.0, 4.0, 7.0])];
+        let null = null_space(&vectors, EPS);
+        assert_eq!(null.len(), 1);
+        for v in &vectors {
+            let dot: f64 = v.iter().zip(null[0].iter()).map(|(a, b)| a * b).sum();
+            assert!(dot.abs() < EPS);
+        }
+
+        // rank + nullity = N
+        let vectors = [
+            List::new([1.0, 1.0, 1.0, 1.0]),
+            List::new([2.0, 2.0, 2.0, 2.0]),
+        ];
+        let null = null_space(&vectors, EPS);
+        assert_eq!(null.len(), 3);
+        assert!(are_linearly_independent(&null, EPS));
+
+        let empty: [List<f64, 2>; 0] = [];
+        assert_eq!(
+            null_space(&empty, EPS),
+            vec![List::new([1.0, 0.0]), List::new([0.0, 1.0])]
+        );
+        assert!(null_space(&[List::new([1.0, 0.0]), List::new([0.0, 1.0])], EPS).is_empty());
+    }
+
+    #[test]
+    fn complex_vectors() {
+        // (1, i) and (i, -1) = i (1, i) are dependent over C
+        let i = Complex::new(0.0, 1.0);
+        let one = Complex::from(1.0);
+        let vectors = [List::new([one, i]), List::new([i, -one])];
+        assert!(!are_linearly_independent(&vectors, EPS));
+        assert!(are_linearly_independent(
+            &[List::new([one, i]), List::new([one, -i])],
+            EPS
+        ));
+    }
+}
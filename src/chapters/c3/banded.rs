@@ -0,0 +1,254 @@
+use std::ops::{Add, Mul};
+
+use super::matrix::Matrix;
+use super::solve::SolveError;
+use crate::chapters::c1::definitions::{Divide, Subtract, Zero};
+use crate::chapters::c6::inner_product::Modulus;
+
+// An n x n matrix that is zero outside the band i - lower <= j <= i + upper.
+// Row i keeps the lower + upper + 1 entries from column i - lower on, so a band that hangs over
+// the edge of the matrix leaves a few slots at the start and end unused.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandedMatrix<T> {
+    n: usize,
+    lower: usize,
+    upper: usize,
+    data: Vec<T>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutsideBand {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl<T> BandedMatrix<T> {
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    pub fn bandwidths(&self) -> (usize, usize) {
+        (self.lower, self.upper)
+    }
+
+    // Where (i, j) lives in data, if it is inside the band
+    fn slot(&self, i: usize, j: usize) -> Option<usize> {
+        assert!(i < self.n && j < self.n, "index out of range");
+        (j + self.lower >= i && j <= i + self.upper)
+            .then(|| i * (self.lower + self.upper + 1) + j + self.lower - i)
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> T
+    where
+        T: Zero + Copy,
+    {
+        self.slot(i, j).map_or(T::ZERO, |k| self.data[k])
+    }
+
+    // Only entries inside the band can be changed, everything else stays zero
+    pub fn set(&mut self, i: usize, j: usize, x: T) -> Result<(), OutsideBand> {
+        let k = self.slot(i, j).ok_or(OutsideBand { row: i, col: j })?;
+        self.data[k] = x;
+        Ok(())
+    }
+}
+
+impl<T> BandedMatrix<T>
+where
+    T: Zero + Copy,
+{
+    pub fn new(n: usize, lower: usize, upper: usize) -> Self {
+        BandedMatrix {
+            n,
+            lower,
+            upper,
+            data: vec![T::ZERO; n * (lower + upper + 1)],
+        }
+    }
+
+    // diagonals[k] is the diagonal j - i = k - lower, with n - |k - lower| entries.
+    // Panics when the lengths don't fit that pattern.
+    pub fn from_diagonals(n: usize, lower: usize, upper: usize, diagonals: &[&[T]]) -> Self {
+        assert_eq!(
+            diagonals.len(),
+            lower + upper + 1,
+            "wrong number of diagonals"
+        );
+        let mut m = Self::new(n, lower, upper);
+        for (k, diagonal) in diagonals.iter().enumerate() {
+            let (first_row, first_col) = if k < lower {
+                (lower - k, 0)
+            } else {
+                (0, k - lower)
+            };
+            assert_eq!(
+                diagonal.len(),
+                n.saturating_sub(first_row + first_col),
+                "wrong length"
+            );
+            for (t, &x) in diagonal.iter().enumerate() {
+                m.set(first_row + t, first_col + t, x).unwrap();
+            }
+        }
+        m
+    }
+
+    // Panics when the matrix isn't N x N
+    pub fn to_matrix<const N: usize>(&self) -> Matrix<T, N, N> {
+        assert_eq!(self.n, N, "wrong shape");
+        Matrix::from_fn(|i, j| self.get(i, j))
+    }
+
+    // The columns of row i that can be nonzero
+    fn band(&self, i: usize) -> std::ops::Range<usize> {
+        i.saturating_sub(self.lower)..(i + self.upper + 1).min(self.n)
+    }
+
+    // The matrix applied to x in O(n * bandwidth), panics when x doesn't have n entries
+    pub fn mul_vec(&self, x: &[T]) -> Vec<T>
+    where
+        T: Add<Output = T> + Mul<Output = T>,
+    {
+        assert_eq!(x.len(), self.n, "wrong length");
+        (0..self.n)
+            .map(|i| {
+                self.band(i)
+                    .fold(T::ZERO, |acc, j| acc + self.get(i, j) * x[j])
+            })
+            .collect()
+    }
+
+    // Gaussian elimination restricted to the band, the Thomas algorithm when both bandwidths
+    // are 1. Without row swaps no entry outside the band ever becomes nonzero, so the whole thing
+    // takes O(n * lower * upper). The price is that it only works when no pivot vanishes on the
+    // way, which is guaranteed for diagonally dominant and positive definite matrices, the usual
+    // case for splines and finite differences.
+    pub fn solve(&self, b: &[T]) -> Result<Vec<T>, SolveError>
+    where
+        T: Add<Output = T> + Mul<Output = T> + Subtract + Divide + Modulus,
+    {
+        assert_eq!(b.len(), self.n, "wrong length");
+        let mut lu = self.clone();
+        for k in 0..self.n {
+            let pivot = lu.get(k, k);
+            if pivot.modulus() == 0.0 {
+                return Err(SolveError::Singular);
+            }
+            for i in k + 1..(k + self.lower + 1).min(self.n) {
+                let factor = lu.get(i, k).divide(pivot);
+                lu.set(i, k, factor).unwrap();
+                for j in k + 1..(k + self.upper + 1).min(self.n) {
+                    lu.set(i, j, lu.get(i, j).subtract(factor * lu.get(k, j)))
+                        .unwrap();
+                }
+            }
+        }
+
+        // Forward substitution with the multipliers, then back substitution with U
+        let mut x = b.to_vec();
+        for i in 0..self.n {
+            for j in lu.band(i).start..i {
+                x[i] = x[i].subtract(lu.get(i, j) * x[j]);
+            }
+        }
+        for i in (0..self.n).rev() {
+            for j in i + 1..lu.band(i).end {
+                x[i] = x[i].subtract(lu.get(i, j) * x[j]);
+            }
+            x[i] = x[i].divide(lu.get(i, i));
+        }
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BandedMatrix, OutsideBand};
+    use crate::chapters::c1::definitions::List;
+    use crate::chapters::c3::solve::{SolveError, solve};
+
+    #[test]
+    fn storage() {
+        let m = BandedMatrix::from_diagonals(
+            4,
+            1,
+            2,
+            &[&[1, 2, 3], &[4, 5, 6, 7], &[8, 9, 10], &[11, 12]],
+        );
+        assert_eq!(m.bandwidths(), (1, 2));
+        assert_eq!(m.data.len(), 4 * 4);
+        assert_eq!(m.get(1, 0), 1);
+        assert_eq!(m.get(3, 3), 7);
+        assert_eq!(m.get(0, 2), 11);
+        assert_eq!(m.get(1, 3), 12);
+
+        // Outside the band reads zero but can't be written
+        assert_eq!(m.get(3, 0), 0);
+        assert_eq!(m.get(0, 3), 0);
+        let mut m = m;
+        assert_eq!(m.set(2, 0, 5), Err(OutsideBand { row: 2, col: 0 }));
+        assert_eq!(m.set(0, 1, 5), Ok(()));
+        assert_eq!(m.get(0, 1), 5);
+    }
+
+    #[test]
+    fn multiply() {
+        let m = BandedMatrix::from_diagonals(
+            5,
+            2,
+            1,
+            &[
+                &[0.5, -1.0, 2.0],
+                &[1.0, 3.0, -2.0, 0.25],
+                &[4.0, 5.0, 6.0, 7.0, 8.0],
+                &[-1.0, 1.5, 2.5, -3.0],
+            ],
+        );
+        let x = [1.0, -2.0, 0.5, 3.0, -1.5];
+        assert_eq!(
+            m.mul_vec(&x),
+            (m.to_matrix::<5>() * List::new(x)).as_slice()
+        );
+    }
+
+    #[test]
+    fn tridiagonal_solve() {
+        // The second difference matrix of a spline
+        let m = BandedMatrix::from_diagonals(
+            6,
+            1,
+            1,
+            &[&[1.0; 5], &[4.0, 4.0, 4.0, 4.0, 4.0, 4.0], &[1.0; 5]],
+        );
+        let b = [1.0, -2.0, 3.0, 0.5, 2.0, -1.0];
+        let x = m.solve(&b).unwrap();
+        let dense = solve(m.to_matrix::<6>(), List::new(b)).unwrap();
+        for i in 0..6 {
+            assert!((x[i] - dense[i]).abs() < 1e-14);
+        }
+
+        let singular = BandedMatrix::from_diagonals(2, 1, 1, &[&[1.0], &[0.0, 1.0], &[1.0]]);
+        assert_eq!(singular.solve(&[1.0, 1.0]), Err(SolveError::Singular));
+    }
+
+    #[test]
+    fn wider_band_solve() {
+        let m = BandedMatrix::from_diagonals(
+            5,
+            2,
+            1,
+            &[
+                &[0.5, -1.0, 2.0],
+                &[1.0, 3.0, -2.0, 0.25],
+                &[6.0, 7.0, 8.0, 9.0, 10.0],
+                &[-1.0, 1.5, 2.5, -3.0],
+            ],
+        );
+        let b = [2.0, 0.0, -1.0, 4.0, 1.0];
+        let x = m.solve(&b).unwrap();
+        let residual = m.mul_vec(&x);
+        for i in 0..5 {
+            assert!((residual[i] - b[i]).abs() < 1e-13);
+        }
+    }
+}
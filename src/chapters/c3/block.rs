@@ -0,0 +1,193 @@
+use std::ops::{Add, Mul};
+
+use super::matrix::Matrix;
+use crate::chapters::c1::definitions::{List, Subtract, Zero};
+
+// Partitioned matrices
+//  M = ( A  B )
+//      ( C  D )
+// with A R1 x C1 and D R2 x C2. Stable Rust can't write the size of M as R1 + R2, so it is a
+// parameter of its own, usually inferred from the context, and checked at compile time:
+/// ```compile_fail
+/// use linear_algebra::chapters::c3::matrix::Matrix;
+///
+/// let a = Matrix::<f64, 2, 2>::identity();
+/// let m: Matrix<f64, 3, 4> = Matrix::from_blocks(a, a, a, a);
+/// ```
+impl<T, const R: usize, const C: usize> Matrix<T, R, C>
+where
+    T: Copy,
+{
+    pub fn from_blocks<const R1: usize, const R2: usize, const C1: usize, const C2: usize>(
+        a: Matrix<T, R1, C1>,
+        b: Matrix<T, R1, C2>,
+        c: Matrix<T, R2, C1>,
+        d: Matrix<T, R2, C2>,
+    ) -> Self {
+        const {
+            assert!(
+                R1 + R2 == R && C1 + C2 == C,
+                "the blocks don't add up to R x C"
+            )
+        };
+        Matrix::from_fn(|i, j| match (i < R1, j < C1) {
+            (true, true) => a[(i, j)],
+            (true, false) => b[(i, j - C1)],
+            (false, true) => c[(i - R1, j)],
+            (false, false) => d[(i - R1, j - C1)],
+        })
+    }
+
+    // The inverse of from_blocks, with A of size R1 x C1
+    #[allow(clippy::type_complexity)]
+    pub fn split_blocks<const R1: usize, const R2: usize, const C1: usize, const C2: usize>(
+        &self,
+    ) -> (
+        Matrix<T, R1, C1>,
+        Matrix<T, R1, C2>,
+        Matrix<T, R2, C1>,
+        Matrix<T, R2, C2>,
+    ) {
+        const {
+            assert!(
+                R1 + R2 == R && C1 + C2 == C,
+                "the blocks don't add up to R x C"
+            )
+        };
+        (
+            Matrix::from_fn(|i, j| self[(i, j)]),
+            Matrix::from_fn(|i, j| self[(i, C1 + j)]),
+            Matrix::from_fn(|i, j| self[(R1 + i, j)]),
+            Matrix::from_fn(|i, j| self[(R1 + i, C1 + j)]),
+        )
+    }
+
+    // ( A 0 )
+    // ( 0 B )
+    pub fn block_diag<const R1: usize, const R2: usize, const C1: usize, const C2: usize>(
+        a: Matrix<T, R1, C1>,
+        b: Matrix<T, R2, C2>,
+    ) -> Self
+    where
+        T: Zero,
+    {
+        Self::from_blocks(a, Matrix::ZERO, Matrix::ZERO, b)
+    }
+}
+
+impl<T, const N: usize> Matrix<T, N, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + Subtract + Zero + Copy,
+{
+    // The Schur complement M / D = A - B D^-1 C of the square R2 x R2 block D. It is what is left
+    // of A after eliminating the last R2 unknowns, and M is invertible exactly when D and M / D
+    // are. solve_d(y) has to return D^-1 y, so any factorization of D can be plugged in.
+    pub fn schur_complement<const R1: usize, const R2: usize, E>(
+        &self,
+        mut solve_d: impl FnMut(List<T, R2>) -> Result<List<T, R2>, E>,
+    ) -> Result<Matrix<T, R1, R1>, E> {
+        let (a, b, c, _) = self.split_blocks::<R1, R2, R1, R2>();
+
+        // D^-1 C one column at a time
+        let mut d_inv_c = Matrix::<T, R2, R1>::ZERO;
+        for j in 0..R1 {
+            let column = solve_d(List::from_fn(|i| c[(i, j)]))?;
+            for i in 0..R2 {
+                d_inv_c[(i, j)] = column[i];
+            }
+        }
+
+        let correction = b * d_inv_c;
+        Ok(Matrix::from_fn(|i, j| {
+            a[(i, j)].subtract(correction[(i, j)])
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::chapters::c1::definitions::{List, MulScalar};
+    use crate::chapters::c3::matrix::Matrix;
+    use crate::chapters::c3::solve::{SolveError, solve};
+
+    fn assert_close<const R: usize, const C: usize>(a: Matrix<f64, R, C>, b: Matrix<f64, R, C>) {
+        for i in 0..R {
+            for j in 0..C {
+                assert!((a[(i, j)] - b[(i, j)]).abs() < 1e-12, "{a}\n!=\n{b}");
+            }
+        }
+    }
+
+    #[test]
+    fn compose_and_split() {
+        let a = Matrix::new([[1, 2], [3, 4]]);
+        let b = Matrix::new([[5], [6]]);
+        let c = Matrix::new([[7, 8]]);
+        let d = Matrix::new([[9]]);
+        let m: Matrix<i32, 3, 3> = Matrix::from_blocks(a, b, c, d);
+        assert_eq!(m, Matrix::new([[1, 2, 5], [3, 4, 6], [7, 8, 9]]));
+        assert_eq!(m.split_blocks::<2, 1, 2, 1>(), (a, b, c, d));
+
+        // The same matrix split differently
+        let (a, b, c, d) = m.split_blocks::<1, 2, 2, 1>();
+        assert_eq!(a, Matrix::new([[1, 2]]));
+        assert_eq!(d, Matrix::new([[6], [9]]));
+        assert_eq!(Matrix::<i32, 3, 3>::from_blocks(a, b, c, d), m);
+
+        // Empty blocks are fine
+        let (_, _, _, whole) = m.split_blocks::<0, 3, 0, 3>();
+        assert_eq!(whole, m);
+    }
+
+    #[test]
+    fn block_diagonal() {
+        let a = Matrix::new([[2.0, 1.0], [0.0, 3.0]]);
+        let b = Matrix::new([[-1.0, 4.0, 0.5]]);
+        let m: Matrix<f64, 3, 5> = Matrix::block_diag(a, b);
+        assert_eq!(m[(0, 1)], 1.0);
+        assert_eq!(m[(2, 3)], 4.0);
+        assert_eq!(m[(0, 4)], 0.0);
+
+        // Products act on each block on its own
+        let p = Matrix::<f64, 2, 2>::from_fn(|i, j| (i + 2 * j) as f64);
+        let q = Matrix::<f64, 3, 3>::from_fn(|i, j| (i * j) as f64 - 1.0);
+        let n: Matrix<f64, 5, 5> = Matrix::block_diag(p, q);
+        let blockwise: Matrix<f64, 3, 5> = Matrix::block_diag(a * p, b * q);
+        assert_eq!(m * n, blockwise);
+    }
+
+    #[test]
+    fn schur_complement_inverse() {
+        let m = Matrix::new([
+            [4.0, 1.0, 0.5, 0.0, 1.0],
+            [1.0, 5.0, 0.0, 1.0, 0.0],
+            [0.5, 0.0, 6.0, 1.0, 0.5],
+            [0.0, 1.0, 1.0, 3.0, 0.0],
+            [1.0, 0.0, 0.5, 0.0, 4.0],
+        ]);
+        let (a, b, c, d) = m.split_blocks::<2, 3, 2, 3>();
+        let s = m.schur_complement::<2, 3, _>(|y| solve(d, y)).unwrap();
+        assert_close(s, a + -(b * d.invert().unwrap() * c));
+
+        // M^-1 = ( S^-1               -S^-1 B D^-1                  )
+        //        ( -D^-1 C S^-1       D^-1 + D^-1 C S^-1 B D^-1     )
+        let (s_inv, d_inv) = (s.invert().unwrap(), d.invert().unwrap());
+        let top_right = -(s_inv * b * d_inv);
+        let bottom_left = -(d_inv * c * s_inv);
+        let bottom_right = d_inv + d_inv * c * s_inv * b * d_inv;
+        let inverse: Matrix<f64, 5, 5> =
+            Matrix::from_blocks(s_inv, top_right, bottom_left, bottom_right);
+        assert_close(inverse, m.invert().unwrap());
+        assert_close(m * inverse, Matrix::identity());
+
+        // A singular D block is reported by the solver
+        let singular = Matrix::<f64, 2, 2>::block_diag(
+            Matrix::new([[1.0]]),
+            MulScalar::mul(Matrix::new([[1.0]]), 0.0),
+        );
+        assert_eq!(
+            singular.schur_complement::<1, 1, _>(|y: List<f64, 1>| solve(Matrix::new([[0.0]]), y)),
+            Err(SolveError::Singular)
+        );
+    }
+}
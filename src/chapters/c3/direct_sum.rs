@@ -0,0 +1,244 @@
+use std::ops::{Add, Neg};
+
+use crate::chapters::c1::axioms::ApproxEq;
+use crate::chapters::c1::definitions::{
+    AddInverse, Associative, Commutative, Distributive, Identity, Inverse, List, MulIdent,
+    MulScalar, One, Zero,
+};
+
+// The product V x W of two vector spaces over the same field (3.71 in the book), with
+// (v1, w1) + (v2, w2) = (v1 + v2, w1 + w2) and a(v, w) = (av, aw).
+// Every axiom holds componentwise, so the marker traits carry over from V and W.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DirectSum<V, W> {
+    first: V,
+    second: W,
+}
+
+impl<V, W> DirectSum<V, W> {
+    pub const fn from_parts(first: V, second: W) -> Self {
+        Self { first, second }
+    }
+
+    pub fn to_parts(self) -> (V, W) {
+        (self.first, self.second)
+    }
+
+    // Projections onto V and W
+    pub fn first(&self) -> &V {
+        &self.first
+    }
+
+    pub fn second(&self) -> &W {
+        &self.second
+    }
+
+    // Injections, (v, 0) and (0, w)
+    pub fn from_first(first: V) -> Self
+    where
+        W: Zero,
+    {
+        Self::from_parts(first, W::ZERO)
+    }
+
+    pub fn from_second(second: W) -> Self
+    where
+        V: Zero,
+    {
+        Self::from_parts(V::ZERO, second)
+    }
+}
+
+impl<V, W> From<(V, W)> for DirectSum<V, W> {
+    fn from((first, second): (V, W)) -> Self {
+        Self::from_parts(first, second)
+    }
+}
+
+// F^N x F^M is F^(N + M), const generics can't spell out N + M yet so the caller has to
+impl<T, const N: usize, const M: usize> DirectSum<List<T, N>, List<T, M>>
+where
+    T: Copy,
+{
+    // None unless K == N + M
+    pub fn concat<const K: usize>(&self) -> Option<List<T, K>> {
+        let first = self.first.iter();
+        let second = self.second.iter();
+        List::try_from_iter(first.chain(second).copied()).ok()
+    }
+}
+
+impl<V, W> Add for DirectSum<V, W>
+where
+    V: Add<Output = V>,
+    W: Add<Output = W>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from_parts(self.first + rhs.first, self.second + rhs.second)
+    }
+}
+
+impl<V, W> Neg for DirectSum<V, W>
+where
+    V: Neg<Output = V>,
+    W: Neg<Output = W>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::from_parts(-self.first, -self.second)
+    }
+}
+
+impl<V, W> AddInverse for DirectSum<V, W>
+where
+    V: AddInverse,
+    W: AddInverse,
+{
+    fn negate(self) -> Self {
+        Self::from_parts(self.first.negate(), self.second.negate())
+    }
+}
+
+impl<V, W, F> MulScalar<F> for DirectSum<V, W>
+where
+    V: MulScalar<F>,
+    W: MulScalar<F>,
+    F: Copy,
+{
+    fn mul(self, rhs: F) -> Self {
+        Self::from_parts(
+            MulScalar::mul(self.first, rhs),
+            MulScalar::mul(self.second, rhs),
+        )
+    }
+}
+
+impl<V, W> Zero for DirectSum<V, W>
+where
+    V: Zero,
+    W: Zero,
+{
+    const ZERO: Self = Self {
+        first: V::ZERO,
+        second: W::ZERO,
+    };
+}
+
+impl<V, W> ApproxEq for DirectSum<V, W>
+where
+    V: ApproxEq,
+    W: ApproxEq,
+{
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.first.approx_eq(&other.first) && self.second.approx_eq(&other.second)
+    }
+}
+
+impl<V, W> Commutative for DirectSum<V, W>
+where
+    V: Commutative,
+    W: Commutative,
+{
+}
+impl<V, W> Associative for DirectSum<V, W>
+where
+    V: Associative,
+    W: Associative,
+{
+}
+impl<V, W> Identity for DirectSum<V, W>
+where
+    V: Identity,
+    W: Identity,
+{
+}
+impl<V, W> Inverse for DirectSum<V, W>
+where
+    V: Inverse,
+    W: Inverse,
+{
+}
+impl<V, W, X> MulIdent<X> for DirectSum<V, W>
+where
+    V: MulIdent<X>,
+    W: MulIdent<X>,
+    X: One + Copy,
+{
+}
+impl<V, W, X> Distributive<X> for DirectSum<V, W>
+where
+    V: Distributive<X>,
+    W: Distributive<X>,
+    X: Copy,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::DirectSum;
+    use crate::chapters::c1::axioms::verify_vector_space;
+    use crate::chapters::c1::definitions::{Complex, List, MulScalar, VectorSpace, Zero};
+    use crate::chapters::c4::polynomial::Polynomial;
+
+    fn accept_field<T: VectorSpace<X>, X>() {}
+
+    #[test]
+    fn vector_space() {
+        accept_field::<DirectSum<List<f64, 2>, List<f64, 3>>, f64>();
+        accept_field::<DirectSum<Complex<f64>, List<Complex<f64>, 2>>, Complex<f64>>();
+        accept_field::<DirectSum<Polynomial<f64>, f64>, f64>();
+        // Products of products
+        accept_field::<DirectSum<DirectSum<f64, f64>, Complex<f64>>, f64>();
+    }
+
+    #[test]
+    fn componentwise() {
+        let u = DirectSum::from_parts(List::new([1.0, 2.0]), List::new([0.0, -1.0, 4.0]));
+        let v = DirectSum::from((List::new([0.5, 0.5]), List::new([1.0, 1.0, 1.0])));
+
+        assert_eq!(
+            (u + v).to_parts(),
+            (List::new([1.5, 2.5]), List::new([1.0, 0.0, 5.0]))
+        );
+        assert_eq!(*(-u).first(), List::new([-1.0, -2.0]));
+        assert_eq!(
+            *MulScalar::mul(u, 2.0).second(),
+            List::new([0.0, -2.0, 8.0])
+        );
+        assert_eq!(u + DirectSum::ZERO, u);
+    }
+
+    #[test]
+    fn projections_and_injections() {
+        let v = List::new([1.0, 2.0]);
+        let w = List::new([3.0, 4.0, 5.0]);
+        let sum = DirectSum::<_, List<f64, 3>>::from_first(v) + DirectSum::from_second(w);
+        assert_eq!(*sum.first(), v);
+        assert_eq!(*sum.second(), w);
+        assert_eq!(
+            *DirectSum::<List<f64, 2>, _>::from_second(w).first(),
+            List::ZERO
+        );
+
+        assert_eq!(
+            sum.concat::<5>(),
+            Some(List::new([1.0, 2.0, 3.0, 4.0, 5.0]))
+        );
+        assert_eq!(sum.concat::<4>(), None);
+    }
+
+    #[test]
+    fn axioms() {
+        let i = Complex::new(0.0, 1.0);
+        let vectors = [
+            DirectSum::from_parts(Complex::new(1.0, -2.0), List::new([i, Complex::from(3.0)])),
+            DirectSum::from_parts(Complex::ZERO, List::new([Complex::new(0.5, 0.5), -i])),
+            DirectSum::from_parts(Complex::new(-4.0, 0.25), List::ZERO),
+        ];
+        let scalars = [Complex::from(2.0), i, Complex::new(-1.5, 0.75)];
+        assert_eq!(verify_vector_space(&vectors, &scalars), Ok(()));
+    }
+}
@@ -0,0 +1,634 @@
+use super::banded::BandedMatrix;
+use super::matrix::Matrix;
+use super::solve::LuFactors;
+use crate::chapters::c1::definitions::List;
+
+// Square systems given row by row, all that the iterative solvers below need. Sparse matrices
+// only visit the entries that can be nonzero, so a sweep costs O(nonzeros) instead of O(n^2).
+pub trait SparseRows {
+    fn dimension(&self) -> usize;
+
+    // f(j, a_ij) for every column j of row i that can be nonzero
+    fn row_entries(&self, i: usize, f: impl FnMut(usize, f64));
+
+    fn diagonal(&self, i: usize) -> f64 {
+        let mut d = 0.0;
+        self.row_entries(i, |j, a| {
+            if j == i {
+                d = a;
+            }
+        });
+        d
+    }
+
+    fn mul_slice(&self, x: &[f64]) -> Vec<f64> {
+        (0..self.dimension())
+            .map(|i| {
+                let mut s = 0.0;
+                self.row_entries(i, |j, a| s += a * x[j]);
+                s
+            })
+            .collect()
+    }
+
+    // A^T x, scattered from the rows
+    fn mul_transpose_slice(&self, x: &[f64]) -> Vec<f64> {
+        let mut y = vec![0.0; self.dimension()];
+        for (i, &xi) in x.iter().enumerate() {
+            self.row_entries(i, |j, a| y[j] += a * xi);
+        }
+        y
+    }
+
+    // The largest singular value |A|_2, from power iteration on A^T A with one product with A and
+    // one with A^T per step, so the matrix never needs to be dense. See power_norm.
+    fn norm_two_estimate(&self, tol: f64, max_iter: usize) -> Result<f64, IterativeError> {
+        power_norm(
+            self.dimension(),
+            |x| self.mul_slice(x),
+            |x| self.mul_transpose_slice(x),
+            tol,
+            max_iter,
+        )
+    }
+
+    // Jacobi: every x_i is solved from row i with the other entries of the previous x,
+    //  x_i <- (b_i - sum_{j != i} a_ij x_j) / a_ii
+    // Converges for strictly diagonally dominant matrices.
+    fn solve_jacobi(
+        &self,
+        b: &[f64],
+        x0: &[f64],
+        tol: f64,
+        max_iter: usize,
+    ) -> Result<Iterated, IterativeError>
+    where
+        Self: Sized,
+    {
+        iterate(self, b, x0, tol, max_iter, |x| {
+            let previous = x.to_vec();
+            for (i, xi) in x.iter_mut().enumerate() {
+                *xi = relax(self, b, &previous, i);
+            }
+        })
+    }
+
+    // Gauss-Seidel: like Jacobi, but the new x_j are used as soon as they are known. Converges
+    // for diagonally dominant and for symmetric positive definite matrices, usually about twice
+    // as fast as Jacobi, and needs no second copy of x.
+    fn solve_gauss_seidel(
+        &self,
+        b: &[f64],
+        x0: &[f64],
+        tol: f64,
+        max_iter: usize,
+    ) -> Result<Iterated, IterativeError>
+    where
+        Self: Sized,
+    {
+        iterate(self, b, x0, tol, max_iter, |x| {
+            for i in 0..x.len() {
+                x[i] = relax(self, b, x, i);
+            }
+        })
+    }
+
+    // Preconditioned conjugate gradients for symmetric positive definite A. Every step
+    // minimizes the A-norm of the error over one more dimension of the Krylov space
+    // span(z_0, M A z_0, ...), so in exact arithmetic it is done after n steps, and long before
+    // that when the eigenvalues of M A cluster. Each step costs one product with A and one
+    // application of the preconditioner M ~ A^-1. A p with p^T A p <= 0 proves A isn't
+    // positive definite, which is reported instead of dividing by it.
+    fn solve_cg(
+        &self,
+        b: &[f64],
+        x0: &[f64],
+        tol: f64,
+        max_iter: usize,
+        preconditioner: &impl Preconditioner,
+    ) -> Result<Iterated, IterativeError>
+    where
+        Self: Sized,
+    {
+        let n = self.dimension();
+        assert!(b.len() == n && x0.len() == n, "wrong length");
+        let dot = |u: &[f64], v: &[f64]| u.iter().zip(v).map(|(a, b)| a * b).sum::<f64>();
+
+        let target = tol * norm(b);
+        let mut x = x0.to_vec();
+        let mut r: Vec<f64> = b
+            .iter()
+            .zip(self.mul_slice(&x))
+            .map(|(b, ax)| b - ax)
+            .collect();
+        let mut z = preconditioner.apply(&r);
+        let mut p = z.clone();
+        let mut rz = dot(&r, &z);
+        let mut history = vec![norm(&r)];
+        for iterations in 0..=max_iter {
+            if history[iterations] <= target {
+                return Ok(Iterated {
+                    residual: history[iterations],
+                    x,
+                    iterations,
+                    history,
+                });
+            }
+            if iterations == max_iter {
+                break;
+            }
+
+            let ap = self.mul_slice(&p);
+            let pap = dot(&p, &ap);
+            if pap.is_nan() || pap <= 0.0 {
+                return Err(IterativeError::NotPositiveDefinite { iterations });
+            }
+            let alpha = rz / pap;
+            for i in 0..n {
+                x[i] += alpha * p[i];
+                r[i] -= alpha * ap[i];
+            }
+            history.push(norm(&r));
+
+            z = preconditioner.apply(&r);
+            let next = dot(&r, &z);
+            let beta = next / rz;
+            rz = next;
+            for i in 0..n {
+                p[i] = z[i] + beta * p[i];
+            }
+        }
+        Err(IterativeError::NoConvergence {
+            iterations: max_iter,
+            residual: history[max_iter],
+        })
+    }
+}
+
+// An approximation of A^-1 for solve_cg, it has to be symmetric and positive definite itself
+pub trait Preconditioner {
+    fn apply(&self, r: &[f64]) -> Vec<f64>;
+}
+
+// No preconditioning, plain conjugate gradients
+pub struct Identity;
+
+impl Preconditioner for Identity {
+    fn apply(&self, r: &[f64]) -> Vec<f64> {
+        r.to_vec()
+    }
+}
+
+// Divides by the diagonal of A, which undoes a bad scaling of the rows and columns
+pub struct JacobiPreconditioner {
+    inverse_diagonal: Vec<f64>,
+}
+
+impl JacobiPreconditioner {
+    // Fails like the Jacobi iteration when some a_ii is zero
+    pub fn new(a: &impl SparseRows) -> Result<Self, IterativeError> {
+        let n = a.dimension();
+        if let Some(i) = (0..n).find(|&i| a.diagonal(i) == 0.0) {
+            return Err(IterativeError::ZeroDiagonal(i));
+        }
+        Ok(JacobiPreconditioner {
+            inverse_diagonal: (0..n).map(|i| 1.0 / a.diagonal(i)).collect(),
+        })
+    }
+}
+
+impl Preconditioner for JacobiPreconditioner {
+    fn apply(&self, r: &[f64]) -> Vec<f64> {
+        r.iter()
+            .zip(&self.inverse_diagonal)
+            .map(|(r, d)| r * d)
+            .collect()
+    }
+}
+
+impl<const N: usize> SparseRows for Matrix<f64, N, N> {
+    fn dimension(&self) -> usize {
+        N
+    }
+
+    fn row_entries(&self, i: usize, mut f: impl FnMut(usize, f64)) {
+        for j in 0..N {
+            f(j, self[(i, j)]);
+        }
+    }
+}
+
+impl SparseRows for BandedMatrix<f64> {
+    fn dimension(&self) -> usize {
+        self.n()
+    }
+
+    fn row_entries(&self, i: usize, mut f: impl FnMut(usize, f64)) {
+        let (lower, upper) = self.bandwidths();
+        for j in i.saturating_sub(lower)..(i + upper + 1).min(self.n()) {
+            f(j, self.get(i, j));
+        }
+    }
+}
+
+impl<const N: usize> Matrix<f64, N, N> {
+    // |A|_2 |A^-1|_2 = sigma_max / sigma_min. |A^-1|_2 comes from the same power iteration as
+    // norm_two_estimate, with the products replaced by solves with the LU factors of A and A^T.
+    // Infinite when A is singular to working precision.
+    pub fn condition_two_estimate(&self, tol: f64, max_iter: usize) -> Result<f64, IterativeError> {
+        let (Ok(lu), Ok(lu_t)) = (self.lu(), self.transpose().lu()) else {
+            return Ok(f64::INFINITY);
+        };
+        let inverse = power_norm(
+            N,
+            |x| solve_with(&lu, x),
+            |x| solve_with(&lu_t, x),
+            tol,
+            max_iter,
+        )?;
+        Ok(self.norm_two_estimate(tol, max_iter)? * inverse)
+    }
+}
+
+// Only fails on an exact zero on the diagonal of U, which lu has already ruled out
+fn solve_with<const N: usize>(lu: &LuFactors<f64, N>, x: &[f64]) -> Vec<f64> {
+    match lu.solve(&List::from_fn(|i| x[i])) {
+        Ok(y) => y.as_slice().to_vec(),
+        Err(_) => vec![f64::INFINITY; N],
+    }
+}
+
+// max |A v| over unit v for the operator given by x -> A x and x -> A^T x.
+// Every step maps v to A^T A v normalized, which turns towards the right singular vector of the
+// largest singular value at the rate (sigma_2 / sigma_1)^2, and sigma = |A v| gets there twice as
+// fast. Stops when |A^T A v - sigma^2 v| <= tol sigma^2, fails after max_iter steps with that
+// relative residual. The start vector has irrational steps between its entries, so it is
+// unlikely to be orthogonal to the singular vector, but a matrix that is zero on it gives 0.
+fn power_norm(
+    n: usize,
+    apply: impl Fn(&[f64]) -> Vec<f64>,
+    apply_transpose: impl Fn(&[f64]) -> Vec<f64>,
+    tol: f64,
+    max_iter: usize,
+) -> Result<f64, IterativeError> {
+    let start: Vec<f64> = (0..n)
+        .map(|i| 1.0 + (i as f64 * 0.618_033_988_75).fract())
+        .collect();
+    let mut v: Vec<f64> = start.iter().map(|x| x / norm(&start)).collect();
+    let mut residual = f64::INFINITY;
+    for _ in 0..max_iter {
+        let av = apply(&v);
+        let sigma = norm(&av);
+        if sigma == 0.0 {
+            return Ok(0.0);
+        }
+        let w = apply_transpose(&av);
+        residual = norm(
+            &w.iter()
+                .zip(&v)
+                .map(|(w, v)| w - sigma * sigma * v)
+                .collect::<Vec<_>>(),
+        ) / (sigma * sigma);
+        if residual <= tol {
+            return Ok(sigma);
+        }
+        let length = norm(&w);
+        v = w.iter().map(|w| w / length).collect();
+    }
+    Err(IterativeError::NoConvergence {
+        iterations: max_iter,
+        residual,
+    })
+}
+
+// The solution with the number of sweeps (or conjugate gradient steps) it took, the final
+// |b - A x| and that residual norm after every sweep, starting with the one of x0
+#[derive(Debug, Clone, PartialEq)]
+pub struct Iterated {
+    pub x: Vec<f64>,
+    pub iterations: usize,
+    pub residual: f64,
+    pub history: Vec<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IterativeError {
+    // a_ii = 0, the update for x_i would divide by it
+    ZeroDiagonal(usize),
+    // The residual grew past DIVERGED times its initial value, or stopped being a number
+    Diverged { iterations: usize, residual: f64 },
+    // Still above tolerance after max_iter sweeps
+    NoConvergence { iterations: usize, residual: f64 },
+    // Conjugate gradients found a direction p with p^T A p <= 0
+    NotPositiveDefinite { iterations: usize },
+}
+
+pub const DIVERGED: f64 = 1e8;
+
+fn norm(x: &[f64]) -> f64 {
+    x.iter().fold(0.0, |acc: f64, &t| acc.hypot(t))
+}
+
+fn residual(a: &impl SparseRows, b: &[f64], x: &[f64]) -> f64 {
+    let ax = a.mul_slice(x);
+    norm(&b.iter().zip(ax).map(|(b, ax)| b - ax).collect::<Vec<_>>())
+}
+
+// x_i solved from row i with the other entries of x
+fn relax(a: &impl SparseRows, b: &[f64], x: &[f64], i: usize) -> f64 {
+    let (mut s, mut d) = (b[i], 0.0);
+    a.row_entries(i, |j, aij| {
+        if j == i {
+            d = aij;
+        } else {
+            s -= aij * x[j];
+        }
+    });
+    s / d
+}
+
+// Runs sweep until |b - A x| <= tol |b|
+fn iterate(
+    a: &impl SparseRows,
+    b: &[f64],
+    x0: &[f64],
+    tol: f64,
+    max_iter: usize,
+    mut sweep: impl FnMut(&mut [f64]),
+) -> Result<Iterated, IterativeError> {
+    let n = a.dimension();
+    assert!(b.len() == n && x0.len() == n, "wrong length");
+    if let Some(i) = (0..n).find(|&i| a.diagonal(i) == 0.0) {
+        return Err(IterativeError::ZeroDiagonal(i));
+    }
+
+    let target = tol * norm(b);
+    let mut x = x0.to_vec();
+    let mut history = vec![residual(a, b, &x)];
+    for iterations in 0..=max_iter {
+        let r = history[iterations];
+        if r <= target {
+            return Ok(Iterated {
+                x,
+                iterations,
+                residual: r,
+                history,
+            });
+        }
+        if !r.is_finite() || r > DIVERGED * history[0] {
+            return Err(IterativeError::Diverged {
+                iterations,
+                residual: r,
+            });
+        }
+        if iterations == max_iter {
+            break;
+        }
+        sweep(&mut x);
+        history.push(residual(a, b, &x));
+    }
+    Err(IterativeError::NoConvergence {
+        iterations: max_iter,
+        residual: history[max_iter],
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Identity, IterativeError, JacobiPreconditioner, SparseRows};
+    use crate::chapters::c1::definitions::List;
+    use crate::chapters::c3::banded::BandedMatrix;
+    use crate::chapters::c3::matrix::Matrix;
+    use crate::chapters::c3::solve::solve;
+
+    fn dominant() -> Matrix<f64, 4, 4> {
+        Matrix::new([
+            [10.0, -1.0, 2.0, 0.0],
+            [-1.0, 11.0, -1.0, 3.0],
+            [2.0, -1.0, 10.0, -1.0],
+            [0.0, 3.0, -1.0, 8.0],
+        ])
+    }
+
+    #[test]
+    fn converge_to_direct_solution() {
+        let a = dominant();
+        let b = [6.0, 25.0, -11.0, 15.0];
+        let exact = solve(a, List::new(b)).unwrap();
+
+        let jacobi = a.solve_jacobi(&b, &[0.0; 4], 1e-12, 100).unwrap();
+        let gauss_seidel = a.solve_gauss_seidel(&b, &[0.0; 4], 1e-12, 100).unwrap();
+        for result in [&jacobi, &gauss_seidel] {
+            for i in 0..4 {
+                assert!((result.x[i] - exact[i]).abs() < 1e-10);
+            }
+            assert_eq!(result.history.len(), result.iterations + 1);
+            assert_eq!(result.residual, *result.history.last().unwrap());
+            assert!(result.history.windows(2).all(|w| w[1] < w[0]));
+        }
+        assert!(gauss_seidel.iterations < jacobi.iterations);
+
+        // Starting at the solution takes no sweeps
+        let exact = exact.into_array();
+        assert_eq!(
+            a.solve_jacobi(&b, &exact, 1e-12, 100).unwrap().iterations,
+            0
+        );
+
+        // Too few sweeps
+        assert!(matches!(
+            a.solve_jacobi(&b, &[0.0; 4], 1e-12, 3),
+            Err(IterativeError::NoConvergence { iterations: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn banded() {
+        let n = 50;
+        let m = BandedMatrix::from_diagonals(
+            n,
+            1,
+            1,
+            &[&vec![-1.0; n - 1], &vec![4.0; n], &vec![-1.0; n - 1]],
+        );
+        let b: Vec<f64> = (0..n).map(|i| (i as f64).sin()).collect();
+        let exact = m.solve(&b).unwrap();
+        let result = m.solve_gauss_seidel(&b, &vec![0.0; n], 1e-13, 200).unwrap();
+        for (x, e) in result.x.iter().zip(&exact) {
+            assert!((x - e).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn divergence_and_zero_diagonal() {
+        // Far from diagonally dominant, the iteration matrix has spectral radius 3
+        let a = Matrix::new([[1.0, 3.0], [3.0, 1.0]]);
+        let found = a.solve_jacobi(&[1.0, 1.0], &[0.0, 1.0], 1e-12, 1000);
+        let Err(IterativeError::Diverged {
+            iterations,
+            residual,
+        }) = found
+        else {
+            panic!("{found:?}")
+        };
+        assert!(iterations < 1000 && residual > 1e8);
+
+        let a = Matrix::new([[1.0, 2.0], [2.0, 0.0]]);
+        assert_eq!(
+            a.solve_gauss_seidel(&[1.0, 1.0], &[0.0, 0.0], 1e-12, 100),
+            Err(IterativeError::ZeroDiagonal(1))
+        );
+        assert!(matches!(
+            JacobiPreconditioner::new(&a),
+            Err(IterativeError::ZeroDiagonal(1))
+        ));
+    }
+
+    // -(k u')' = f on a grid of n points, k is the diffusion coefficient between the points
+    fn diffusion(n: usize, k: impl Fn(usize) -> f64) -> BandedMatrix<f64> {
+        let off: Vec<f64> = (1..n).map(|i| -k(i)).collect();
+        let diagonal: Vec<f64> = (0..n).map(|i| k(i) + k(i + 1)).collect();
+        BandedMatrix::from_diagonals(n, 1, 1, &[&off, &diagonal, &off])
+    }
+
+    #[test]
+    fn conjugate_gradients() {
+        let n = 200;
+        let poisson = diffusion(n, |_| 1.0);
+        let h = 1.0 / (n + 1) as f64;
+        let b: Vec<f64> = (0..n).map(|i| h * h * ((i + 1) as f64 * h).exp()).collect();
+        let result = poisson
+            .solve_cg(&b, &vec![0.0; n], 1e-10, n, &Identity)
+            .unwrap();
+        let exact = poisson.solve(&b).unwrap();
+        assert!(result.iterations <= n);
+        assert_eq!(result.history.len(), result.iterations + 1);
+        for (x, e) in result.x.iter().zip(&exact) {
+            assert!((x - e).abs() < 1e-6 * e.abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn preconditioning() {
+        // Diagonally dominant with a diagonal spread over four orders of magnitude. Dividing by
+        // the diagonal leaves something close to the identity.
+        let n = 100;
+        let off = vec![-0.5; n - 1];
+        let diagonal: Vec<f64> = (0..n)
+            .map(|i| 2.0 * 10f64.powf(4.0 * i as f64 / n as f64))
+            .collect();
+        let a = BandedMatrix::from_diagonals(n, 1, 1, &[&off, &diagonal, &off]);
+        let b: Vec<f64> = (0..n).map(|i| (i as f64 * 0.3).cos()).collect();
+        let plain = a
+            .solve_cg(&b, &vec![0.0; n], 1e-10, 10 * n, &Identity)
+            .unwrap();
+        let jacobi = JacobiPreconditioner::new(&a).unwrap();
+        let preconditioned = a
+            .solve_cg(&b, &vec![0.0; n], 1e-10, 10 * n, &jacobi)
+            .unwrap();
+        assert!(10 * preconditioned.iterations < plain.iterations);
+        for (x, y) in plain.x.iter().zip(&preconditioned.x) {
+            assert!((x - y).abs() < 1e-6 * x.abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn not_positive_definite() {
+        let a = Matrix::new([[1.0, 2.0], [2.0, 1.0]]);
+        assert_eq!(
+            a.solve_cg(&[1.0, -1.0], &[0.0, 0.0], 1e-12, 10, &Identity),
+            Err(IterativeError::NotPositiveDefinite { iterations: 0 })
+        );
+    }
+
+    #[test]
+    fn two_norm() {
+        // Against the largest singular value of the SVD, for a non symmetric matrix
+        let a = dominant();
+        let mut b = a;
+        b[(0, 3)] = -4.0;
+        b[(2, 0)] = 7.0;
+        for m in [a, b] {
+            let sigma = m.svd().singular_values()[0];
+            let estimate = m.norm_two_estimate(1e-12, 1000).unwrap();
+            assert!((estimate - sigma).abs() < 1e-10 * sigma);
+        }
+
+        // The transposed product only visits the band too
+        let x: Vec<f64> = (0..6).map(|i| i as f64 - 2.5).collect();
+        let m = BandedMatrix::from_diagonals(
+            6,
+            1,
+            2,
+            &[
+                &[1.0; 5],
+                &[4.0, -3.0, 2.0, 5.0, 1.0, -2.0],
+                &[0.5; 5],
+                &[-1.0; 4],
+            ],
+        );
+        let dense = m.to_matrix::<6>();
+        let expected = dense.transpose() * List::<f64, 6>::from_fn(|i| x[i]);
+        assert_eq!(m.mul_transpose_slice(&x), expected.as_slice());
+        let sigma = dense.svd().singular_values()[0];
+        let estimate = m.norm_two_estimate(1e-12, 1000).unwrap();
+        assert!((estimate - sigma).abs() < 1e-10 * sigma);
+    }
+
+    #[test]
+    fn rank_one() {
+        // A single non zero row (0, 3, 4, 12, 0, 0) in a banded matrix, |A|_2 is its length 13
+        let mut m = BandedMatrix::new(6, 1, 2);
+        m.set(2, 1, 3.0).unwrap();
+        m.set(2, 2, 4.0).unwrap();
+        m.set(2, 3, 12.0).unwrap();
+        let estimate = m.norm_two_estimate(1e-14, 10).unwrap();
+        assert!((estimate - 13.0).abs() < 1e-13);
+
+        assert_eq!(
+            BandedMatrix::<f64>::new(4, 1, 1).norm_two_estimate(1e-12, 10),
+            Ok(0.0)
+        );
+    }
+
+    #[test]
+    fn two_norm_no_convergence() {
+        // Two nearly equal singular values make the power iteration crawl
+        let m = Matrix::new([[1.0, 0.0, 0.0], [0.0, 0.999, 0.0], [0.0, 0.0, 0.5]]);
+        match m.norm_two_estimate(1e-12, 5) {
+            Err(IterativeError::NoConvergence {
+                iterations,
+                residual,
+            }) => {
+                assert_eq!(iterations, 5);
+                assert!(residual > 1e-12);
+            }
+            other => panic!("{other:?}"),
+        }
+        assert!(m.norm_two_estimate(1e-12, 100_000).is_ok());
+    }
+
+    #[test]
+    fn condition_number() {
+        let a = Matrix::new([
+            [4.0, -2.0, 1.0, 0.5],
+            [3.0, 6.0, -4.0, 1.0],
+            [2.0, 1.0, 8.0, -1.0],
+            [0.0, 1.0, 0.5, 0.1],
+        ]);
+        let sigma = a.svd().singular_values().as_slice().to_vec();
+        let estimate = a.condition_two_estimate(1e-12, 1000).unwrap();
+        let exact = sigma[0] / sigma[3];
+        assert!((estimate - exact).abs() < 1e-8 * exact);
+
+        let singular = Matrix::new([[1.0, 2.0], [2.0, 4.0]]);
+        assert_eq!(
+            singular.condition_two_estimate(1e-12, 100),
+            Ok(f64::INFINITY)
+        );
+        assert_eq!(
+            Matrix::<f64, 3, 3>::identity().condition_two_estimate(1e-12, 100),
+            Ok(1.0)
+        );
+    }
+}
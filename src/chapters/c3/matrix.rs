@@ -0,0 +1,746 @@
+use std::fmt::{Alignment, Debug, Display, Formatter, Write};
+use std::ops::{Add, Index, IndexMut, Mul, Neg};
+
+use crate::chapters::c1::definitions::{List, MulScalar, One, Zero, write_forwarded};
+
+// R x C matrix, stored as R rows of length C unless the layout says otherwise.
+// A matrix is the linear map List<T, C> -> List<T, R>, so mismatched products don't compile:
+/// ```compile_fail
+/// use linear_algebra::chapters::c3::matrix::Matrix;
+///
+/// let a = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+/// let _ = a * a;
+/// ```
+pub struct Matrix<T, const R: usize, const C: usize, L: Layout = RowMajor> {
+    data: L::Storage<T, R, C>,
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+// How the entries of a matrix are laid out in memory. Everything works for both layouts,
+// but walking along the stored lists is what's fast: rows for RowMajor, columns for ColMajor
+pub trait Layout: sealed::Sealed {
+    type Storage<T, const R: usize, const C: usize>;
+
+    fn entry<T, const R: usize, const C: usize>(
+        data: &Self::Storage<T, R, C>,
+        i: usize,
+        j: usize,
+    ) -> &T;
+
+    fn entry_mut<T, const R: usize, const C: usize>(
+        data: &mut Self::Storage<T, R, C>,
+        i: usize,
+        j: usize,
+    ) -> &mut T;
+
+    fn from_fn<T, const R: usize, const C: usize>(
+        f: impl FnMut(usize, usize) -> T,
+    ) -> Self::Storage<T, R, C>;
+}
+
+// R rows of length C, the default
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowMajor;
+
+// C columns of length R, the convention of LAPACK and nalgebra
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColMajor;
+
+impl sealed::Sealed for RowMajor {}
+impl sealed::Sealed for ColMajor {}
+
+impl Layout for RowMajor {
+    type Storage<T, const R: usize, const C: usize> = [List<T, C>; R];
+
+    fn entry<T, const R: usize, const C: usize>(data: &[List<T, C>; R], i: usize, j: usize) -> &T {
+        &data[i][j]
+    }
+
+    fn entry_mut<T, const R: usize, const C: usize>(
+        data: &mut [List<T, C>; R],
+        i: usize,
+        j: usize,
+    ) -> &mut T {
+        &mut data[i][j]
+    }
+
+    fn from_fn<T, const R: usize, const C: usize>(
+        mut f: impl FnMut(usize, usize) -> T,
+    ) -> [List<T, C>; R] {
+        core::array::from_fn(|i| List::from_fn(|j| f(i, j)))
+    }
+}
+
+impl Layout for ColMajor {
+    type Storage<T, const R: usize, const C: usize> = [List<T, R>; C];
+
+    fn entry<T, const R: usize, const C: usize>(data: &[List<T, R>; C], i: usize, j: usize) -> &T {
+        &data[j][i]
+    }
+
+    fn entry_mut<T, const R: usize, const C: usize>(
+        data: &mut [List<T, R>; C],
+        i: usize,
+        j: usize,
+    ) -> &mut T {
+        &mut data[j][i]
+    }
+
+    fn from_fn<T, const R: usize, const C: usize>(
+        mut f: impl FnMut(usize, usize) -> T,
+    ) -> [List<T, R>; C] {
+        core::array::from_fn(|j| List::from_fn(|i| f(i, j)))
+    }
+}
+
+// What derive would give, but bounded on the storage since that depends on the layout
+impl<T, const R: usize, const C: usize, L: Layout> Clone for Matrix<T, R, C, L>
+where
+    L::Storage<T, R, C>: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<T, const R: usize, const C: usize, L: Layout> Copy for Matrix<T, R, C, L> where
+    L::Storage<T, R, C>: Copy
+{
+}
+
+impl<T, const R: usize, const C: usize, L: Layout> PartialEq for Matrix<T, R, C, L>
+where
+    L::Storage<T, R, C>: PartialEq,
+{
+    fn eq(&self, rhs: &Self) -> bool {
+        self.data == rhs.data
+    }
+}
+
+impl<T, const R: usize, const C: usize, L: Layout> Debug for Matrix<T, R, C, L>
+where
+    L::Storage<T, R, C>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Matrix").field("data", &self.data).finish()
+    }
+}
+
+impl<T, const R: usize, const C: usize, L: Layout> Matrix<T, R, C, L> {
+    // The same matrix in another layout, e.g. a.to_layout::<ColMajor>()
+    pub fn to_layout<M: Layout>(&self) -> Matrix<T, R, C, M>
+    where
+        T: Copy,
+    {
+        Matrix {
+            data: M::from_fn(|i, j| self[(i, j)]),
+        }
+    }
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn new(rows: [[T; C]; R]) -> Self {
+        Self {
+            data: rows.map(List::new),
+        }
+    }
+
+    pub const fn from_rows(rows: [List<T, C>; R]) -> Self {
+        Self { data: rows }
+    }
+
+    pub fn from_fn(f: impl FnMut(usize, usize) -> T) -> Self {
+        Self {
+            data: RowMajor::from_fn(f),
+        }
+    }
+
+    pub fn row(&self, i: usize) -> &List<T, C> {
+        &self.data[i]
+    }
+
+    pub fn rows_mut(&mut self) -> &mut [List<T, C>; R] {
+        &mut self.data
+    }
+
+    // A copy, the entries of a column are spread over all rows
+    pub fn col(&self, j: usize) -> List<T, R>
+    where
+        T: Copy,
+    {
+        List::from_fn(|i| self.data[i][j])
+    }
+
+    pub fn swap_rows(&mut self, i: usize, j: usize) {
+        self.data.swap(i, j);
+    }
+
+    pub fn transpose(&self) -> Matrix<T, C, R>
+    where
+        T: Copy,
+    {
+        Matrix::from_fn(|i, j| self[(j, i)])
+    }
+
+    // The rows of a row major matrix are the columns of its transpose in column major order,
+    // so this moves no entries around
+    pub fn into_transpose(self) -> Matrix<T, C, R, ColMajor> {
+        Matrix { data: self.data }
+    }
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C, ColMajor> {
+    pub const fn from_cols(cols: [List<T, R>; C]) -> Self {
+        Self { data: cols }
+    }
+
+    pub fn col(&self, j: usize) -> &List<T, R> {
+        &self.data[j]
+    }
+
+    // A copy, the entries of a row are spread over all columns
+    pub fn row(&self, i: usize) -> List<T, C>
+    where
+        T: Copy,
+    {
+        List::from_fn(|j| self.data[j][i])
+    }
+
+    pub fn swap_cols(&mut self, i: usize, j: usize) {
+        self.data.swap(i, j);
+    }
+
+    pub fn transpose(&self) -> Matrix<T, C, R, ColMajor>
+    where
+        T: Copy,
+    {
+        Matrix {
+            data: ColMajor::from_fn(|i, j| self[(j, i)]),
+        }
+    }
+
+    pub fn into_transpose(self) -> Matrix<T, C, R> {
+        Matrix { data: self.data }
+    }
+}
+
+impl<T, const N: usize> Matrix<T, N, N>
+where
+    T: Zero + One,
+{
+    pub fn identity() -> Self {
+        Self::from_fn(|i, j| if i == j { T::ONE } else { T::ZERO })
+    }
+}
+
+impl<T, const R: usize, const C: usize> From<[[T; C]; R]> for Matrix<T, R, C> {
+    fn from(rows: [[T; C]; R]) -> Self {
+        Self::new(rows)
+    }
+}
+
+// One row per line. Precision and the + flag go to every entry, and every column is padded to
+// its widest entry (at least the width of the formatter) with its fill and alignment, so the
+// columns line up. Entries are right aligned unless asked otherwise.
+// {:#} prints [[a, b], [c, d]] with a row per line, which numpy.array accepts as is
+impl<T, const R: usize, const C: usize, L: Layout> Display for Matrix<T, R, C, L>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_entries(f, R, C, |i, j| &self[(i, j)])
+    }
+}
+
+// The Display of an rows x cols matrix with the given entries, for anything that looks like one
+pub(crate) fn write_entries<'a, T: Display + 'a>(
+    f: &mut Formatter<'_>,
+    rows: usize,
+    cols: usize,
+    entry: impl Fn(usize, usize) -> &'a T,
+) -> std::fmt::Result {
+    let mut entries = Vec::with_capacity(rows);
+    for i in 0..rows {
+        let mut formatted = Vec::with_capacity(cols);
+        for j in 0..cols {
+            let mut s = String::new();
+            write_forwarded(&mut s, entry(i, j), f)?;
+            formatted.push(s);
+        }
+        entries.push(formatted);
+    }
+    let widths: Vec<usize> = (0..cols)
+        .map(|j| {
+            let widest = entries.iter().map(|row| row[j].chars().count()).max();
+            widest.unwrap_or(0).max(f.width().unwrap_or(0))
+        })
+        .collect();
+
+    let (open, close, separator) = if f.alternate() {
+        ('[', ']', ",\n ")
+    } else {
+        ('(', ')', "\n")
+    };
+    if f.alternate() {
+        f.write_char('[')?;
+    }
+    for (i, row) in entries.iter().enumerate() {
+        if i > 0 {
+            f.write_str(separator)?;
+        }
+        f.write_char(open)?;
+        for (j, entry) in row.iter().enumerate() {
+            if j > 0 {
+                f.write_str(", ")?;
+            }
+            pad_entry(f, entry, widths[j])?;
+        }
+        f.write_char(close)?;
+    }
+    if f.alternate() {
+        f.write_char(']')?;
+    }
+    Ok(())
+}
+
+fn pad_entry(f: &mut Formatter<'_>, s: &str, width: usize) -> std::fmt::Result {
+    let padding = width.saturating_sub(s.chars().count());
+    let (before, after) = match f.align() {
+        None | Some(Alignment::Right) => (padding, 0),
+        Some(Alignment::Left) => (0, padding),
+        Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+    };
+
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    f.write_str(s)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+impl<T, const R: usize, const C: usize, L: Layout> Index<(usize, usize)> for Matrix<T, R, C, L> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        L::entry(&self.data, i, j)
+    }
+}
+
+impl<T, const R: usize, const C: usize, L: Layout> IndexMut<(usize, usize)> for Matrix<T, R, C, L> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        L::entry_mut(&mut self.data, i, j)
+    }
+}
+
+impl<T, const R: usize, const C: usize> Add for Matrix<T, R, C>
+where
+    T: Add<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        for (a, b) in self.data.iter_mut().zip(rhs.data.iter()) {
+            a.add_assign_ref(b);
+        }
+        self
+    }
+}
+
+impl<T, const R: usize, const C: usize> Neg for Matrix<T, R, C>
+where
+    T: Neg<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn neg(mut self) -> Self::Output {
+        for row in self.data.iter_mut() {
+            row.neg_mut();
+        }
+        self
+    }
+}
+
+impl<T, const R: usize, const C: usize> MulScalar<T> for Matrix<T, R, C>
+where
+    T: Mul<Output = T> + Copy,
+{
+    fn mul(mut self, rhs: T) -> Self {
+        for row in self.data.iter_mut() {
+            row.scale_mut(rhs);
+        }
+        self
+    }
+}
+
+impl<T, const R: usize, const C: usize> Zero for Matrix<T, R, C>
+where
+    T: Zero,
+{
+    const ZERO: Self = Self {
+        data: [List::ZERO; R],
+    };
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    // out = self rhs, written into storage the caller already has.
+    // Row i of the product is sum_j A_ij (row j of B), accumulated with axpy so both
+    // matrices are walked along their rows
+    pub fn mul_into<const K: usize>(&self, rhs: &Matrix<T, C, K>, out: &mut Matrix<T, R, K>) {
+        for (row, lhs) in out.data.iter_mut().zip(self.data.iter()) {
+            *row = List::ZERO;
+            for (a, b) in lhs.iter().zip(rhs.data.iter()) {
+                row.axpy(*a, b);
+            }
+        }
+    }
+}
+
+// (AB)_ik = sum_j A_ij B_jk
+impl<T, const R: usize, const C: usize, const K: usize> Mul<Matrix<T, C, K>> for Matrix<T, R, C>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, rhs: Matrix<T, C, K>) -> Self::Output {
+        let mut out = Matrix::ZERO;
+        self.mul_into(&rhs, &mut out);
+        out
+    }
+}
+
+// The matrix applied as a linear map to a vector
+impl<T, const R: usize, const C: usize> Mul<List<T, C>> for Matrix<T, R, C>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = List<T, R>;
+
+    fn mul(self, rhs: List<T, C>) -> Self::Output {
+        List::from_fn(|i| (0..C).fold(T::ZERO, |acc, j| acc + self[(i, j)] * rhs[j]))
+    }
+}
+
+impl<T, const R: usize, const C: usize> Add for Matrix<T, R, C, ColMajor>
+where
+    T: Add<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        for (a, b) in self.data.iter_mut().zip(rhs.data.iter()) {
+            a.add_assign_ref(b);
+        }
+        self
+    }
+}
+
+impl<T, const R: usize, const C: usize> Neg for Matrix<T, R, C, ColMajor>
+where
+    T: Neg<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn neg(mut self) -> Self::Output {
+        for col in self.data.iter_mut() {
+            col.neg_mut();
+        }
+        self
+    }
+}
+
+impl<T, const R: usize, const C: usize> MulScalar<T> for Matrix<T, R, C, ColMajor>
+where
+    T: Mul<Output = T> + Copy,
+{
+    fn mul(mut self, rhs: T) -> Self {
+        for col in self.data.iter_mut() {
+            col.scale_mut(rhs);
+        }
+        self
+    }
+}
+
+impl<T, const R: usize, const C: usize> Zero for Matrix<T, R, C, ColMajor>
+where
+    T: Zero,
+{
+    const ZERO: Self = Self {
+        data: [List::ZERO; C],
+    };
+}
+
+// The products below add the terms in the same order as the row major one, so every layout
+// gives bit for bit the same result. Only the order of memory accesses differs.
+
+// A x = sum_j x_j (column j of A)
+impl<T, const R: usize, const C: usize> Mul<List<T, C>> for Matrix<T, R, C, ColMajor>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = List<T, R>;
+
+    fn mul(self, rhs: List<T, C>) -> Self::Output {
+        let mut out = List::ZERO;
+        for (col, x) in self.data.iter().zip(rhs.iter()) {
+            out.axpy(*x, col);
+        }
+        out
+    }
+}
+
+// Every entry is a dot product of a stored row and a stored column
+impl<T, const R: usize, const C: usize, const K: usize> Mul<Matrix<T, C, K, ColMajor>>
+    for Matrix<T, R, C>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, rhs: Matrix<T, C, K, ColMajor>) -> Self::Output {
+        Matrix::from_fn(|i, k| {
+            let (row, col) = (self.row(i), rhs.col(k));
+            (0..C).fold(T::ZERO, |acc, j| acc + row[j] * col[j])
+        })
+    }
+}
+
+// Column k of the product is A times column k of B
+impl<T, const R: usize, const C: usize, const K: usize> Mul<Matrix<T, C, K, ColMajor>>
+    for Matrix<T, R, C, ColMajor>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = Matrix<T, R, K, ColMajor>;
+
+    fn mul(self, rhs: Matrix<T, C, K, ColMajor>) -> Self::Output {
+        Matrix {
+            data: rhs.data.map(|col| self * col),
+        }
+    }
+}
+
+// Row i of the product is sum_j A_ij (row j of B)
+impl<T, const R: usize, const C: usize, const K: usize> Mul<Matrix<T, C, K>>
+    for Matrix<T, R, C, ColMajor>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, rhs: Matrix<T, C, K>) -> Self::Output {
+        Matrix::from_rows(core::array::from_fn(|i| {
+            let mut row = List::ZERO;
+            for j in 0..C {
+                row.axpy(self[(i, j)], rhs.row(j));
+            }
+            row
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ColMajor, Matrix, RowMajor};
+    use crate::chapters::c1::definitions::{Complex, List, MulScalar, Zero};
+
+    fn rotation(theta: f64) -> Matrix<f64, 2, 2> {
+        Matrix::new([[theta.cos(), -theta.sin()], [theta.sin(), theta.cos()]])
+    }
+
+    fn assert_close<const N: usize>(actual: List<f64, N>, expected: [f64; N]) {
+        for i in 0..N {
+            assert!(
+                (actual[i] - expected[i]).abs() < 1e-12,
+                "{actual:?} != {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn linear_map() {
+        let quarter = rotation(std::f64::consts::FRAC_PI_2);
+        assert_close(quarter * List::new([1.0, 0.0]), [0.0, 1.0]);
+        assert_close(quarter * List::new([0.0, 1.0]), [-1.0, 0.0]);
+        assert_close(quarter * List::new([2.0, -3.0]), [3.0, 2.0]);
+
+        // Rotations compose by adding their angles
+        let v = List::new([0.3, -1.2]);
+        assert_close(rotation(0.4) * (rotation(0.5) * v), {
+            let w = rotation(0.9) * v;
+            [w[0], w[1]]
+        });
+
+        let a = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        assert_eq!(a * List::new([1.0, 0.0, -1.0]), List::new([-2.0, -2.0]));
+    }
+
+    #[test]
+    fn identity_and_associativity() {
+        let a = Matrix::new([[1.0, 2.0], [3.0, 4.0], [-1.0, 0.5]]);
+        let b = Matrix::new([[0.0, 1.0, -2.0, 1.0], [2.0, 0.5, 1.0, -1.0]]);
+        let c = Matrix::new([[1.0], [2.0], [0.0], [-4.0]]);
+
+        assert_eq!(Matrix::identity() * a, a);
+        assert_eq!(a * Matrix::identity(), a);
+        assert_eq!((a * b) * c, a * (b * c));
+        assert_eq!(
+            a * b,
+            Matrix::new([
+                [4.0, 2.0, 0.0, -1.0],
+                [8.0, 5.0, -2.0, -1.0],
+                [1.0, -0.75, 2.5, -1.5],
+            ])
+        );
+    }
+
+    #[test]
+    fn transpose_add_scale() {
+        let a = Matrix::new([[1isize, 2, 3], [4, 5, 6]]);
+        let t = a.transpose();
+        assert_eq!(t, Matrix::new([[1, 4], [2, 5], [3, 6]]));
+        assert_eq!(t.transpose(), a);
+        assert_eq!(t[(2, 1)], 6);
+
+        let b = Matrix::new([[0, -2, 1], [1, 1, 1]]);
+        assert_eq!(a + b, Matrix::new([[1, 0, 4], [5, 6, 7]]));
+        assert_eq!(a + -a, Matrix::ZERO);
+        assert_eq!(MulScalar::mul(a, 3), Matrix::new([[3, 6, 9], [12, 15, 18]]));
+
+        let mut m = Matrix::<f64, 2, 2>::ZERO;
+        m[(0, 1)] = 7.0;
+        assert_eq!(*m.row(0), List::new([0.0, 7.0]));
+    }
+
+    #[test]
+    fn complex_entries() {
+        let i = Complex::new(0.0, 1.0);
+        let one = Complex::from(1.0);
+        let zero = Complex::ZERO;
+
+        // diag(i, -i)
+        let m = Matrix::new([[i, zero], [zero, -i]]);
+        let v = List::new([one, Complex::new(2.0, 3.0)]);
+        assert_eq!(m * v, List::new([i, Complex::new(3.0, -2.0)]));
+
+        let id = Matrix::<Complex<f64>, 2, 2>::identity();
+        assert_eq!(id * m, m);
+        assert_eq!(m * m, MulScalar::mul(id, Complex::from(-1.0)));
+    }
+
+    #[test]
+    fn display_flags() {
+        let a = Matrix::new([[1.0, -2.5], [-1.0 / 3.0, 10.0]]);
+        assert_eq!(format!("{a:.2}"), "( 1.00, -2.50)\n(-0.33, 10.00)");
+        assert_eq!(format!("{a:+.2}"), "(+1.00,  -2.50)\n(-0.33, +10.00)");
+        assert_eq!(format!("{a:#.1}"), "[[ 1.0, -2.5],\n [-0.3, 10.0]]");
+        assert_eq!(format!("{a:+#.1}"), "[[+1.0,  -2.5],\n [-0.3, +10.0]]");
+        assert_eq!(format!("{a:7.1}"), "(    1.0,    -2.5)\n(   -0.3,    10.0)");
+        assert_eq!(format!("{a:<6.1}|"), "(1.0   , -2.5  )\n(-0.3  , 10.0  )|");
+        assert_eq!(format!("{a:*^6.1}"), "(*1.0**, *-2.5*)\n(*-0.3*, *10.0*)");
+        assert_eq!(
+            format!("{a}"),
+            "(                  1, -2.5)\n(-0.3333333333333333,   10)"
+        );
+
+        let ints = Matrix::new([[1, -20, 3], [400, 5, -6]]);
+        assert_eq!(format!("{ints}"), "(  1, -20,  3)\n(400,   5, -6)");
+        assert_eq!(format!("{ints:+}"), "(  +1, -20, +3)\n(+400,  +5, -6)");
+
+        assert_eq!(format!("{}", Matrix::<f64, 0, 0>::new([])), "");
+        assert_eq!(format!("{:#}", Matrix::<f64, 0, 0>::new([])), "[]");
+        assert_eq!(
+            format!("{:#}", Matrix::<f64, 2, 0>::new([[], []])),
+            "[[],\n []]"
+        );
+    }
+
+    #[test]
+    fn mul_into() {
+        let a = Matrix::new([[1.0, 2.0, 3.0], [-4.0, 0.5, 6.0]]);
+        let b = Matrix::new([[0.5, -1.0], [2.0, 0.0], [1.0, 3.0]]);
+        let expected = Matrix::new([[7.5, 8.0], [5.0, 22.0]]);
+
+        // Whatever was in out before is overwritten
+        let mut out = Matrix::new([[9.0; 2]; 2]);
+        a.mul_into(&b, &mut out);
+        assert_eq!(out, expected);
+        assert_eq!(a * b, expected);
+
+        let m = Matrix::<f64, 8, 8>::from_fn(|i, j| (i * 8 + j) as f64 * 0.25 - 3.0);
+        let by_value = m * m.transpose();
+        let one_entry_at_a_time =
+            Matrix::from_fn(|i, k| (0..8).fold(0.0, |acc, j| acc + m[(i, j)] * m[(k, j)]));
+        let mut in_place = Matrix::ZERO;
+        m.mul_into(&m.transpose(), &mut in_place);
+        assert_eq!(in_place, by_value);
+        assert_eq!(in_place, one_entry_at_a_time);
+    }
+
+    #[test]
+    fn layouts() {
+        let a = Matrix::<f64, 3, 4>::from_fn(|i, j| ((i * 4 + j) as f64).sin());
+        let b = Matrix::<f64, 4, 2>::from_fn(|i, j| 1.0 / (i + 2 * j + 1) as f64);
+        let v = List::new([0.3, -1.0 / 3.0, 2.0, 1e-3]);
+        let (ac, bc) = (a.to_layout::<ColMajor>(), b.to_layout::<ColMajor>());
+
+        for i in 0..3 {
+            assert_eq!(ac.row(i), *a.row(i));
+            for j in 0..4 {
+                assert_eq!(ac[(i, j)], a[(i, j)]);
+            }
+        }
+        for j in 0..4 {
+            assert_eq!(*ac.col(j), a.col(j));
+        }
+        assert_eq!(ac.to_layout::<RowMajor>(), a);
+        assert_eq!(a.to_layout::<RowMajor>(), a);
+
+        // Bit for bit the same numbers in every layout
+        let product = a * b;
+        assert_eq!(a * bc, product);
+        assert_eq!(ac * b, product);
+        assert_eq!((ac * bc).to_layout::<RowMajor>(), product);
+        assert_eq!(ac * v, a * v);
+        assert_eq!((ac + ac).to_layout::<RowMajor>(), a + a);
+        assert_eq!((-ac).to_layout::<RowMajor>(), -a);
+        assert_eq!(
+            MulScalar::mul(ac, 0.7).to_layout::<RowMajor>(),
+            MulScalar::mul(a, 0.7)
+        );
+        assert_eq!(
+            (ac + Matrix::ZERO).to_layout::<RowMajor>(),
+            a + Matrix::ZERO
+        );
+        assert_eq!(format!("{ac:+.3}"), format!("{a:+.3}"));
+
+        // Transposing by changing the layout moves nothing, and agrees with the copy
+        assert_eq!(a.into_transpose().to_layout::<RowMajor>(), a.transpose());
+        assert_eq!(ac.into_transpose(), a.transpose());
+        assert_eq!(ac.transpose().to_layout::<RowMajor>(), a.transpose());
+        assert_eq!(a.into_transpose().into_transpose(), a);
+
+        let mut swapped = ac;
+        swapped.swap_cols(0, 3);
+        assert_eq!(swapped.col(0), ac.col(3));
+        assert_eq!(swapped[(2, 3)], a[(2, 0)]);
+        swapped[(1, 1)] = 5.0;
+        assert_eq!(*swapped.col(1), List::new([a[(0, 1)], 5.0, a[(2, 1)]]));
+        assert_eq!(
+            Matrix::from_cols([List::new([1, 2]), List::new([3, 4])]).to_layout::<RowMajor>(),
+            Matrix::new([[1, 3], [2, 4]])
+        );
+    }
+}
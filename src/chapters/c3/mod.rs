@@ -0,0 +1,11 @@
+pub mod banded;
+pub mod block;
+pub mod direct_sum;
+pub mod iterative;
+pub mod matrix;
+pub mod rref;
+pub mod solve;
+pub mod symmetric;
+pub mod toeplitz;
+pub mod triangular;
+pub mod view;
@@ -0,0 +1,423 @@
+use std::ops::{Add, Mul, Neg};
+
+use super::matrix::Matrix;
+use crate::chapters::c1::definitions::{
+    AddInverse, Divide, List, One, Subtract, WrongLength, Zero,
+};
+use crate::chapters::c2::span::{null_space, rank};
+use crate::chapters::c6::inner_product::Modulus;
+
+// b is not in the column space of A, A x = b has no solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Inconsistent;
+
+// All solutions of A x = b: x0 + span(n_1, ..., n_k) with the n_i a basis of the null space
+#[derive(Debug, Clone, PartialEq)]
+pub struct AffineSolutionSet<T, const C: usize> {
+    particular: List<T, C>,
+    null_basis: Vec<List<T, C>>,
+}
+
+impl<T, const C: usize> AffineSolutionSet<T, C> {
+    pub fn particular(&self) -> &List<T, C> {
+        &self.particular
+    }
+
+    pub fn null_basis(&self) -> &[List<T, C>] {
+        &self.null_basis
+    }
+
+    // 0 for a unique solution
+    pub fn dim(&self) -> usize {
+        self.null_basis.len()
+    }
+
+    // x0 + c_1 n_1 + ... + c_k n_k, with one coefficient per null space basis vector
+    pub fn sample(&self, coeffs: &[T]) -> Result<List<T, C>, WrongLength>
+    where
+        T: Add<Output = T> + Mul<Output = T> + Copy,
+    {
+        if coeffs.len() != self.dim() {
+            return Err(WrongLength {
+                expected: self.dim(),
+                found: coeffs.len(),
+            });
+        }
+        Ok(List::from_fn(|j| {
+            self.null_basis
+                .iter()
+                .zip(coeffs)
+                .fold(self.particular[j], |acc, (n, &c)| acc + c * n[j])
+        }))
+    }
+}
+
+// A and B after Gauss-Jordan elimination on (A | B): the first pivots.len() rows have a one in
+// their pivot column, which is zero in every other row, and the remaining rows of A are zero
+struct Reduced<T, const R: usize, const C: usize, const K: usize> {
+    a: Matrix<T, R, C>,
+    b: Matrix<T, R, K>,
+    pivots: Vec<usize>,
+}
+
+// Entries with a modulus up to tol count as zero. Every column takes the largest entry that is
+// left as its pivot, as in partial pivoting. The row operations on B are the same as on A.
+fn reduce<T, const R: usize, const C: usize, const K: usize>(
+    mut a: Matrix<T, R, C>,
+    mut b: Matrix<T, R, K>,
+    tol: f64,
+) -> Reduced<T, R, C, K>
+where
+    T: Add<Output = T> + Mul<Output = T> + AddInverse + Divide + Modulus + Zero + One + Copy,
+{
+    let mut pivots = Vec::new();
+    for col in 0..C {
+        let k = pivots.len();
+        if k == R {
+            break;
+        }
+        let best = (k..R)
+            .max_by(|&i, &j| a[(i, col)].modulus().total_cmp(&a[(j, col)].modulus()))
+            .unwrap();
+        if a[(best, col)].modulus() <= tol {
+            for i in k..R {
+                a[(i, col)] = T::ZERO;
+            }
+            continue;
+        }
+        a.swap_rows(best, k);
+        b.swap_rows(best, k);
+
+        let p = a[(k, col)];
+        for j in col + 1..C {
+            a[(k, j)] = a[(k, j)].divide(p);
+        }
+        for j in 0..K {
+            b[(k, j)] = b[(k, j)].divide(p);
+        }
+        a[(k, col)] = T::ONE;
+        for i in (0..R).filter(|&i| i != k) {
+            let factor = a[(i, col)];
+            for j in col + 1..C {
+                a[(i, j)] = a[(i, j)].subtract(factor * a[(k, j)]);
+            }
+            for j in 0..K {
+                b[(i, j)] = b[(i, j)].subtract(factor * b[(k, j)]);
+            }
+            a[(i, col)] = T::ZERO;
+        }
+        pivots.push(col);
+    }
+    Reduced { a, b, pivots }
+}
+
+// Each free variable, a non pivot column, set to one with the others zero gives a basis vector of
+// the null space, so its dimension is C - rank
+fn null_basis<T, const R: usize, const C: usize>(
+    rref: &Matrix<T, R, C>,
+    pivots: &[usize],
+) -> Vec<List<T, C>>
+where
+    T: AddInverse + Zero + One + Copy,
+{
+    (0..C)
+        .filter(|col| !pivots.contains(col))
+        .map(|free| {
+            let mut n = List::from_fn(|_| T::ZERO);
+            n[free] = T::ONE;
+            for (k, &col) in pivots.iter().enumerate() {
+                n[col] = rref[(k, free)].negate();
+            }
+            n
+        })
+        .collect()
+}
+
+// Gauss-Jordan elimination for floating point entries, where entries with a modulus up to tol
+// count as zero to hide rounding errors
+impl<T, const R: usize, const C: usize> Matrix<T, R, C>
+where
+    T: Add<Output = T> + Mul<Output = T> + AddInverse + Divide + Modulus + Zero + One + Copy,
+{
+    // The reduced row echelon form and its pivot columns, one per non zero row
+    pub fn rref_tol(&self, tol: f64) -> (Self, Vec<usize>) {
+        let Reduced { a, pivots, .. } =
+            reduce(*self, Matrix::<T, R, 0>::from_fn(|_, _| T::ZERO), tol);
+        (a, pivots)
+    }
+
+    // Every x with A x = b, from the reduced row echelon form of (A | b). The system is
+    // consistent when the zero rows of A have zeros on the right as well. The particular solution
+    // sets the free variables to zero.
+    pub fn solve_general(
+        &self,
+        b: &List<T, R>,
+        tol: f64,
+    ) -> Result<AffineSolutionSet<T, C>, Inconsistent> {
+        let Reduced { a, b, pivots } = reduce(*self, Matrix::<T, R, 1>::from_fn(|i, _| b[i]), tol);
+        if (pivots.len()..R).any(|i| b[(i, 0)].modulus() > tol) {
+            return Err(Inconsistent);
+        }
+
+        let mut particular = List::from_fn(|_| T::ZERO);
+        for (k, &col) in pivots.iter().enumerate() {
+            particular[col] = b[(k, 0)];
+        }
+        Ok(AffineSolutionSet {
+            particular,
+            null_basis: null_basis(&a, &pivots),
+        })
+    }
+}
+
+// The same over an exact field like Rational or Zp, where a pivot is either zero or not and the
+// results are exact, as they are for invert. The fractions in a Rational elimination can grow
+// quickly, an entry that no longer fits an i64 panics. Floating point entries fail to compile,
+// they need a tolerance:
+/// ```compile_fail
+/// use linear_algebra::chapters::c3::matrix::Matrix;
+///
+/// let rank = Matrix::new([[1.0, 2.0], [2.0, 4.0]]).rank();
+/// ```
+impl<T, const R: usize, const C: usize> Matrix<T, R, C>
+where
+    T: Add<Output = T> + Mul<Output = T> + AddInverse + Divide + Modulus + Zero + One + Copy,
+{
+    pub fn rref(&self) -> (Self, Vec<usize>) {
+        const { assert!(T::EXACT, "T must be an exact type") };
+        self.rref_tol(0.0)
+    }
+
+    // The dimension of the span of the rows, by c2::span::rank
+    pub fn rank(&self) -> usize
+    where
+        T: Neg<Output = T>,
+    {
+        const { assert!(T::EXACT, "T must be an exact type") };
+        rank(&self.rows(), 0.0)
+    }
+
+    // A basis of the x with A x = 0, by c2::span::null_space
+    pub fn null_space(&self) -> Vec<List<T, C>>
+    where
+        T: Neg<Output = T>,
+    {
+        const { assert!(T::EXACT, "T must be an exact type") };
+        null_space(&self.rows(), 0.0)
+    }
+
+    pub fn solve_exact(&self, b: &List<T, R>) -> Result<AffineSolutionSet<T, C>, Inconsistent> {
+        const { assert!(T::EXACT, "T must be an exact type") };
+        self.solve_general(b, 0.0)
+    }
+
+    fn rows(&self) -> Vec<List<T, C>> {
+        (0..R).map(|i| *self.row(i)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Inconsistent;
+    use crate::chapters::c1::definitions::{List, One, WrongLength, Zero};
+    use crate::chapters::c1::modular::Zp;
+    use crate::chapters::c1::rational::Rational;
+    use crate::chapters::c3::matrix::Matrix;
+    use crate::chapters::c3::solve::{SolveError, solve};
+
+    const EPS: f64 = 1e-12;
+
+    fn max_residual<const R: usize, const C: usize>(
+        a: &Matrix<f64, R, C>,
+        x: &List<f64, C>,
+        b: &List<f64, R>,
+    ) -> f64 {
+        let ax = *a * *x;
+        (0..R).map(|i| (ax[i] - b[i]).abs()).fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn underdetermined() {
+        // Rank 2 with 4 unknowns, a plane of solutions
+        let a = Matrix::new([
+            [1.0, 2.0, -1.0, 3.0],
+            [2.0, 4.0, 0.0, 2.0],
+            [3.0, 6.0, -1.0, 5.0],
+        ]);
+        let b = List::new([1.0, 6.0, 7.0]);
+        let set = a.solve_general(&b, EPS).unwrap();
+        assert_eq!(set.dim(), 4 - a.rank_revealing().rank());
+        assert_eq!(set.dim(), 2);
+
+        for coeffs in [
+            [0.0, 0.0],
+            [1.0, 0.0],
+            [0.0, -2.5],
+            [3.0, 7.0],
+            [-1e3, 0.25],
+        ] {
+            let x = set.sample(&coeffs).unwrap();
+            assert!(max_residual(&a, &x, &b) < 1e-9, "{x:?}");
+        }
+        for n in set.null_basis() {
+            assert!(max_residual(&a, n, &List::new([0.0; 3])) < EPS);
+        }
+        assert_eq!(
+            set.sample(&[1.0]),
+            Err(WrongLength {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn singular_square() {
+        // The third row is the sum of the first two, a line of solutions
+        let a = Matrix::new([[1.0, -1.0, 2.0], [0.0, 3.0, 1.0], [1.0, 2.0, 3.0]]);
+        let b = List::new([2.0, -1.0, 1.0]);
+        assert!(solve(a, b).is_err());
+
+        let set = a.solve_general(&b, EPS).unwrap();
+        assert_eq!(set.dim(), 1);
+        for t in [-2.0, 0.0, 0.5, 10.0] {
+            assert!(max_residual(&a, &set.sample(&[t]).unwrap(), &b) < EPS);
+        }
+    }
+
+    #[test]
+    fn inconsistent() {
+        let a = Matrix::new([[1.0, -1.0, 2.0], [0.0, 3.0, 1.0], [1.0, 2.0, 3.0]]);
+        assert_eq!(
+            a.solve_general(&List::new([2.0, -1.0, 1.5]), EPS),
+            Err(Inconsistent)
+        );
+
+        // Overdetermined, three lines that don't meet
+        let a = Matrix::new([[1.0, 1.0], [1.0, -1.0], [2.0, 1.0]]);
+        assert_eq!(
+            a.solve_general(&List::new([2.0, 0.0, 4.0]), EPS),
+            Err(Inconsistent)
+        );
+        // Three lines through (1, 1)
+        let set = a.solve_general(&List::new([2.0, 0.0, 3.0]), EPS).unwrap();
+        assert_eq!(set.dim(), 0);
+        assert!((set.particular()[0] - 1.0).abs() < EPS);
+        assert!((set.particular()[1] - 1.0).abs() < EPS);
+    }
+
+    #[test]
+    fn unique() {
+        let a = Matrix::new([[4.0, -2.0, 1.0], [3.0, 6.0, -4.0], [2.0, 1.0, 8.0]]);
+        let b = List::new([12.0, -25.0, 32.0]);
+        let set = a.solve_general(&b, EPS).unwrap();
+        assert!(set.null_basis().is_empty());
+        let x = solve(a, b).unwrap();
+        for j in 0..3 {
+            assert!((set.particular()[j] - x[j]).abs() < EPS);
+        }
+        assert_eq!(set.sample(&[]).unwrap(), *set.particular());
+    }
+
+    fn rational<const R: usize, const C: usize>(rows: [[i64; C]; R]) -> Matrix<Rational, R, C> {
+        Matrix::new(rows.map(|row| row.map(Rational::from)))
+    }
+
+    #[test]
+    fn exact_rref() {
+        // x + 2y + z = 3, 2x - y + 3z = 1, 3x + y - 2z = 2 as an augmented matrix
+        let system = rational([[1, 2, 1, 3], [2, -1, 3, 1], [3, 1, -2, 2]]);
+        let (rref, pivots) = system.rref();
+        let one = Rational::ONE;
+        let zero = Rational::ZERO;
+        assert_eq!(
+            rref,
+            Matrix::new([
+                [one, zero, zero, Rational::new(8, 15)],
+                [zero, one, zero, Rational::new(16, 15)],
+                [zero, zero, one, Rational::new(1, 3)],
+            ])
+        );
+        assert_eq!(pivots, [0, 1, 2]);
+        assert_eq!(system.rank(), 3);
+
+        // The rank 2 matrix of underdetermined, exactly
+        let a = rational([[1, 2, -1, 3], [2, 4, 0, 2], [3, 6, -1, 5]]);
+        assert_eq!(a.rank(), 2);
+        let null = a.null_space();
+        let q = |x: [i64; 4]| List::new(x.map(|x| Rational::new(x, 4)));
+        assert_eq!(null, [q([4, -2, 0, 0]), q([0, -1, 4, 2])]);
+        for n in &null {
+            assert_eq!(a * *n, List::ZERO);
+        }
+        // The free variables of the RREF give another basis of the same plane
+        let b = List::new([1, 6, 7].map(Rational::from));
+        let set = a.solve_exact(&b).unwrap();
+        assert_eq!(
+            set.null_basis(),
+            [
+                List::new([-2, 1, 0, 0].map(Rational::from)),
+                List::new([-1, 0, 2, 1].map(Rational::from))
+            ]
+        );
+        assert_eq!(null[0], set.null_basis()[0] * Rational::new(-1, 2));
+        assert_eq!(
+            null[1],
+            set.null_basis()[1] * Rational::new(1, 2) + set.null_basis()[0] * Rational::new(-1, 4)
+        );
+        assert_eq!(
+            set.sample(&[Rational::new(1, 2), Rational::new(-7, 3)])
+                .map(|x| a * x),
+            Ok(b)
+        );
+    }
+
+    #[test]
+    fn exact_inverse() {
+        // The second difference matrix, its inverse is (1 / 4) (3 2 1; 2 4 2; 1 2 3)
+        let a = rational([[2, -1, 0], [-1, 2, -1], [0, -1, 2]]);
+        let inverse = a.invert().unwrap();
+        let quarter = |x| Rational::new(x, 4);
+        assert_eq!(
+            inverse,
+            Matrix::new([[3, 2, 1], [2, 4, 2], [1, 2, 3]].map(|row| row.map(quarter)))
+        );
+        assert_eq!(a * inverse, Matrix::identity());
+
+        let singular = rational([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        assert_eq!(singular.invert(), Err(SolveError::Singular));
+
+        // Far below the rounding error test of floating point, which gives up on it
+        let a = rational([[1, 0], [1_000_000_000_000_000_000, 1]]);
+        assert_eq!(
+            a.invert().map(|inverse| a * inverse),
+            Ok(Matrix::identity())
+        );
+        let float = Matrix::new([[1.0, 0.0], [1e18, 1.0]]);
+        assert_eq!(float.invert(), Err(SolveError::Singular));
+        assert_eq!(singular.rank(), 2);
+    }
+
+    #[test]
+    fn modulo_seven() {
+        type Z7 = Zp<7>;
+        // The same system as exact_rref, whose solution 8/15, 16/15, 1/3 is 1, 2, 5 mod 7
+        let a = Matrix::new([[1, 2, 1], [2, -1, 3], [3, 1, -2]].map(|row| row.map(Z7::new)));
+        let b = List::new([3, 1, 2].map(Z7::new));
+        let set = a.solve_exact(&b).unwrap();
+        assert_eq!(set.dim(), 0);
+        assert_eq!(*set.particular(), List::new([1, 2, 5].map(Z7::new)));
+
+        let inverse = a.invert().unwrap();
+        assert_eq!(a * inverse, Matrix::identity());
+        assert_eq!(inverse * b, *set.particular());
+
+        // The rank depends on the field: 7 divides the determinant -7 of this one
+        assert_eq!(rational([[1, 2], [4, 1]]).rank(), 2);
+        let a = Matrix::new([[1, 2], [4, 1]].map(|row| row.map(Z7::new)));
+        assert_eq!(a.rank(), 1);
+        let null = a.null_space();
+        assert_eq!(null, [List::new([Z7::new(1), Z7::new(3)])]);
+        assert_eq!(a * null[0], List::ZERO);
+    }
+}
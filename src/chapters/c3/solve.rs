@@ -0,0 +1,487 @@
+use std::ops::{Add, Div, Mul};
+
+use super::matrix::Matrix;
+use super::triangular::{LowerTriangular, UpperTriangular};
+use crate::chapters::c1::definitions::{AddInverse, Divide, List, One, Subtract, Zero};
+use crate::chapters::c6::inner_product::Modulus;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveError {
+    // A pivot vanished during elimination, the matrix is not invertible
+    Singular,
+}
+
+// PA = LU with L unit lower triangular, both stored in one matrix (the diagonal belongs to U).
+// row[i] is the row of A that ended up in row i, odd is set when that permutation is odd.
+// smallest_pivot is the smallest |u_kk| relative to the largest entry of its row in A.
+struct Lu<T, const N: usize> {
+    lu: Matrix<T, N, N>,
+    row: [usize; N],
+    odd: bool,
+    smallest_pivot: f64,
+}
+
+// Gaussian elimination with scaled partial pivoting: every row is measured against its own
+// largest entry, so multiplying a row by a constant doesn't change the choice of pivots.
+// Fails only on an exactly zero pivot column, see Lu::check for the numerical test.
+fn decompose<T, const N: usize>(mut a: Matrix<T, N, N>) -> Result<Lu<T, N>, SolveError>
+where
+    T: Add<Output = T> + Mul<Output = T> + AddInverse + Divide + Modulus + Copy,
+{
+    let mut scale: [f64; N] =
+        core::array::from_fn(|i| (0..N).map(|j| a[(i, j)].modulus()).fold(0.0, f64::max));
+
+    let mut row = core::array::from_fn(|i| i);
+    let mut odd = false;
+    let mut smallest_pivot = f64::INFINITY;
+    for k in 0..N {
+        let relative = |i: usize| a[(i, k)].modulus() / scale[i];
+        let pivot = (k..N)
+            .filter(|&i| scale[i] > 0.0)
+            .max_by(|&i, &j| relative(i).total_cmp(&relative(j)))
+            .filter(|&i| a[(i, k)].modulus() > 0.0)
+            .ok_or(SolveError::Singular)?;
+        smallest_pivot = smallest_pivot.min(relative(pivot));
+        if pivot != k {
+            a.swap_rows(pivot, k);
+            row.swap(pivot, k);
+            scale.swap(pivot, k);
+            odd = !odd;
+        }
+
+        for i in k + 1..N {
+            let factor = a[(i, k)].divide(a[(k, k)]);
+            a[(i, k)] = factor;
+            for j in k + 1..N {
+                a[(i, j)] = a[(i, j)].subtract(factor * a[(k, j)]);
+            }
+        }
+    }
+
+    Ok(Lu {
+        lu: a,
+        row,
+        odd,
+        smallest_pivot,
+    })
+}
+
+impl<T: Modulus, const N: usize> Lu<T, N> {
+    // A pivot within rounding error of the rest of its row means the matrix is singular
+    // as far as floating point can tell, and the solution would be noise. Exact types have no
+    // rounding errors, any non zero pivot is fine.
+    fn check(self) -> Result<Self, SolveError> {
+        if !T::EXACT && self.smallest_pivot <= N as f64 * f64::EPSILON {
+            Err(SolveError::Singular)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+impl<T, const N: usize> Lu<T, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + AddInverse + Divide + Copy,
+{
+    // Forward substitution with L, then back substitution with U
+    fn solve(&self, b: &List<T, N>) -> List<T, N> {
+        let mut x = List::from_fn(|i| b[self.row[i]]);
+        for i in 0..N {
+            for j in 0..i {
+                x[i] = x[i].subtract(self.lu[(i, j)] * x[j]);
+            }
+        }
+        for i in (0..N).rev() {
+            for j in i + 1..N {
+                x[i] = x[i].subtract(self.lu[(i, j)] * x[j]);
+            }
+            x[i] = x[i].divide(self.lu[(i, i)]);
+        }
+        x
+    }
+}
+
+// PA = LU with row[i] the row of A that ended up in row i of PA and l unit lower triangular
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LuFactors<T, const N: usize> {
+    pub row: [usize; N],
+    pub l: LowerTriangular<T, N>,
+    pub u: UpperTriangular<T, N>,
+}
+
+impl<T, const N: usize> LuFactors<T, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + AddInverse + Divide + Zero + One + PartialEq + Copy,
+{
+    // L y = P b, then U x = y
+    pub fn solve(&self, b: &List<T, N>) -> Result<List<T, N>, SolveError> {
+        self.u
+            .solve(&self.l.solve(&List::from_fn(|i| b[self.row[i]]))?)
+    }
+}
+
+// The x with a x = b
+pub fn solve<T, const N: usize>(a: Matrix<T, N, N>, b: List<T, N>) -> Result<List<T, N>, SolveError>
+where
+    T: Add<Output = T> + Mul<Output = T> + AddInverse + Divide + Modulus + Copy,
+{
+    Ok(decompose(a)?.check()?.solve(&b))
+}
+
+impl<T, const N: usize> Matrix<T, N, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + AddInverse + Divide + Modulus + Zero + One + Copy,
+{
+    // Product of the pivots, zero only when elimination hits an exactly zero column
+    pub fn determinant(&self) -> T {
+        match decompose(*self) {
+            Ok(lu) => {
+                let det = (0..N).fold(T::ONE, |acc, i| acc * lu.lu[(i, i)]);
+                if lu.odd { det.negate() } else { det }
+            }
+            Err(SolveError::Singular) => T::ZERO,
+        }
+    }
+
+    // Solves a x = e_j for every column j of the identity, exactly for Rational and Zp
+    pub fn invert(&self) -> Result<Self, SolveError> {
+        let lu = decompose(*self)?.check()?;
+        let columns: [List<T, N>; N] = core::array::from_fn(|j| {
+            lu.solve(&List::from_fn(|i| if i == j { T::ONE } else { T::ZERO }))
+        });
+        Ok(Matrix::from_fn(|i, j| columns[j][i]))
+    }
+
+    // The factors solve uses, split into their triangles
+    pub fn lu(&self) -> Result<LuFactors<T, N>, SolveError> {
+        let lu = decompose(*self)?.check()?;
+        let unit = Matrix::from_fn(|i, j| if i == j { T::ONE } else { lu.lu[(i, j)] });
+        Ok(LuFactors {
+            row: lu.row,
+            l: LowerTriangular::lower_part_of(&unit),
+            u: UpperTriangular::upper_part_of(&lu.lu),
+        })
+    }
+}
+
+impl<T, const N: usize> Matrix<T, N, N>
+where
+    T: Add<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + AddInverse
+        + Zero
+        + One
+        + PartialEq
+        + Copy,
+{
+    // Fraction free elimination (Bareiss) for integer matrices. After step k every entry is a
+    // (k + 1) x (k + 1) minor of the matrix, so the division by the previous pivot is exact and
+    // the entries never grow past det-sized numbers. The products just before a division can,
+    // use bareiss_determinant_wide when they don't fit in T.
+    pub fn bareiss_determinant(&self) -> T {
+        let mut m = *self;
+        let mut previous = T::ONE;
+        let mut odd = false;
+        for k in 0..N {
+            let Some(pivot) = (k..N).find(|&i| m[(i, k)] != T::ZERO) else {
+                return T::ZERO;
+            };
+            if pivot != k {
+                m.swap_rows(pivot, k);
+                odd = !odd;
+            }
+
+            for i in k + 1..N {
+                for j in k + 1..N {
+                    m[(i, j)] = (m[(i, j)] * m[(k, k)]).subtract(m[(i, k)] * m[(k, j)]) / previous;
+                }
+            }
+            previous = m[(k, k)];
+        }
+
+        if odd { previous.negate() } else { previous }
+    }
+
+    // The Bareiss determinant computed in W, e.g. i128 for an i64 matrix
+    pub fn bareiss_determinant_wide<W>(&self) -> W
+    where
+        T: Into<W>,
+        W: Add<Output = W>
+            + Mul<Output = W>
+            + Div<Output = W>
+            + AddInverse
+            + Zero
+            + One
+            + PartialEq
+            + Copy,
+    {
+        Matrix::<W, N, N>::from_fn(|i, j| self[(i, j)].into()).bareiss_determinant()
+    }
+}
+
+// b - a x with every product and sum rounded once, as usual
+fn residual<const N: usize>(
+    a: &Matrix<f64, N, N>,
+    b: &List<f64, N>,
+    x: &List<f64, N>,
+) -> List<f64, N> {
+    List::from_fn(|i| (0..N).fold(b[i], |acc, j| acc - a[(i, j)] * x[j]))
+}
+
+// b - a x carried as an unevaluated sum hi + lo of two floats (double-double). mul_add gives
+// the rounding error of a product exactly, and Knuth's two-sum that of a sum, so the result is
+// as accurate as if it were computed with twice the precision and rounded at the end.
+fn residual_extended<const N: usize>(
+    a: &Matrix<f64, N, N>,
+    b: &List<f64, N>,
+    x: &List<f64, N>,
+) -> List<f64, N> {
+    let two_sum = |s: f64, t: f64| {
+        let sum = s + t;
+        let v = sum - s;
+        (sum, (s - (sum - v)) + (t - v))
+    };
+    List::from_fn(|i| {
+        let (mut hi, mut lo) = (b[i], 0.0);
+        for j in 0..N {
+            let p = -a[(i, j)] * x[j];
+            let p_error = (-a[(i, j)]).mul_add(x[j], -p);
+            let (sum, error) = two_sum(hi, p);
+            hi = sum;
+            lo += error + p_error;
+        }
+        hi + lo
+    })
+}
+
+fn norm<const N: usize>(x: &List<f64, N>) -> f64 {
+    x.iter().fold(0.0, |acc: f64, &t| acc.hypot(t))
+}
+
+impl<const N: usize> Matrix<f64, N, N> {
+    // solve followed by up to max_refinements rounds of x <- x + d with a d = b - a x, reusing
+    // the LU factors. Returns x with the norm of its residual. Stops early once d is below
+    // rounding level or stops shrinking.
+    pub fn solve_refined(
+        &self,
+        b: &List<f64, N>,
+        max_refinements: usize,
+    ) -> Result<(List<f64, N>, f64), SolveError> {
+        self.refine(b, max_refinements, residual)
+    }
+
+    // solve_refined with the residuals computed in double-double precision. With a residual
+    // that is only as accurate as x this gets a backward stable answer at best, with an
+    // accurate one the error of x itself shrinks by about 1 / (eps cond(a)) every round.
+    pub fn solve_refined_extended(
+        &self,
+        b: &List<f64, N>,
+        max_refinements: usize,
+    ) -> Result<(List<f64, N>, f64), SolveError> {
+        self.refine(b, max_refinements, residual_extended)
+    }
+
+    fn refine(
+        &self,
+        b: &List<f64, N>,
+        max_refinements: usize,
+        residual: fn(&Self, &List<f64, N>, &List<f64, N>) -> List<f64, N>,
+    ) -> Result<(List<f64, N>, f64), SolveError> {
+        let lu = decompose(*self)?.check()?;
+        let mut x = lu.solve(b);
+        let mut r = residual(self, b, &x);
+        let mut previous = f64::INFINITY;
+        for _ in 0..max_refinements {
+            let d = lu.solve(&r);
+            let size = norm(&d);
+            // The corrections should at least halve each round, anything else is noise
+            if size > previous / 2.0 {
+                break;
+            }
+            x += d;
+            r = residual(self, b, &x);
+            previous = size;
+            if size <= f64::EPSILON * norm(&x) {
+                break;
+            }
+        }
+        Ok((x, norm(&r)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SolveError, norm, residual, residual_extended, solve};
+    use crate::chapters::c1::definitions::{Complex, List};
+    use crate::chapters::c3::matrix::Matrix;
+    use crate::chapters::c6::inner_product::Modulus;
+
+    const EPS: f64 = 1e-12;
+
+    fn assert_close<T: Modulus + std::ops::Sub<Output = T> + Copy + std::fmt::Debug>(
+        actual: T,
+        expected: T,
+    ) {
+        assert!(
+            (actual - expected).modulus() < EPS,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn three_by_three() {
+        // x = (1, -2, 3)
+        let a = Matrix::new([[2.0, 1.0, -1.0], [-3.0, -1.0, 2.0], [-2.0, 1.0, 2.0]]);
+        let b = List::new([-3.0, 5.0, 2.0]);
+        let x = solve(a, b).unwrap();
+        for (x, expected) in x.iter().zip([1.0, -2.0, 3.0]) {
+            assert_close(*x, expected);
+        }
+        assert_close(a.determinant(), -1.0);
+    }
+
+    #[test]
+    fn zero_in_top_left() {
+        let a = Matrix::new([[0.0, 1.0], [1.0, 0.0]]);
+        assert_eq!(solve(a, List::new([2.0, 5.0])), Ok(List::new([5.0, 2.0])));
+        assert_eq!(a.determinant(), -1.0);
+    }
+
+    #[test]
+    fn inverse() {
+        let a = Matrix::new([[4.0, 7.0, 2.0], [0.0, 3.0, -1.0], [1.0, 0.0, 5.0]]);
+        let product = a * a.invert().unwrap();
+        let identity = Matrix::<f64, 3, 3>::identity();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_close(product[(i, j)], identity[(i, j)]);
+            }
+        }
+    }
+
+    #[test]
+    fn singular() {
+        let a = Matrix::new([[1.0, 2.0], [2.0, 4.0]]);
+        assert_eq!(solve(a, List::new([1.0, 2.0])), Err(SolveError::Singular));
+        assert_eq!(a.invert(), Err(SolveError::Singular));
+        assert_eq!(a.determinant(), 0.0);
+
+        // Not exactly singular after rounding, but within rounding error of it
+        let b = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        assert_eq!(b.invert(), Err(SolveError::Singular));
+        assert_eq!(
+            Matrix::<f64, 2, 2>::identity().invert(),
+            Ok(Matrix::identity())
+        );
+    }
+
+    #[test]
+    fn badly_scaled() {
+        // Invertible, the rows just live on very different scales
+        let a = Matrix::new([[1e20, 0.0], [0.0, 1.0]]);
+        assert_eq!(a.determinant(), 1e20);
+        assert_eq!(a.invert(), Ok(Matrix::new([[1e-20, 0.0], [0.0, 1.0]])));
+        assert_eq!(solve(a, List::new([2e20, 3.0])), Ok(List::new([2.0, 3.0])));
+
+        let b = Matrix::new([[1e-30, 2e-30], [3.0, 1.0]]);
+        let x = solve(b, List::new([3e-30, 4.0])).unwrap();
+        assert_close(x[0], 1.0);
+        assert_close(x[1], 1.0);
+    }
+
+    #[test]
+    fn complex_system() {
+        let i = Complex::new(0.0, 1.0);
+        let one = Complex::from(1.0);
+
+        // x = (1 + i, 2 - i)
+        let a = Matrix::new([[i, one], [Complex::new(2.0, 0.0), -i]]);
+        let x = List::new([Complex::new(1.0, 1.0), Complex::new(2.0, -1.0)]);
+        let b = a * x;
+
+        let solution = solve(a, b).unwrap();
+        assert_close(solution[0], x[0]);
+        assert_close(solution[1], x[1]);
+        // det = i * -i - 2 = -1
+        assert_close(a.determinant(), -one);
+    }
+
+    #[test]
+    fn bareiss() {
+        let a = Matrix::new([[2, 1, -1], [-3, -1, 2], [-2, 1, 2]]);
+        assert_eq!(a.bareiss_determinant(), -1);
+        assert_eq!(Matrix::new([[0, 1], [1, 0]]).bareiss_determinant(), -1);
+        assert_eq!(Matrix::new([[1, 2], [2, 4]]).bareiss_determinant(), 0);
+        assert_eq!(Matrix::<i64, 0, 0>::new([]).bareiss_determinant(), 1);
+
+        // A zero pivot that needs a row swap halfway through
+        let b = Matrix::new([[1, 2, 3], [2, 4, 7], [3, 7, 1]]);
+        assert_eq!(b.bareiss_determinant(), -1);
+    }
+
+    #[test]
+    fn bareiss_wide() {
+        // A = L U with L unit lower triangular, so det A is the product of the diagonal of U,
+        // about 7e20 and past i64::MAX, while every entry of A still fits comfortably
+        let l = Matrix::<i64, 6, 6>::from_fn(|i, j| match i.cmp(&j) {
+            std::cmp::Ordering::Less => 0,
+            std::cmp::Ordering::Equal => 1,
+            std::cmp::Ordering::Greater => (i * 3 + j) as i64 % 5 - 2,
+        });
+        let u = Matrix::<i64, 6, 6>::from_fn(|i, j| match i.cmp(&j) {
+            std::cmp::Ordering::Less => (i + 2 * j) as i64 * 100 - 700,
+            std::cmp::Ordering::Equal => 1000 * (i as i64 + 1),
+            std::cmp::Ordering::Greater => 0,
+        });
+        let a = l * u;
+        let expected: i128 = (1..=6).map(|k| 1000 * k).product();
+        assert!(expected > i64::MAX as i128);
+
+        assert_eq!(a.bareiss_determinant_wide::<i128>(), expected);
+        assert_eq!(a.transpose().bareiss_determinant_wide::<i128>(), expected);
+        let mut swapped = a;
+        swapped.swap_rows(0, 5);
+        assert_eq!(swapped.bareiss_determinant_wide::<i128>(), -expected);
+
+        // LU in floating point only gets the leading digits
+        let float = Matrix::<f64, 6, 6>::from_fn(|i, j| a[(i, j)] as f64);
+        assert!((float.determinant() / expected as f64 - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn refinement() {
+        // The Hilbert matrix scaled by lcm(1, ..., 15) so that every entry is an exact integer.
+        // Its inverse is known in closed form, for b = e_k the solution is column k of it.
+        let (n, k, lcm) = (8, 3, 360360.0);
+        let a = Matrix::<f64, 8, 8>::from_fn(|i, j| lcm / (i + j + 1) as f64);
+        let binomial =
+            |n: usize, k: usize| (0..k).fold(1.0, |acc, t| acc * (n - t) as f64 / (t + 1) as f64);
+        let exact = List::<f64, 8>::from_fn(|i| {
+            let sign = if (i + k) % 2 == 0 { 1.0 } else { -1.0 };
+            let entry = (i + k + 1) as f64
+                * binomial(n + i, n - k - 1)
+                * binomial(n + k, n - i - 1)
+                * binomial(i + k, i).powi(2);
+            sign * entry / lcm
+        });
+        let b = List::from_fn(|i| if i == k { 1.0 } else { 0.0 });
+        let error = |x: &List<f64, 8>| norm(&(*x + -exact)) / norm(&exact);
+
+        let (x, r) = a.solve_refined(&b, 0).unwrap();
+        assert_eq!(x, solve(a, b).unwrap());
+        assert_eq!(norm(&residual(&a, &b, &x)), r);
+        let plain = (error(&x), norm(&residual_extended(&a, &b, &x)));
+
+        // With working precision residuals LU is about as good as it gets already
+        let (_, refined_r) = a.solve_refined(&b, 5).unwrap();
+        assert!(refined_r <= r);
+
+        // With accurate residuals the error drops from about eps cond(a) to about eps,
+        // and the residual is smaller as well
+        let (x, r) = a.solve_refined_extended(&b, 5).unwrap();
+        assert!(plain.0 > 1e-9);
+        assert!(error(&x) < 1e-14);
+        assert!(r < plain.1 / 5.0);
+    }
+}
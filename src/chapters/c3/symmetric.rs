@@ -0,0 +1,202 @@
+use std::ops::{Add, Mul};
+
+use super::matrix::Matrix;
+use super::triangular::LowerTriangular;
+use crate::chapters::c1::definitions::{Divide, List, One, Zero};
+
+// A symmetric N x N matrix keeping only its lower triangle, row by row:
+//  a00, a10 a11, a20 a21 a22, ...
+// (i, j) and (j, i) are the same slot, so the two halves can't drift apart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymmetricMatrix<T, const N: usize> {
+    data: Vec<T>,
+}
+
+// The first entry (row, col) with a_ij != a_ji
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotSymmetric {
+    pub row: usize,
+    pub col: usize,
+}
+
+// Cholesky found a pivot that isn't positive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotPositiveDefinite;
+
+fn slot(i: usize, j: usize) -> usize {
+    let (i, j) = if i >= j { (i, j) } else { (j, i) };
+    i * (i + 1) / 2 + j
+}
+
+impl<T, const N: usize> SymmetricMatrix<T, N> {
+    pub fn from_fn(mut f: impl FnMut(usize, usize) -> T) -> Self {
+        SymmetricMatrix {
+            data: (0..N)
+                .flat_map(|i| (0..=i).map(move |j| (i, j)))
+                .map(|(i, j)| f(i, j))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> &T {
+        assert!(i < N && j < N, "index out of range");
+        &self.data[slot(i, j)]
+    }
+
+    // Sets both a_ij and a_ji
+    pub fn set(&mut self, i: usize, j: usize, x: T) {
+        assert!(i < N && j < N, "index out of range");
+        self.data[slot(i, j)] = x;
+    }
+
+    pub fn packed(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn to_matrix(&self) -> Matrix<T, N, N>
+    where
+        T: Copy,
+    {
+        Matrix::from_fn(|i, j| *self.get(i, j))
+    }
+
+    // Fails unless m equals its transpose exactly
+    pub fn from_matrix(m: &Matrix<T, N, N>) -> Result<Self, NotSymmetric>
+    where
+        T: PartialEq + Copy,
+    {
+        for i in 0..N {
+            for j in 0..i {
+                if m[(i, j)] != m[(j, i)] {
+                    return Err(NotSymmetric { row: i, col: j });
+                }
+            }
+        }
+        Ok(Self::from_fn(|i, j| m[(i, j)]))
+    }
+
+    // (m + m^T) / 2, the symmetric matrix closest to m
+    pub fn symmetrize(m: &Matrix<T, N, N>) -> Self
+    where
+        T: Add<Output = T> + Divide + One + Copy,
+    {
+        let two = T::ONE + T::ONE;
+        Self::from_fn(|i, j| (m[(i, j)] + m[(j, i)]).divide(two))
+    }
+
+    // self += alpha x x^T, touching each slot once
+    pub fn syr(&mut self, alpha: T, x: &List<T, N>)
+    where
+        T: Add<Output = T> + Mul<Output = T> + Copy,
+    {
+        for i in 0..N {
+            let ax = alpha * x[i];
+            for j in 0..=i {
+                let k = slot(i, j);
+                self.data[k] = self.data[k] + ax * x[j];
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> Mul<&List<T, N>> for &SymmetricMatrix<T, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = List<T, N>;
+
+    fn mul(self, rhs: &List<T, N>) -> Self::Output {
+        List::from_fn(|i| (0..N).fold(T::ZERO, |acc, j| acc + *self.get(i, j) * rhs[j]))
+    }
+}
+
+impl<const N: usize> SymmetricMatrix<f64, N> {
+    // The lower triangular L with L L^T = self, read straight from the packed triangle
+    pub fn cholesky(&self) -> Result<LowerTriangular<f64, N>, NotPositiveDefinite> {
+        let mut l = Matrix::<f64, N, N>::ZERO;
+        for j in 0..N {
+            let d = self.get(j, j) - (0..j).map(|k| l[(j, k)] * l[(j, k)]).sum::<f64>();
+            if d.is_nan() || d <= 0.0 {
+                return Err(NotPositiveDefinite);
+            }
+            l[(j, j)] = d.sqrt();
+            for i in j + 1..N {
+                let s = self.get(i, j) - (0..j).map(|k| l[(i, k)] * l[(j, k)]).sum::<f64>();
+                l[(i, j)] = s / l[(j, j)];
+            }
+        }
+        Ok(LowerTriangular::lower_part_of(&l))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NotPositiveDefinite, NotSymmetric, SymmetricMatrix};
+    use crate::chapters::c1::definitions::{List, MulScalar};
+    use crate::chapters::c3::matrix::Matrix;
+
+    #[test]
+    fn storage() {
+        let mut s = SymmetricMatrix::<i32, 4>::from_fn(|i, j| (10 * i + j) as i32);
+        assert_eq!(s.packed().len(), 4 * 5 / 2);
+        assert_eq!(s.packed(), &[0, 10, 11, 20, 21, 22, 30, 31, 32, 33]);
+
+        // Writing either half writes both
+        s.set(0, 3, -1);
+        assert_eq!(*s.get(3, 0), -1);
+        s.set(2, 1, 7);
+        assert_eq!(*s.get(1, 2), 7);
+        let m = s.to_matrix();
+        assert_eq!(m, m.transpose());
+    }
+
+    #[test]
+    fn from_matrix() {
+        let m = Matrix::new([[1.0, 2.0, 3.0], [2.0, 4.0, 5.0], [3.0, 5.0, 6.0]]);
+        assert_eq!(SymmetricMatrix::from_matrix(&m).unwrap().to_matrix(), m);
+
+        let mut skewed = m;
+        skewed[(0, 2)] = 0.0;
+        assert_eq!(
+            SymmetricMatrix::from_matrix(&skewed),
+            Err(NotSymmetric { row: 2, col: 0 })
+        );
+        let s = SymmetricMatrix::symmetrize(&skewed);
+        assert_eq!(*s.get(0, 2), 1.5);
+        assert_eq!(
+            s.to_matrix(),
+            MulScalar::mul(skewed + skewed.transpose(), 0.5)
+        );
+    }
+
+    #[test]
+    fn operations_match_dense() {
+        let m = Matrix::new([[4.0, 1.0, -2.0], [1.0, 5.0, 0.5], [-2.0, 0.5, 6.0]]);
+        let mut s = SymmetricMatrix::from_matrix(&m).unwrap();
+        let x = List::new([1.0, -2.0, 0.5]);
+        assert_eq!(&s * &x, m * x);
+
+        s.syr(0.5, &x);
+        let outer = Matrix::from_fn(|i, j| 0.5 * x[i] * x[j]);
+        assert_eq!(s.to_matrix(), m + outer);
+    }
+
+    #[test]
+    fn cholesky() {
+        let m = Matrix::new([[4.0, 2.0, -2.0], [2.0, 5.0, 1.0], [-2.0, 1.0, 6.0]]);
+        let l = SymmetricMatrix::from_matrix(&m)
+            .unwrap()
+            .cholesky()
+            .unwrap();
+        assert_eq!(l.matrix()[(0, 1)], 0.0);
+        let product = l * l.transpose();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((product[(i, j)] - m[(i, j)]).abs() < 1e-14);
+            }
+        }
+
+        let indefinite = SymmetricMatrix::<f64, 2>::from_fn(|i, j| if i == j { 1.0 } else { 2.0 });
+        assert_eq!(indefinite.cholesky(), Err(NotPositiveDefinite));
+    }
+}
@@ -0,0 +1,299 @@
+use super::matrix::Matrix;
+use crate::chapters::c1::definitions::{Complex, Zero};
+use crate::chapters::c4::fft::{fft, ifft};
+
+// An n x n matrix that is constant along every diagonal, T[i][j] = t_(j - i).
+// The first column holds t_0, t_-1, ..., t_-(n-1) and the first row t_0, t_1, ..., t_(n-1),
+// so 2n - 1 numbers describe it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToeplitzMatrix<T> {
+    column: Vec<T>,
+    row: Vec<T>,
+}
+
+// A Toeplitz matrix where every row is the one above it shifted right by one and wrapped
+// around, C[i][j] = r_((j - i) mod n). The first row alone describes it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CirculantMatrix<T> {
+    row: Vec<T>,
+}
+
+impl<T> ToeplitzMatrix<T> {
+    // Panics when the two are empty, differ in length or disagree on the corner t_0
+    pub fn new(column: Vec<T>, row: Vec<T>) -> Self
+    where
+        T: PartialEq,
+    {
+        assert!(!row.is_empty(), "empty Toeplitz matrix");
+        assert_eq!(column.len(), row.len(), "wrong length");
+        assert!(column[0] == row[0], "first row and column disagree");
+        ToeplitzMatrix { column, row }
+    }
+
+    pub fn n(&self) -> usize {
+        self.row.len()
+    }
+
+    pub fn column(&self) -> &[T] {
+        &self.column
+    }
+
+    pub fn row(&self) -> &[T] {
+        &self.row
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> &T {
+        assert!(i < self.n() && j < self.n(), "index out of range");
+        if j >= i {
+            &self.row[j - i]
+        } else {
+            &self.column[i - j]
+        }
+    }
+
+    pub fn to_matrix<const N: usize>(&self) -> Matrix<T, N, N>
+    where
+        T: Copy,
+    {
+        assert_eq!(self.n(), N, "wrong shape");
+        Matrix::from_fn(|i, j| *self.get(i, j))
+    }
+}
+
+impl ToeplitzMatrix<f64> {
+    // T x in O(n log n). T is the top left corner of a circulant matrix of size m >= 2n - 1,
+    // whose first column is t_0, t_-1, ..., t_-(n-1), then zeros, then t_(n-1), ..., t_1.
+    // Multiplying that by x padded with zeros is a cyclic convolution, which the FFT turns
+    // into a pointwise product, and the first n entries are T x.
+    pub fn mul_vec(&self, x: &[f64]) -> Vec<f64> {
+        let n = self.n();
+        assert_eq!(x.len(), n, "wrong length");
+        let m = (2 * n - 1).next_power_of_two();
+
+        let mut c = vec![Complex::ZERO; m];
+        for (k, &t) in self.column.iter().enumerate() {
+            c[k] = Complex::from(t);
+        }
+        for (k, &t) in self.row.iter().enumerate().skip(1) {
+            c[m - k] = Complex::from(t);
+        }
+        let mut padded = vec![Complex::ZERO; m];
+        for (p, &xi) in padded.iter_mut().zip(x) {
+            *p = Complex::from(xi);
+        }
+
+        cyclic_convolution(&c, &padded)
+            .into_iter()
+            .take(n)
+            .map(|z| *z.re())
+            .collect()
+    }
+}
+
+impl<T> CirculantMatrix<T> {
+    pub fn new(row: Vec<T>) -> Self {
+        CirculantMatrix { row }
+    }
+
+    pub fn n(&self) -> usize {
+        self.row.len()
+    }
+
+    pub fn row(&self) -> &[T] {
+        &self.row
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> &T {
+        let n = self.n();
+        assert!(i < n && j < n, "index out of range");
+        &self.row[(j + n - i) % n]
+    }
+
+    pub fn to_matrix<const N: usize>(&self) -> Matrix<T, N, N>
+    where
+        T: Copy,
+    {
+        assert_eq!(self.n(), N, "wrong shape");
+        Matrix::from_fn(|i, j| *self.get(i, j))
+    }
+
+    // Every circulant matrix is a Toeplitz matrix
+    pub fn to_toeplitz(&self) -> ToeplitzMatrix<T>
+    where
+        T: Copy,
+    {
+        let n = self.n();
+        assert!(n > 0, "empty Toeplitz matrix");
+        ToeplitzMatrix {
+            column: (0..n).map(|i| *self.get(i, 0)).collect(),
+            row: self.row.clone(),
+        }
+    }
+}
+
+impl CirculantMatrix<f64> {
+    // The first column c_k = r_(-k mod n), as complex numbers
+    fn first_column(&self) -> Vec<Complex<f64>> {
+        (0..self.n())
+            .map(|i| Complex::from(*self.get(i, 0)))
+            .collect()
+    }
+
+    // C x = c * x, the cyclic convolution with the first column, in O(n log n)
+    pub fn mul_vec(&self, x: &[f64]) -> Vec<f64> {
+        assert_eq!(x.len(), self.n(), "wrong length");
+        let x: Vec<_> = x.iter().map(|&xi| Complex::from(xi)).collect();
+        cyclic_convolution(&self.first_column(), &x)
+            .into_iter()
+            .map(|z| *z.re())
+            .collect()
+    }
+
+    // The DFT diagonalises every circulant matrix: the vector v_j = e^(2 pi i jk / n) is an
+    // eigenvector for every k, and its eigenvalue sum_m c_m e^(-2 pi i mk / n) is entry k of
+    // the FFT of the first column. So these are the eigenvalues in that order, found in
+    // O(n log n).
+    pub fn eigenvalues(&self) -> Vec<Complex<f64>> {
+        fft(&self.first_column())
+    }
+}
+
+// (a * b)_i = sum_j a_(i - j mod n) b_j, through the convolution theorem
+fn cyclic_convolution(a: &[Complex<f64>], b: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    let product: Vec<_> = fft(a).into_iter().zip(fft(b)).map(|(p, q)| p * q).collect();
+    ifft(&product)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CirculantMatrix, ToeplitzMatrix};
+    use crate::chapters::c1::definitions::{Complex, List};
+
+    // xorshift64 mapped onto [-1, 1)
+    fn random(state: &mut u64) -> f64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        (*state >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+    }
+
+    fn random_vec(n: usize, state: &mut u64) -> Vec<f64> {
+        (0..n).map(|_| random(state)).collect()
+    }
+
+    fn assert_close(found: &[f64], expected: &[f64], eps: f64) {
+        assert_eq!(found.len(), expected.len());
+        for (a, b) in found.iter().zip(expected) {
+            assert!((a - b).abs() < eps, "{found:?} != {expected:?}");
+        }
+    }
+
+    fn check_multiply<const N: usize>(state: &mut u64) {
+        let mut column = random_vec(N, state);
+        let row = random_vec(N, state);
+        column[0] = row[0];
+        let t = ToeplitzMatrix::new(column, row.clone());
+        let c = CirculantMatrix::new(row);
+        let x = random_vec(N, state);
+
+        let dense = t.to_matrix::<N>() * List::<f64, N>::from_fn(|i| x[i]);
+        assert_close(&t.mul_vec(&x), dense.as_slice(), 1e-12 * N as f64);
+        let dense = c.to_matrix::<N>() * List::<f64, N>::from_fn(|i| x[i]);
+        assert_close(&c.mul_vec(&x), dense.as_slice(), 1e-12 * N as f64);
+    }
+
+    #[test]
+    fn structure() {
+        let t = ToeplitzMatrix::new(vec![1, 4, 5], vec![1, 2, 3]);
+        assert_eq!(t.get(0, 2), &3);
+        assert_eq!(t.get(2, 0), &5);
+        assert_eq!(t.get(2, 1), &4);
+        assert_eq!(t.get(1, 2), &2);
+
+        let c = CirculantMatrix::new(vec![1, 2, 3]);
+        assert_eq!(c.get(1, 0), &3);
+        assert_eq!(c.get(2, 0), &2);
+        assert_eq!(c.get(2, 1), &3);
+        assert_eq!(
+            c.to_toeplitz(),
+            ToeplitzMatrix::new(vec![1, 3, 2], vec![1, 2, 3])
+        );
+        assert_eq!(c.to_toeplitz().to_matrix::<3>(), c.to_matrix::<3>());
+    }
+
+    #[test]
+    #[should_panic(expected = "first row and column disagree")]
+    fn corner_mismatch() {
+        ToeplitzMatrix::new(vec![1, 4, 5], vec![2, 2, 3]);
+    }
+
+    #[test]
+    fn multiply() {
+        let mut state = 0x9e37_79b9_7f4a_7c15;
+        for _ in 0..5 {
+            check_multiply::<1>(&mut state);
+            check_multiply::<2>(&mut state);
+            check_multiply::<3>(&mut state);
+            check_multiply::<5>(&mut state);
+            check_multiply::<8>(&mut state);
+            check_multiply::<13>(&mut state);
+            check_multiply::<16>(&mut state);
+            check_multiply::<31>(&mut state);
+            check_multiply::<64>(&mut state);
+        }
+    }
+
+    #[test]
+    fn convolution() {
+        // A moving average filter is a banded Toeplitz product
+        let t = ToeplitzMatrix::new(
+            vec![0.5, 0.25, 0.0, 0.0, 0.0],
+            vec![0.5, 0.25, 0.0, 0.0, 0.0],
+        );
+        let x = [4.0, 0.0, 8.0, 0.0, 4.0];
+        assert_close(&t.mul_vec(&x), &[2.0, 3.0, 4.0, 3.0, 2.0], 1e-14);
+    }
+
+    // The same values with multiplicity, in any order
+    fn assert_same(found: Vec<Complex<f64>>, expected: &[Complex<f64>], eps: f64) {
+        assert_eq!(found.len(), expected.len());
+        let mut left = found;
+        for &w in expected {
+            let closest = (0..left.len())
+                .min_by(|&i, &j| (left[i] - w).abs().total_cmp(&(left[j] - w).abs()))
+                .unwrap();
+            assert!((left[closest] - w).abs() < eps, "{w} missing from {left:?}");
+            left.swap_remove(closest);
+        }
+    }
+
+    #[test]
+    fn eigenvalues() {
+        let c = CirculantMatrix::new(vec![2.0, -1.0, 0.5, 3.0, 1.5]);
+        let dense = c.to_matrix::<5>().eigenvalues_qr(1e-14, 500).unwrap();
+        assert_same(dense, &c.eigenvalues(), 1e-10);
+
+        // The cyclic second difference, eigenvalues 2 - 2 cos(2 pi k / n)
+        let c = CirculantMatrix::new(vec![2.0, -1.0, 0.0, 0.0, 0.0, -1.0]);
+        let dense = c.to_matrix::<6>().eigenvalues_qr(1e-14, 500).unwrap();
+        assert_same(dense, &c.eigenvalues(), 1e-10);
+        for (k, lambda) in c.eigenvalues().into_iter().enumerate() {
+            let expected = 2.0 - 2.0 * (std::f64::consts::PI * k as f64 / 3.0).cos();
+            assert!((lambda - Complex::from(expected)).abs() < 1e-14);
+        }
+
+        // The eigenvectors are the columns of the DFT
+        let c = CirculantMatrix::new(vec![1.0, 2.0, 0.0, -1.0]);
+        let lambda = c.eigenvalues();
+        let dense = c.to_matrix::<4>();
+        for (k, &l) in lambda.iter().enumerate() {
+            let v: Vec<_> = (0..4)
+                .map(|j| Complex::from_polar(1.0, std::f64::consts::PI * (j * k) as f64 / 2.0))
+                .collect();
+            for i in 0..4 {
+                let cv = (0..4).fold(Complex::new(0.0, 0.0), |acc, j| acc + v[j] * dense[(i, j)]);
+                assert!((cv - l * v[i]).abs() < 1e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use std::ops::{Add, Mul};
+
+use super::matrix::Matrix;
+use super::solve::SolveError;
+use crate::chapters::c1::definitions::{Divide, List, One, Subtract, Zero};
+
+// Square matrices that are zero above (Lower) or below (Upper) the diagonal. The wrapped matrix
+// can only be reached through a shared reference, so it stays triangular, and solves cost O(N^2)
+// instead of a full elimination.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LowerTriangular<T, const N: usize>(Matrix<T, N, N>);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UpperTriangular<T, const N: usize>(Matrix<T, N, N>);
+
+// The first entry (row, col) on the wrong side of the diagonal that isn't zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotTriangular {
+    pub row: usize,
+    pub col: usize,
+}
+
+fn check<T: Zero + PartialEq, const N: usize>(
+    m: &Matrix<T, N, N>,
+    outside: impl Fn(usize, usize) -> bool,
+) -> Result<(), NotTriangular> {
+    for i in 0..N {
+        for j in 0..N {
+            if outside(i, j) && m[(i, j)] != T::ZERO {
+                return Err(NotTriangular { row: i, col: j });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn diagonal_product<T: Mul<Output = T> + One + Copy, const N: usize>(m: &Matrix<T, N, N>) -> T {
+    (0..N).fold(T::ONE, |acc, i| acc * m[(i, i)])
+}
+
+fn check_diagonal<T: Zero + PartialEq, const N: usize>(
+    m: &Matrix<T, N, N>,
+) -> Result<(), SolveError> {
+    if (0..N).any(|i| m[(i, i)] == T::ZERO) {
+        Err(SolveError::Singular)
+    } else {
+        Ok(())
+    }
+}
+
+impl<T: Zero + Copy, const N: usize> LowerTriangular<T, N> {
+    // Fails when anything above the diagonal is nonzero
+    pub fn new(m: Matrix<T, N, N>) -> Result<Self, NotTriangular>
+    where
+        T: PartialEq,
+    {
+        check(&m, |i, j| j > i)?;
+        Ok(LowerTriangular(m))
+    }
+
+    // Keeps the lower triangle of m and zeroes the rest
+    pub fn lower_part_of(m: &Matrix<T, N, N>) -> Self {
+        LowerTriangular(Matrix::from_fn(
+            |i, j| if j > i { T::ZERO } else { m[(i, j)] },
+        ))
+    }
+
+    pub fn transpose(&self) -> UpperTriangular<T, N> {
+        UpperTriangular(self.0.transpose())
+    }
+}
+
+impl<T: Zero + Copy, const N: usize> UpperTriangular<T, N> {
+    // Fails when anything below the diagonal is nonzero
+    pub fn new(m: Matrix<T, N, N>) -> Result<Self, NotTriangular>
+    where
+        T: PartialEq,
+    {
+        check(&m, |i, j| j < i)?;
+        Ok(UpperTriangular(m))
+    }
+
+    // Keeps the upper triangle of m and zeroes the rest
+    pub fn upper_part_of(m: &Matrix<T, N, N>) -> Self {
+        UpperTriangular(Matrix::from_fn(
+            |i, j| if j < i { T::ZERO } else { m[(i, j)] },
+        ))
+    }
+
+    pub fn transpose(&self) -> LowerTriangular<T, N> {
+        LowerTriangular(self.0.transpose())
+    }
+}
+
+impl<T, const N: usize> LowerTriangular<T, N> {
+    pub fn matrix(&self) -> &Matrix<T, N, N> {
+        &self.0
+    }
+
+    pub fn into_matrix(self) -> Matrix<T, N, N> {
+        self.0
+    }
+}
+
+impl<T, const N: usize> UpperTriangular<T, N> {
+    pub fn matrix(&self) -> &Matrix<T, N, N> {
+        &self.0
+    }
+
+    pub fn into_matrix(self) -> Matrix<T, N, N> {
+        self.0
+    }
+}
+
+impl<T, const N: usize> LowerTriangular<T, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + Subtract + Divide + Zero + One + PartialEq + Copy,
+{
+    // Forward substitution
+    pub fn solve(&self, b: &List<T, N>) -> Result<List<T, N>, SolveError> {
+        check_diagonal(&self.0)?;
+        let mut x = *b;
+        for i in 0..N {
+            for j in 0..i {
+                x[i] = x[i].subtract(self.0[(i, j)] * x[j]);
+            }
+            x[i] = x[i].divide(self.0[(i, i)]);
+        }
+        Ok(x)
+    }
+
+    pub fn det(&self) -> T {
+        diagonal_product(&self.0)
+    }
+
+    // Column j of the inverse solves L x = e_j and is zero above row j
+    pub fn inverse(&self) -> Result<Self, SolveError> {
+        check_diagonal(&self.0)?;
+        let mut inverse = Matrix::ZERO;
+        for j in 0..N {
+            for i in j..N {
+                let s = (j..i).fold(if i == j { T::ONE } else { T::ZERO }, |acc, k| {
+                    acc.subtract(self.0[(i, k)] * inverse[(k, j)])
+                });
+                inverse[(i, j)] = s.divide(self.0[(i, i)]);
+            }
+        }
+        Ok(LowerTriangular(inverse))
+    }
+}
+
+impl<T, const N: usize> UpperTriangular<T, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + Subtract + Divide + Zero + One + PartialEq + Copy,
+{
+    // Back substitution
+    pub fn solve(&self, b: &List<T, N>) -> Result<List<T, N>, SolveError> {
+        check_diagonal(&self.0)?;
+        let mut x = *b;
+        for i in (0..N).rev() {
+            for j in i + 1..N {
+                x[i] = x[i].subtract(self.0[(i, j)] * x[j]);
+            }
+            x[i] = x[i].divide(self.0[(i, i)]);
+        }
+        Ok(x)
+    }
+
+    pub fn det(&self) -> T {
+        diagonal_product(&self.0)
+    }
+
+    // U^-1 = ((U^T)^-1)^T
+    pub fn inverse(&self) -> Result<Self, SolveError> {
+        Ok(self.transpose().inverse()?.transpose())
+    }
+}
+
+// Products only run over the part of k where neither factor is known to be zero, so the result
+// is exactly triangular again
+impl<T, const N: usize> Mul for LowerTriangular<T, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        LowerTriangular(Matrix::from_fn(|i, j| {
+            (j..=i).fold(T::ZERO, |acc, k| acc + self.0[(i, k)] * rhs.0[(k, j)])
+        }))
+    }
+}
+
+impl<T, const N: usize> Mul for UpperTriangular<T, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        UpperTriangular(Matrix::from_fn(|i, j| {
+            (i..=j).fold(T::ZERO, |acc, k| acc + self.0[(i, k)] * rhs.0[(k, j)])
+        }))
+    }
+}
+
+impl<T, const N: usize> Mul<UpperTriangular<T, N>> for LowerTriangular<T, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = Matrix<T, N, N>;
+
+    fn mul(self, rhs: UpperTriangular<T, N>) -> Self::Output {
+        Matrix::from_fn(|i, j| {
+            (0..=i.min(j)).fold(T::ZERO, |acc, k| acc + self.0[(i, k)] * rhs.0[(k, j)])
+        })
+    }
+}
+
+impl<T, const N: usize> Mul<LowerTriangular<T, N>> for UpperTriangular<T, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = Matrix<T, N, N>;
+
+    fn mul(self, rhs: LowerTriangular<T, N>) -> Self::Output {
+        Matrix::from_fn(|i, j| {
+            (i.max(j)..N).fold(T::ZERO, |acc, k| acc + self.0[(i, k)] * rhs.0[(k, j)])
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LowerTriangular, NotTriangular, UpperTriangular};
+    use crate::chapters::c1::definitions::List;
+    use crate::chapters::c3::matrix::Matrix;
+    use crate::chapters::c3::solve::{SolveError, solve};
+
+    fn assert_close<const N: usize>(a: &Matrix<f64, N, N>, b: &Matrix<f64, N, N>) {
+        for i in 0..N {
+            for j in 0..N {
+                assert!((a[(i, j)] - b[(i, j)]).abs() < 1e-12, "{a}\n!=\n{b}");
+            }
+        }
+    }
+
+    #[test]
+    fn constructors() {
+        let m = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        assert_eq!(
+            LowerTriangular::new(m),
+            Err(NotTriangular { row: 0, col: 1 })
+        );
+        assert_eq!(
+            UpperTriangular::new(m),
+            Err(NotTriangular { row: 1, col: 0 })
+        );
+
+        let l = LowerTriangular::lower_part_of(&m);
+        assert_eq!(l.matrix()[(0, 2)], 0.0);
+        assert_eq!(l.matrix()[(2, 0)], 7.0);
+        assert_eq!(LowerTriangular::new(*l.matrix()), Ok(l));
+        assert_eq!(
+            UpperTriangular::new(*l.transpose().matrix()),
+            Ok(l.transpose())
+        );
+    }
+
+    #[test]
+    fn match_general_routines() {
+        let l = LowerTriangular::new(Matrix::new([
+            [2.0, 0.0, 0.0],
+            [1.0, -3.0, 0.0],
+            [0.5, 4.0, 1.5],
+        ]))
+        .unwrap();
+        let u = UpperTriangular::upper_part_of(&Matrix::new([
+            [1.0, 2.0, -1.0],
+            [0.0, 4.0, 0.5],
+            [0.0, 0.0, -2.0],
+        ]));
+        let b = List::new([1.0, -2.0, 3.0]);
+
+        let x = l.solve(&b).unwrap();
+        let dense = solve(*l.matrix(), b).unwrap();
+        for i in 0..3 {
+            assert!((x[i] - dense[i]).abs() < 1e-14);
+        }
+        let x = u.solve(&b).unwrap();
+        let dense = solve(*u.matrix(), b).unwrap();
+        for i in 0..3 {
+            assert!((x[i] - dense[i]).abs() < 1e-14);
+        }
+
+        assert_eq!(l.det(), -9.0);
+        assert!((l.det() - l.matrix().determinant()).abs() < 1e-12);
+        assert!((u.det() - u.matrix().determinant()).abs() < 1e-12);
+
+        let l_inv = l.inverse().unwrap();
+        assert_close(l_inv.matrix(), &l.matrix().invert().unwrap());
+        assert_close((l * l_inv).matrix(), &Matrix::identity());
+        let u_inv = u.inverse().unwrap();
+        assert_close(u_inv.matrix(), &u.matrix().invert().unwrap());
+        assert_close((u_inv * u).matrix(), &Matrix::identity());
+
+        // Products agree with the dense ones and keep their shape
+        assert_eq!(l * u, *l.matrix() * *u.matrix());
+        assert_eq!(u * l, *u.matrix() * *l.matrix());
+        assert_eq!((l * l).matrix(), &(*l.matrix() * *l.matrix()));
+        assert_eq!((u * u).matrix(), &(*u.matrix() * *u.matrix()));
+
+        let singular = UpperTriangular::upper_part_of(&Matrix::new([[1.0, 2.0], [0.0, 0.0]]));
+        assert_eq!(
+            singular.solve(&List::new([1.0, 1.0])),
+            Err(SolveError::Singular)
+        );
+        assert_eq!(singular.inverse(), Err(SolveError::Singular));
+    }
+
+    #[test]
+    fn lu_reconstructs() {
+        let a = Matrix::new([
+            [1.0, 2.0, 0.5, -1.0],
+            [4.0, 1.0, 3.0, 2.0],
+            [-2.0, 0.5, 1.0, 6.0],
+            [3.0, -1.0, 2.0, 1.0],
+        ]);
+        let lu = a.lu().unwrap();
+        let pa = Matrix::from_fn(|i, j| a[(lu.row[i], j)]);
+        assert_close(&(lu.l * lu.u), &pa);
+        for i in 0..4 {
+            assert_eq!(lu.l.matrix()[(i, i)], 1.0);
+        }
+
+        let b = List::new([1.0, 2.0, 3.0, 4.0]);
+        let x = lu.solve(&b).unwrap();
+        let dense = solve(a, b).unwrap();
+        for i in 0..4 {
+            assert!((x[i] - dense[i]).abs() < 1e-14);
+        }
+    }
+}
@@ -0,0 +1,433 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Index, IndexMut, Mul, Range};
+
+use super::matrix::{Matrix, write_entries};
+use crate::chapters::c1::definitions::{List, Zero};
+use crate::chapters::c6::inner_product::Modulus;
+
+// A rectangular block of a matrix, borrowed instead of copied. The shape is only known at
+// runtime, so indices and operands are checked when used. Each row of the block is a slice
+// into the parent, which lets the borrow checker prove that mutable views never overlap.
+
+pub struct MatrixView<'a, T> {
+    rows: Vec<&'a [T]>,
+    cols: usize,
+}
+
+pub struct MatrixViewMut<'a, T> {
+    rows: Vec<&'a mut [T]>,
+    cols: usize,
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> {
+    // Panics when the ranges reach outside the matrix, like slicing does
+    pub fn view(&self, rows: Range<usize>, cols: Range<usize>) -> MatrixView<'_, T> {
+        let width = cols.len();
+        MatrixView {
+            rows: (rows.start..rows.end)
+                .map(|i| &self.row(i).as_slice()[cols.clone()])
+                .collect(),
+            cols: width,
+        }
+    }
+
+    pub fn view_mut(&mut self, rows: Range<usize>, cols: Range<usize>) -> MatrixViewMut<'_, T> {
+        let width = cols.len();
+        MatrixViewMut {
+            rows: self.rows_mut()[rows]
+                .iter_mut()
+                .map(|row| &mut row.as_mut_slice()[cols.clone()])
+                .collect(),
+            cols: width,
+        }
+    }
+}
+
+impl<T> MatrixView<'_, T> {
+    pub fn nrows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        self.rows[i]
+    }
+
+    // The entries row by row
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.rows.iter().flat_map(|row| row.iter())
+    }
+
+    // A sub block, with ranges relative to this view
+    pub fn view(&self, rows: Range<usize>, cols: Range<usize>) -> MatrixView<'_, T> {
+        let width = cols.len();
+        MatrixView {
+            rows: self.rows[rows]
+                .iter()
+                .map(|row| &row[cols.clone()])
+                .collect(),
+            cols: width,
+        }
+    }
+
+    // A copy of the block as a fixed size matrix, panics if the shape is not R x C
+    pub fn to_matrix<const R: usize, const C: usize>(&self) -> Matrix<T, R, C>
+    where
+        T: Copy,
+    {
+        assert_eq!((self.nrows(), self.ncols()), (R, C), "wrong shape");
+        Matrix::from_fn(|i, j| self.rows[i][j])
+    }
+
+    // The view applied to x, panics when x doesn't have ncols entries
+    pub fn mul_vec(&self, x: &[T]) -> Vec<T>
+    where
+        T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+    {
+        assert_eq!(x.len(), self.cols, "wrong length");
+        let dot = |row: &[T]| row.iter().zip(x).fold(T::ZERO, |acc, (&a, &b)| acc + a * b);
+        self.rows.iter().map(|row| dot(row)).collect()
+    }
+
+    // sqrt(sum |a_ij|^2)
+    pub fn frobenius_norm(&self) -> f64
+    where
+        T: Modulus + Copy,
+    {
+        self.iter().map(|x| x.modulus().powi(2)).sum::<f64>().sqrt()
+    }
+}
+
+impl<T> Index<(usize, usize)> for MatrixView<'_, T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.rows[i][j]
+    }
+}
+
+// The mutable view reads like a MatrixView, use as_view for the rest of the read operations
+impl<T> MatrixViewMut<'_, T> {
+    pub fn nrows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        self.rows[i]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        self.rows[i]
+    }
+}
+
+impl<T> Index<(usize, usize)> for MatrixViewMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.rows[i][j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for MatrixViewMut<'_, T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        &mut self.rows[i][j]
+    }
+}
+
+impl<T> MatrixViewMut<'_, T> {
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView {
+            rows: self.rows.iter().map(|row| &**row).collect(),
+            cols: self.cols,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.rows.iter_mut().flat_map(|row| row.iter_mut())
+    }
+
+    // The rows before k and the rows from k on, both usable at the same time
+    pub fn split_at_row(mut self, k: usize) -> (Self, Self) {
+        let bottom = self.rows.split_off(k);
+        let cols = self.cols;
+        (self, MatrixViewMut { rows: bottom, cols })
+    }
+
+    pub fn fill(&mut self, x: T)
+    where
+        T: Copy,
+    {
+        self.iter_mut().for_each(|y| *y = x);
+    }
+
+    pub fn scale(&mut self, a: T)
+    where
+        T: Mul<Output = T> + Copy,
+    {
+        self.iter_mut().for_each(|y| *y = *y * a);
+    }
+
+    // self += rhs, panics when the shapes differ
+    pub fn add_assign(&mut self, rhs: &MatrixView<'_, T>)
+    where
+        T: Add<Output = T> + Copy,
+    {
+        assert_eq!(
+            (self.nrows(), self.ncols()),
+            (rhs.nrows(), rhs.ncols()),
+            "wrong shape"
+        );
+        for (y, x) in self.iter_mut().zip(rhs.iter()) {
+            *y = *y + *x;
+        }
+    }
+
+    // Copies rhs into the block, panics when the shapes differ
+    pub fn assign(&mut self, rhs: &MatrixView<'_, T>)
+    where
+        T: Copy,
+    {
+        assert_eq!(
+            (self.nrows(), self.ncols()),
+            (rhs.nrows(), rhs.ncols()),
+            "wrong shape"
+        );
+        for (y, x) in self.iter_mut().zip(rhs.iter()) {
+            *y = *x;
+        }
+    }
+}
+
+// The transpose of a borrowed R x C matrix, a C x R matrix that reads the original with the
+// indices swapped instead of copying it. Products with it walk the stored rows of both
+// operands, and add their terms in the same order as the product with a.transpose().
+pub struct Transposed<'a, T, const R: usize, const C: usize> {
+    of: &'a Matrix<T, R, C>,
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn as_transposed(&self) -> Transposed<'_, T, R, C> {
+        Transposed { of: self }
+    }
+}
+
+// Written out instead of derived, which would require T: Clone
+impl<T, const R: usize, const C: usize> Clone for Transposed<'_, T, R, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, const R: usize, const C: usize> Copy for Transposed<'_, T, R, C> {}
+
+impl<'a, T, const R: usize, const C: usize> Transposed<'a, T, R, C> {
+    // Transposing again gives back the original matrix
+    pub fn as_transposed(&self) -> &'a Matrix<T, R, C> {
+        self.of
+    }
+
+    pub fn to_matrix(&self) -> Matrix<T, C, R>
+    where
+        T: Copy,
+    {
+        self.of.transpose()
+    }
+
+    pub fn frobenius_norm(&self) -> f64
+    where
+        T: Modulus + Copy,
+    {
+        self.of.view(0..R, 0..C).frobenius_norm()
+    }
+}
+
+impl<T, const R: usize, const C: usize> Index<(usize, usize)> for Transposed<'_, T, R, C> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.of[(j, i)]
+    }
+}
+
+impl<T, const R: usize, const C: usize> Display for Transposed<'_, T, R, C>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_entries(f, C, R, |i, j| &self[(i, j)])
+    }
+}
+
+// A^T B = sum_j (row j of A)^T (row j of B), the rows of both are read front to back
+impl<T, const R: usize, const C: usize, const K: usize> Mul<Matrix<T, R, K>>
+    for Transposed<'_, T, R, C>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = Matrix<T, C, K>;
+
+    fn mul(self, rhs: Matrix<T, R, K>) -> Self::Output {
+        let mut out = Matrix::<T, C, K>::ZERO;
+        for j in 0..R {
+            let (a, b) = (self.of.row(j), rhs.row(j));
+            for (i, row) in out.rows_mut().iter_mut().enumerate() {
+                row.axpy(a[i], b);
+            }
+        }
+        out
+    }
+}
+
+// (A B^T)_ik is the dot product of row i of A and row k of B
+impl<T, const R: usize, const C: usize, const K: usize> Mul<Transposed<'_, T, K, C>>
+    for Matrix<T, R, C>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, rhs: Transposed<'_, T, K, C>) -> Self::Output {
+        Matrix::from_fn(|i, k| {
+            let (a, b) = (self.row(i), rhs.of.row(k));
+            (0..C).fold(T::ZERO, |acc, j| acc + a[j] * b[j])
+        })
+    }
+}
+
+// A^T x = sum_j x_j (row j of A)
+impl<T, const R: usize, const C: usize> Mul<List<T, R>> for Transposed<'_, T, R, C>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = List<T, C>;
+
+    fn mul(self, rhs: List<T, R>) -> Self::Output {
+        let mut out = List::ZERO;
+        for (j, x) in rhs.iter().enumerate() {
+            out.axpy(*x, self.of.row(j));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::chapters::c1::definitions::{List, Zero};
+    use crate::chapters::c3::matrix::Matrix;
+
+    fn sample() -> Matrix<f64, 4, 5> {
+        Matrix::from_fn(|i, j| (i * 5 + j) as f64 - 7.0)
+    }
+
+    #[test]
+    fn matches_copies() {
+        let m = sample();
+        let view = m.view(1..3, 2..5);
+        let copy: Matrix<f64, 2, 3> = Matrix::from_fn(|i, j| m[(i + 1, j + 2)]);
+        assert_eq!((view.nrows(), view.ncols()), (2, 3));
+        assert_eq!(view.to_matrix::<2, 3>(), copy);
+        assert_eq!(view[(1, 2)], m[(2, 4)]);
+        assert_eq!(view.row(0), &[0.0, 1.0, 2.0]);
+
+        let x = [0.5, -1.0, 2.0];
+        assert_eq!(view.mul_vec(&x), (copy * List::new(x)).as_slice());
+        let entries: Vec<f64> = view.iter().copied().collect();
+        assert_eq!(entries, [0.0, 1.0, 2.0, 5.0, 6.0, 7.0]);
+        let frobenius = entries.iter().map(|x| x * x).sum::<f64>().sqrt();
+        assert_eq!(view.frobenius_norm(), frobenius);
+
+        // Views of views are relative to the view
+        assert_eq!(
+            view.view(1..2, 0..2).to_matrix::<1, 2>(),
+            Matrix::new([[5.0, 6.0]])
+        );
+        assert_eq!(m.view(2..2, 0..5).iter().count(), 0);
+    }
+
+    #[test]
+    fn mutation_shows_in_parent() {
+        let mut m = sample();
+        let mut view = m.view_mut(0..2, 1..3);
+        view[(0, 0)] = 100.0;
+        view.scale(2.0);
+        view.row_mut(1)[1] = -1.0;
+        assert_eq!(
+            view.as_view().to_matrix::<2, 2>(),
+            Matrix::new([[200.0, -10.0], [-2.0, -1.0]])
+        );
+
+        let expected = Matrix::from_fn(|i, j| match (i, j) {
+            (0, 1) => 200.0,
+            (1, 2) => -1.0,
+            (0..2, 1..3) => 2.0 * sample()[(i, j)],
+            _ => sample()[(i, j)],
+        });
+        assert_eq!(m, expected);
+
+        let mut view = m.view_mut(3..4, 0..5);
+        view.fill(0.0);
+        assert_eq!(*m.row(3), List::ZERO);
+    }
+
+    #[test]
+    fn split_views() {
+        let mut m = sample();
+        let (mut top, mut bottom) = m.view_mut(0..4, 0..5).split_at_row(2);
+        assert_eq!((top.nrows(), bottom.nrows()), (2, 2));
+
+        // Both halves at the same time: swap them through a copy of the top
+        let old_top = top.as_view().to_matrix::<2, 5>();
+        top.assign(&bottom.as_view());
+        bottom.assign(&old_top.view(0..2, 0..5));
+        bottom.add_assign(&top.as_view());
+
+        let original = sample();
+        assert_eq!(*m.row(0), *original.row(2));
+        assert_eq!(*m.row(1), *original.row(3));
+        assert_eq!(*m.row(2), *original.row(0) + *original.row(2));
+        assert_eq!(*m.row(3), *original.row(1) + *original.row(3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range() {
+        let m = sample();
+        m.view(0..2, 3..6);
+    }
+
+    #[test]
+    fn transposed() {
+        let a = Matrix::<f64, 3, 4>::from_fn(|i, j| ((i * 4 + j) as f64 * 0.37).sin());
+        let b = Matrix::<f64, 3, 2>::from_fn(|i, j| 1.0 / (i + 3 * j + 1) as f64);
+        let c = Matrix::<f64, 2, 4>::from_fn(|i, j| (i as f64 - j as f64) / 3.0);
+        let t = a.as_transposed();
+
+        // Exactly equal to the products with the materialized transpose
+        assert_eq!(t * b, a.transpose() * b);
+        assert_eq!(c * t, c * a.transpose());
+        let x = List::new([0.1, -2.0, 1.0 / 7.0]);
+        assert_eq!(t * x, a.transpose() * x);
+
+        assert_eq!(t.to_matrix(), a.transpose());
+        assert_eq!(t[(3, 1)], a[(1, 3)]);
+        assert_eq!(format!("{t:+.3}"), format!("{:+.3}", a.transpose()));
+        assert_eq!(format!("{t:#}"), format!("{:#}", a.transpose()));
+        assert_eq!(
+            t.frobenius_norm(),
+            a.transpose().view(0..4, 0..3).frobenius_norm()
+        );
+
+        // Transposing twice is the identity, entry for entry
+        let back = t.as_transposed();
+        assert!(std::ptr::eq(back, &a));
+        assert_eq!(*back, a);
+    }
+}
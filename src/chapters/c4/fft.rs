@@ -0,0 +1,256 @@
+use std::f64::consts::PI;
+
+use crate::chapters::c1::definitions::{Complex, Zero};
+use crate::chapters::c3::matrix::Matrix;
+
+// The discrete Fourier transform X_k = sum_j x_j e^(-2 pi i jk / n), without any scaling.
+// That is the polynomial with coefficients x evaluated at the n-th roots of unity, in order.
+// Powers of two go through the radix 2 Cooley-Tukey FFT, any other length through Bluestein's
+// algorithm, which rewrites the transform as a convolution of a power of two length.
+// Either way it takes O(n log n).
+pub fn fft(x: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    let mut x = x.to_vec();
+    if x.len().is_power_of_two() {
+        radix2(&mut x);
+        x
+    } else if x.is_empty() {
+        x
+    } else {
+        bluestein(&x)
+    }
+}
+
+// The inverse of fft, including the 1 / n
+pub fn ifft(x: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    // Conjugating on both sides flips the sign of the exponent
+    let conjugated: Vec<_> = x.iter().map(|z| z.conjugate()).collect();
+    let n = x.len() as f64;
+    fft(&conjugated)
+        .into_iter()
+        .map(|z| z.conjugate() / n)
+        .collect()
+}
+
+// F[j][k] = w^(jk) / sqrt(N) with w = e^(-2 pi i / N), the Vandermonde matrix of the N-th roots
+// of unity scaled to be unitary. Its columns are the Fourier basis, so F x is x in that basis.
+pub fn dft_matrix<const N: usize>() -> Matrix<Complex<f64>, N, N> {
+    let scale = 1.0 / (N as f64).sqrt();
+    Matrix::from_fn(|j, k| {
+        // jk mod N keeps the angle small, so that w^N comes out as exactly 1
+        let angle = -2.0 * PI * ((j * k) % N) as f64 / N as f64;
+        Complex::from_polar(scale, angle)
+    })
+}
+
+// F x for the unitary dft_matrix, through the FFT in O(n log n), so it preserves the norm
+pub fn dft(x: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    let scale = 1.0 / (x.len() as f64).sqrt();
+    fft(x).into_iter().map(|z| z * scale).collect()
+}
+
+// F^-1 x = F* x, the inverse of dft
+pub fn idft(x: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    let scale = (x.len() as f64).sqrt();
+    ifft(x).into_iter().map(|z| z * scale).collect()
+}
+
+// In place, x.len() must be a power of two
+fn radix2(x: &mut [Complex<f64>]) {
+    let n = x.len();
+
+    // Bit reversed order first, so that every pass combines neighbouring blocks
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i
+            .reverse_bits()
+            .checked_shr(usize::BITS - bits)
+            .unwrap_or(0);
+        if i < j {
+            x.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        for block in x.chunks_exact_mut(len) {
+            for k in 0..half {
+                let w = Complex::from_polar(1.0, -2.0 * PI * k as f64 / len as f64);
+                let even = block[k];
+                let odd = block[k + half] * w;
+                block[k] = even + odd;
+                block[k + half] = even - odd;
+            }
+        }
+        len *= 2;
+    }
+}
+
+// From jk = (j^2 + k^2 - (k - j)^2) / 2: X_k = w_k sum_j (x_j w_j) conj(w_(k - j)) with
+// w_j = e^(-pi i j^2 / n), a convolution that is done with power of two transforms
+// after padding to at least 2n - 1.
+fn bluestein(x: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    let n = x.len();
+    let m = (2 * n - 1).next_power_of_two();
+
+    // j^2 mod 2n keeps the angles small, so they stay accurate for large j
+    let chirp: Vec<_> = (0..n)
+        .map(|j| {
+            let square = (j * j) % (2 * n);
+            Complex::from_polar(1.0, -PI * square as f64 / n as f64)
+        })
+        .collect();
+
+    let mut a: Vec<_> = x.iter().zip(&chirp).map(|(&xj, &w)| xj * w).collect();
+    a.resize(m, Complex::ZERO);
+    // conj(w) at every index from -(n - 1) to n - 1, the negative ones wrapped around
+    let mut b = vec![Complex::ZERO; m];
+    b[0] = chirp[0].conjugate();
+    for j in 1..n {
+        b[j] = chirp[j].conjugate();
+        b[m - j] = chirp[j].conjugate();
+    }
+
+    radix2(&mut a);
+    radix2(&mut b);
+    let mut c: Vec<_> = a
+        .iter()
+        .zip(&b)
+        .map(|(&p, &q)| (p * q).conjugate())
+        .collect();
+    // The inverse transform through the conjugate, the 1 / m goes in at the end
+    radix2(&mut c);
+    (0..n)
+        .map(|k| c[k].conjugate() * chirp[k] / m as f64)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+
+    use super::{dft, dft_matrix, fft, idft, ifft};
+    use crate::chapters::c1::definitions::{Complex, List};
+
+    fn naive(x: &[Complex<f64>]) -> Vec<Complex<f64>> {
+        let n = x.len();
+        (0..n)
+            .map(|k| {
+                x.iter()
+                    .enumerate()
+                    .fold(Complex::new(0.0, 0.0), |acc, (j, &xj)| {
+                        let angle = -2.0 * PI * ((j * k) % n) as f64 / n as f64;
+                        acc + xj * Complex::from_polar(1.0, angle)
+                    })
+            })
+            .collect()
+    }
+
+    fn signal(n: usize) -> Vec<Complex<f64>> {
+        (0..n)
+            .map(|j| {
+                Complex::new(
+                    (j as f64 * 0.7).sin() + 0.3,
+                    (j as f64 * 1.3).cos() - 0.1 * j as f64,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_definition() {
+        // Powers of two, primes and everything in between go through both paths
+        for n in 1..=33 {
+            let x = signal(n);
+            for (a, b) in fft(&x).into_iter().zip(naive(&x)) {
+                assert!((a - b).abs() < 1e-10 * n as f64, "n = {n}: {a} != {b}");
+            }
+        }
+        assert!(fft(&[]).is_empty());
+    }
+
+    #[test]
+    fn round_trip() {
+        for n in [1, 2, 6, 16, 17, 100, 128, 1000] {
+            let x = signal(n);
+            for (a, b) in ifft(&fft(&x)).into_iter().zip(&x) {
+                assert!((a - *b).abs() < 1e-12 * n as f64, "n = {n}: {a} != {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn unitary() {
+        assert!(dft_matrix::<1>().is_unitary(1e-15));
+        assert!(dft_matrix::<4>().is_unitary(1e-15));
+        assert!(dft_matrix::<7>().is_unitary(1e-14));
+        assert!(dft_matrix::<16>().is_unitary(1e-14));
+
+        // F^2 reverses the indices mod N, so F^4 = I
+        let f = dft_matrix::<5>();
+        let f2 = f * f;
+        for j in 0..5 {
+            for k in 0..5 {
+                let expected = if (j + k) % 5 == 0 { 1.0 } else { 0.0 };
+                assert!((f2[(j, k)] - Complex::from(expected)).abs() < 1e-14);
+            }
+        }
+    }
+
+    fn check_paths<const N: usize>() {
+        let x = signal(N);
+        let dense = dft_matrix::<N>() * List::<Complex<f64>, N>::from_fn(|i| x[i]);
+        let unitary = dft(&x);
+        for (a, b) in unitary.iter().zip(dense.iter()) {
+            assert!((*a - *b).abs() < 1e-12 * N as f64, "N = {N}: {a} != {b}");
+        }
+
+        // Unitary, so the norm doesn't change, and idft undoes it
+        let norm = |v: &[Complex<f64>]| v.iter().map(|z| z.norm_squared()).sum::<f64>().sqrt();
+        assert!((norm(&unitary) - norm(&x)).abs() < 1e-12 * norm(&x));
+        for (a, b) in idft(&unitary).into_iter().zip(&x) {
+            assert!((a - *b).abs() < 1e-12 * N as f64);
+        }
+    }
+
+    #[test]
+    fn fft_and_matrix_agree() {
+        check_paths::<1>();
+        check_paths::<2>();
+        check_paths::<3>();
+        check_paths::<8>();
+        check_paths::<12>();
+        check_paths::<17>();
+        check_paths::<32>();
+    }
+
+    #[test]
+    fn sinusoid() {
+        // cos(2 pi 3 j / n) = (w^-3j + w^3j) / 2, so all the energy is in bins 3 and n - 3
+        for n in [16, 20] {
+            let x: Vec<_> = (0..n)
+                .map(|j| Complex::from((2.0 * PI * 3.0 * j as f64 / n as f64).cos()))
+                .collect();
+            let spectrum = dft(&x);
+            let height = (n as f64).sqrt() / 2.0;
+            for (k, z) in spectrum.into_iter().enumerate() {
+                let expected = if k == 3 || k == n - 3 { height } else { 0.0 };
+                assert!(
+                    (z - Complex::from(expected)).abs() < 1e-12,
+                    "n = {n}, bin {k}: {z}"
+                );
+            }
+        }
+
+        // A complex exponential lands in a single bin
+        let x: Vec<_> = (0..12)
+            .map(|j| Complex::from_polar(1.0, 2.0 * PI * 5.0 * j as f64 / 12.0))
+            .collect();
+        let spectrum = dft(&x);
+        let peak = (0..12)
+            .max_by(|&a, &b| spectrum[a].abs().total_cmp(&spectrum[b].abs()))
+            .unwrap();
+        assert_eq!(peak, 5);
+        assert!((spectrum[5].abs() - 12f64.sqrt()).abs() < 1e-12);
+    }
+}
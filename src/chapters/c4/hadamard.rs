@@ -0,0 +1,113 @@
+use crate::chapters::c3::matrix::Matrix;
+
+// wht only works on lengths 1, 2, 4, 8, ...
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotPowerOfTwo {
+    pub len: usize,
+}
+
+// The Sylvester construction H_1 = (1), H_2n = ( H_n  H_n )
+//                                             ( H_n -H_n ),
+// in closed form H[i][j] = (-1)^(number of bits i and j share). H H^T = N I, so H / sqrt(N) is
+// orthogonal. Only defined when N is a power of two, anything else fails to compile:
+/// ```compile_fail
+/// use linear_algebra::chapters::c3::matrix::Matrix;
+///
+/// let h = Matrix::<i8, 6, 6>::hadamard();
+/// ```
+impl<const N: usize> Matrix<i8, N, N> {
+    pub fn hadamard() -> Self {
+        const { assert!(N.is_power_of_two(), "N must be a power of two") };
+        Matrix::from_fn(|i, j| if (i & j).count_ones() % 2 == 0 { 1 } else { -1 })
+    }
+}
+
+// The Walsh-Hadamard transform x <- H x in place, for the Sylvester H above.
+// Every pass applies the 2 x 2 block to pairs at distance h, which is the recursion unrolled,
+// so it takes O(n log n) additions and no multiplications.
+pub fn wht(x: &mut [f64]) -> Result<(), NotPowerOfTwo> {
+    let n = x.len();
+    if !n.is_power_of_two() {
+        return Err(NotPowerOfTwo { len: n });
+    }
+
+    let mut h = 1;
+    while h < n {
+        for block in x.chunks_exact_mut(2 * h) {
+            let (left, right) = block.split_at_mut(h);
+            for (a, b) in left.iter_mut().zip(right) {
+                (*a, *b) = (*a + *b, *a - *b);
+            }
+        }
+        h *= 2;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NotPowerOfTwo, wht};
+    use crate::chapters::c1::definitions::List;
+    use crate::chapters::c3::matrix::Matrix;
+
+    fn check_orthogonal<const N: usize>() {
+        let h = Matrix::<i8, N, N>::hadamard();
+        let h = Matrix::<i64, N, N>::from_fn(|i, j| h[(i, j)] as i64);
+        let expected = Matrix::from_fn(|i, j| if i == j { N as i64 } else { 0 });
+        assert_eq!(h * h.transpose(), expected);
+    }
+
+    #[test]
+    fn sylvester() {
+        assert_eq!(Matrix::<i8, 1, 1>::hadamard(), Matrix::new([[1]]));
+        assert_eq!(
+            Matrix::<i8, 4, 4>::hadamard(),
+            Matrix::new([[1, 1, 1, 1], [1, -1, 1, -1], [1, 1, -1, -1], [1, -1, -1, 1]])
+        );
+
+        // H H^T = N I exactly
+        check_orthogonal::<1>();
+        check_orthogonal::<2>();
+        check_orthogonal::<8>();
+        check_orthogonal::<32>();
+        check_orthogonal::<128>();
+    }
+
+    fn check_transform<const N: usize>() {
+        let x: [f64; N] = core::array::from_fn(|i| (i as f64 * 0.37).sin() * 4.0 - 1.0);
+        let h = Matrix::<i8, N, N>::hadamard();
+        let h = Matrix::<f64, N, N>::from_fn(|i, j| h[(i, j)] as f64);
+        let dense = h * List::new(x);
+
+        let mut fast = x;
+        wht(&mut fast).unwrap();
+        for (a, b) in fast.iter().zip(dense.iter()) {
+            assert!((a - b).abs() < 1e-12 * N as f64, "{fast:?} != {dense:?}");
+        }
+
+        // H H = N I, so transforming twice gives N x back
+        wht(&mut fast).unwrap();
+        for (a, b) in fast.iter().zip(&x) {
+            assert!((a - N as f64 * b).abs() < 1e-12 * N as f64);
+        }
+    }
+
+    #[test]
+    fn fast_transform() {
+        check_transform::<1>();
+        check_transform::<2>();
+        check_transform::<4>();
+        check_transform::<16>();
+        check_transform::<64>();
+    }
+
+    #[test]
+    fn not_a_power_of_two() {
+        for n in [0, 3, 6, 12, 100] {
+            let mut x = vec![1.0; n];
+            assert_eq!(wht(&mut x), Err(NotPowerOfTwo { len: n }));
+            // Left alone
+            assert!(x.iter().all(|&xi| xi == 1.0));
+        }
+    }
+}
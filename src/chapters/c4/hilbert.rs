@@ -0,0 +1,111 @@
+use crate::chapters::c1::rational::Rational;
+use crate::chapters::c3::matrix::Matrix;
+
+impl<const N: usize> Matrix<f64, N, N> {
+    // h_ij = 1 / (i + j + 1), the Gram matrix of 1, x, x^2, ... in L^2(0, 1). Positive definite
+    // but with a condition number growing like e^(3.5 N), the standard hard case for solvers.
+    // Most entries aren't representable, so this is already a perturbation of the real thing.
+    pub fn hilbert() -> Self {
+        Matrix::from_fn(|i, j| 1.0 / (i + j + 1) as f64)
+    }
+}
+
+impl<const N: usize> Matrix<Rational, N, N> {
+    pub fn hilbert_exact() -> Self {
+        Matrix::from_fn(|i, j| Rational::new(1, (i + j + 1) as i64))
+    }
+
+    // The inverse of the Hilbert matrix has integer entries
+    //  (-1)^(i + j) (i + j + 1) C(N + i, N - j - 1) C(N + j, N - i - 1) C(i + j, i)^2
+    // Overflows an i64 from N = 15 on.
+    pub fn inverse_hilbert_exact() -> Self {
+        Matrix::from_fn(|i, j| {
+            let entry = (i + j + 1) as i64
+                * binomial(N + i, N - j - 1)
+                * binomial(N + j, N - i - 1)
+                * binomial(i + j, i).pow(2);
+            Rational::from(if (i + j) % 2 == 0 { entry } else { -entry })
+        })
+    }
+}
+
+// n choose k, every partial product is itself a binomial coefficient so the division is exact
+fn binomial(n: usize, k: usize) -> i64 {
+    (0..k).fold(1, |acc, t| acc * (n - t) as i64 / (t + 1) as i64)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::chapters::c1::rational::Rational;
+    use crate::chapters::c3::matrix::Matrix;
+
+    fn exact_inverse<const N: usize>() {
+        let (h, inverse) = (
+            Matrix::<Rational, N, N>::hilbert_exact(),
+            Matrix::<Rational, N, N>::inverse_hilbert_exact(),
+        );
+        assert_eq!(h * inverse, Matrix::identity());
+        assert_eq!(inverse * h, Matrix::identity());
+    }
+
+    #[test]
+    fn exact() {
+        exact_inverse::<1>();
+        exact_inverse::<2>();
+        exact_inverse::<3>();
+        exact_inverse::<4>();
+        exact_inverse::<5>();
+        exact_inverse::<6>();
+
+        let inverse = Matrix::<Rational, 3, 3>::inverse_hilbert_exact();
+        let expected = Matrix::new([[9, -36, 30], [-36, 192, -180], [30, -180, 180]]);
+        assert_eq!(
+            inverse,
+            Matrix::from_fn(|i, j| Rational::from(expected[(i, j)]))
+        );
+        assert_eq!(Matrix::<f64, 3, 3>::hilbert()[(1, 2)], 0.25);
+    }
+
+    // max |h h^-1 - I| with the inverse from LU
+    fn float_residual<const N: usize>() -> f64 {
+        let h = Matrix::<f64, N, N>::hilbert();
+        let product = h * h.invert().unwrap();
+        let mut residual: f64 = 0.0;
+        for i in 0..N {
+            for j in 0..N {
+                let target = if i == j { 1.0 } else { 0.0 };
+                residual = residual.max((product[(i, j)] - target).abs());
+            }
+        }
+        residual
+    }
+
+    // The largest over the smallest eigenvalue, the 2-norm condition number of an SPD matrix
+    fn condition<const N: usize>() -> f64 {
+        let (lambda, _) = Matrix::<f64, N, N>::hilbert().symmetric_eigen();
+        lambda[0] / lambda[N - 1]
+    }
+
+    #[test]
+    fn ill_conditioned() {
+        let residuals = [
+            float_residual::<2>(),
+            float_residual::<4>(),
+            float_residual::<6>(),
+            float_residual::<8>(),
+            float_residual::<10>(),
+        ];
+        assert!(residuals.windows(2).all(|w| w[0] < w[1]), "{residuals:?}");
+        assert!(residuals[4] > 1e-6);
+
+        // The known 2-norm condition numbers
+        for (found, known) in [
+            (condition::<2>(), 19.28),
+            (condition::<4>(), 1.551e4),
+            (condition::<6>(), 1.495e7),
+            (condition::<8>(), 1.526e10),
+        ] {
+            assert!((found / known - 1.0).abs() < 1e-3, "{found:e} != {known:e}");
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod fft;
+pub mod hadamard;
+pub mod hilbert;
+pub mod polynomial;
+pub mod vandermonde;
@@ -0,0 +1,340 @@
+use std::ops::{Add, Mul, Neg};
+
+use crate::chapters::c1::definitions::{
+    AddInverse, Associative, Commutative, Complex, Distributive, Identity, Inverse, MulIdent,
+    MulScalar, One, Zero,
+};
+
+// a_0 + a_1 x + a_2 x^2 + ..., coefficients stored lowest degree first.
+// Trailing zero coefficients are always removed, so equal polynomials have equal coefficients
+// and the zero polynomial has no coefficients at all.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Polynomial<T> {
+    coeffs: Vec<T>,
+}
+
+impl<T> Polynomial<T>
+where
+    T: Zero + PartialEq,
+{
+    pub fn new(mut coeffs: Vec<T>) -> Self {
+        while coeffs.last().is_some_and(|c| *c == T::ZERO) {
+            coeffs.pop();
+        }
+        Self { coeffs }
+    }
+
+    // The constant polynomial 1, One needs a constant and a Vec can't be built in one
+    pub fn one() -> Self
+    where
+        T: One,
+    {
+        Self::new(vec![T::ONE])
+    }
+
+    pub fn coeffs(&self) -> &[T] {
+        &self.coeffs
+    }
+
+    // None for the zero polynomial, which has degree -infinity
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    // Horner's rule, x can be any type the coefficients convert into,
+    // so a real polynomial can be evaluated at a complex number
+    pub fn eval<X>(&self, x: X) -> X
+    where
+        T: Copy,
+        X: From<T> + Add<Output = X> + Mul<Output = X> + Zero + Copy,
+    {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(X::ZERO, |acc, &c| acc * x + X::from(c))
+    }
+}
+
+// Closed form roots up to degree 3, counted with multiplicity. None for the zero polynomial,
+// which vanishes everywhere, and for degree 4 and up.
+impl Polynomial<Complex<f64>> {
+    pub fn roots(&self) -> Option<Vec<Complex<f64>>> {
+        match *self.coeffs.as_slice() {
+            [] => None,
+            [_] => Some(Vec::new()),
+            [b, a] => Some(vec![-b / a]),
+            [c, b, a] => Some(quadratic_roots(a, b, c).to_vec()),
+            [d, c, b, a] => Some(cubic_roots(b / a, c / a, d / a).to_vec()),
+            _ => None,
+        }
+    }
+}
+
+// Every real polynomial has complex roots, (fundamental theorem of algebra, 4.12 in the book)
+impl Polynomial<f64> {
+    pub fn roots(&self) -> Option<Vec<Complex<f64>>> {
+        Polynomial::new(self.coeffs.iter().map(|&c| Complex::from(c)).collect()).roots()
+    }
+}
+
+// a x^2 + b x + c with a != 0. Computes the root that doesn't suffer from cancellation
+// in -b +- sqrt(b^2 - 4ac) and gets the other one from the product of the roots, c / a.
+fn quadratic_roots(a: Complex<f64>, b: Complex<f64>, c: Complex<f64>) -> [Complex<f64>; 2] {
+    let d = (b * b - a * c * 4.0).sqrt();
+    let q = if (b + d).abs() >= (b - d).abs() {
+        -(b + d) / 2.0
+    } else {
+        -(b - d) / 2.0
+    };
+    if q == Complex::ZERO {
+        // b = d = 0, so c = 0 as well
+        [Complex::ZERO; 2]
+    } else {
+        [q / a, c / q]
+    }
+}
+
+// x^3 + a x^2 + b x + c with Cardano's formula. Substituting x = t - a/3 gives t^3 + p t + q,
+// with roots t = u + v where u^3 and v^3 are the roots of z^2 + q z - p^3/27 and uv = -p/3.
+fn cubic_roots(a: Complex<f64>, b: Complex<f64>, c: Complex<f64>) -> [Complex<f64>; 3] {
+    let shift = a / 3.0;
+    let p = b - a * shift;
+    let q = shift * shift * shift * 2.0 - shift * b + c;
+
+    // The larger of the two roots of z^2 + q z - p^3/27, so that u = 0 only if p = q = 0
+    let w = (q * q / 4.0 + p * p * p / 27.0).sqrt();
+    let (z1, z2) = (-q / 2.0 + w, -q / 2.0 - w);
+    let z = if z1.abs() >= z2.abs() { z1 } else { z2 };
+    if z == Complex::ZERO {
+        return [-shift; 3];
+    }
+
+    // The three cube roots of z, omega^k u
+    let u = z.powf(1.0 / 3.0);
+    let omega = Complex::from_polar(1.0, 2.0 * std::f64::consts::PI / 3.0);
+    let us = [u, u * omega, u * omega * omega];
+    us.map(|u| u - p / (u * 3.0) - shift)
+}
+
+impl<T> Add for Polynomial<T>
+where
+    T: Add<Output = T> + Zero + PartialEq + Copy,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let (mut long, short) = if self.coeffs.len() >= rhs.coeffs.len() {
+            (self.coeffs, rhs.coeffs)
+        } else {
+            (rhs.coeffs, self.coeffs)
+        };
+        for (a, b) in long.iter_mut().zip(short) {
+            *a = *a + b;
+        }
+        Self::new(long)
+    }
+}
+
+impl<T> Neg for Polynomial<T>
+where
+    T: Neg<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            coeffs: self.coeffs.into_iter().map(|c| -c).collect(),
+        }
+    }
+}
+
+impl<T> AddInverse for Polynomial<T>
+where
+    T: AddInverse + Copy,
+{
+    fn negate(self) -> Self {
+        Self {
+            coeffs: self.coeffs.into_iter().map(|c| c.negate()).collect(),
+        }
+    }
+}
+
+// (sum a_i x^i)(sum b_j x^j) = sum_k (sum_{i+j=k} a_i b_j) x^k
+impl<T> Mul for Polynomial<T>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + PartialEq + Copy,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.coeffs.is_empty() || rhs.coeffs.is_empty() {
+            return Self::ZERO;
+        }
+
+        let mut coeffs = vec![T::ZERO; self.coeffs.len() + rhs.coeffs.len() - 1];
+        for (i, &a) in self.coeffs.iter().enumerate() {
+            for (j, &b) in rhs.coeffs.iter().enumerate() {
+                coeffs[i + j] = coeffs[i + j] + a * b;
+            }
+        }
+        Self::new(coeffs)
+    }
+}
+
+impl<T> MulScalar<T> for Polynomial<T>
+where
+    T: Mul<Output = T> + Zero + PartialEq + Copy,
+{
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.coeffs.into_iter().map(|c| c * rhs).collect())
+    }
+}
+
+impl<T> Zero for Polynomial<T> {
+    const ZERO: Self = Self { coeffs: Vec::new() };
+}
+
+impl<T> Commutative for Polynomial<T> where T: Commutative + Zero + PartialEq + Copy {}
+impl<T> Associative for Polynomial<T> where T: Associative + Zero + PartialEq + Copy {}
+impl<T> Identity for Polynomial<T> where T: Identity + PartialEq + Copy {}
+impl<T> Inverse for Polynomial<T> where T: Inverse + Zero + PartialEq + Copy {}
+impl<T> MulIdent<T> for Polynomial<T>
+where
+    T: One,
+    Self: MulScalar<T>,
+{
+}
+impl<T, X> Distributive<X> for Polynomial<T> where Self: MulScalar<X> {}
+
+#[cfg(test)]
+mod test {
+    use super::Polynomial;
+    use crate::chapters::c1::definitions::{Complex, MulScalar, Subtract, VectorSpace, Zero};
+
+    fn accept_field<T: VectorSpace<X>, X>() {}
+
+    #[test]
+    fn vector_space() {
+        accept_field::<Polynomial<f64>, f64>();
+        accept_field::<Polynomial<Complex<f64>>, Complex<f64>>();
+    }
+
+    #[test]
+    fn normalized() {
+        let p = Polynomial::new(vec![1.0, 2.0, 0.0, 0.0]);
+        assert_eq!(p.coeffs(), &[1.0, 2.0]);
+        assert_eq!(p.degree(), Some(1));
+        assert_eq!(Polynomial::new(vec![0.0; 3]), Polynomial::ZERO);
+        assert_eq!(Polynomial::<f64>::ZERO.degree(), None);
+        assert_eq!(Polynomial::<f64>::one().degree(), Some(0));
+    }
+
+    #[test]
+    fn add_and_subtract() {
+        let p = Polynomial::new(vec![1.0, 2.0, 3.0]);
+        let q = Polynomial::new(vec![-1.0, 0.5]);
+        assert_eq!(p.clone() + q.clone(), Polynomial::new(vec![0.0, 2.5, 3.0]));
+        assert_eq!(q.clone() + p.clone(), Polynomial::new(vec![0.0, 2.5, 3.0]));
+
+        // The leading terms cancel, the degree drops
+        let r = Polynomial::new(vec![0.0, 0.0, -3.0]);
+        assert_eq!((p.clone() + r).degree(), Some(1));
+        assert_eq!(p.clone().subtract(p.clone()), Polynomial::ZERO);
+        assert_eq!(-q.clone() + q, Polynomial::ZERO);
+    }
+
+    #[test]
+    fn multiply() {
+        // (1 + x)(1 - x) = 1 - x^2
+        let p = Polynomial::new(vec![1.0, 1.0]);
+        let q = Polynomial::new(vec![1.0, -1.0]);
+        assert_eq!(p.clone() * q, Polynomial::new(vec![1.0, 0.0, -1.0]));
+        assert_eq!(p.clone() * Polynomial::one(), p);
+        assert_eq!(p.clone() * Polynomial::ZERO, Polynomial::ZERO);
+        assert_eq!(
+            MulScalar::mul(p.clone(), 2.0),
+            Polynomial::new(vec![2.0, 2.0])
+        );
+        assert_eq!(MulScalar::mul(p, 0.0), Polynomial::ZERO);
+    }
+
+    #[test]
+    fn horner() {
+        // 2 - 3x + x^3
+        let p = Polynomial::new(vec![2.0, -3.0, 0.0, 1.0]);
+        assert_eq!(p.eval(0.0), 2.0);
+        assert_eq!(p.eval(1.0), 0.0);
+        assert_eq!(p.eval(-2.0), 0.0);
+        assert_eq!(p.eval(3.0), 20.0);
+        assert_eq!(Polynomial::<f64>::ZERO.eval(5.0), 0.0);
+    }
+
+    #[test]
+    fn complex_roots() {
+        // x^2 - 2x + 5 has roots 1 +- 2i
+        let p = Polynomial::new(vec![5.0, -2.0, 1.0]);
+        for root in [Complex::new(1.0, 2.0), Complex::new(1.0, -2.0)] {
+            let value = p.eval(root);
+            assert!(value.abs() < 1e-12, "{value}");
+        }
+
+        // (x - i)(x + i) = x^2 + 1
+        let i = Complex::new(0.0, 1.0);
+        let one = Complex::from(1.0);
+        let product = Polynomial::new(vec![-i, one]) * Polynomial::new(vec![i, one]);
+        assert_eq!(product, Polynomial::new(vec![one, Complex::ZERO, one]));
+        assert_eq!(product.eval(i), Complex::ZERO);
+    }
+
+    // Sorts by real then imaginary part so roots can be compared with expectations
+    fn sorted(mut roots: Vec<Complex<f64>>) -> Vec<Complex<f64>> {
+        roots.sort_by(|z, w| z.re().total_cmp(w.re()).then(z.im().total_cmp(w.im())));
+        roots
+    }
+
+    fn assert_roots(p: &Polynomial<f64>, expected: &[Complex<f64>]) {
+        let roots = sorted(p.roots().unwrap());
+        assert_eq!(roots.len(), expected.len());
+        for (root, expected) in roots.iter().zip(expected) {
+            assert!((*root - *expected).abs() < 1e-9, "{root} != {expected}");
+            assert!(p.eval(*root).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn closed_form_roots() {
+        let re = |x: f64| Complex::from(x);
+        assert_eq!(Polynomial::<f64>::ZERO.roots(), None);
+        assert_eq!(Polynomial::new(vec![3.0]).roots(), Some(Vec::new()));
+        assert_roots(&Polynomial::new(vec![3.0, 2.0]), &[re(-1.5)]);
+        assert_roots(
+            &Polynomial::new(vec![5.0, -2.0, 1.0]),
+            &[Complex::new(1.0, -2.0), Complex::new(1.0, 2.0)],
+        );
+        assert_roots(&Polynomial::new(vec![1.0, -2.0, 1.0]), &[re(1.0), re(1.0)]);
+
+        // (x - 1)(x - 2)(x + 3), x^3 - 1 and x^3 with a triple root
+        assert_roots(
+            &Polynomial::new(vec![6.0, -7.0, 0.0, 1.0]),
+            &[re(-3.0), re(1.0), re(2.0)],
+        );
+        let omega = Complex::from_polar(1.0, 2.0 * std::f64::consts::PI / 3.0);
+        assert_roots(
+            &Polynomial::new(vec![-1.0, 0.0, 0.0, 1.0]),
+            &[omega.conjugate(), omega, re(1.0)],
+        );
+        assert_roots(&Polynomial::new(vec![0.0, 0.0, 0.0, 2.0]), &[re(0.0); 3]);
+
+        assert_eq!(Polynomial::new(vec![1.0; 5]).roots(), None);
+    }
+
+    #[test]
+    fn complex_coefficients() {
+        // (x - i)(x - 2) = x^2 - (2 + i) x + 2i
+        let i = Complex::new(0.0, 1.0);
+        let p = Polynomial::new(vec![i * 2.0, -Complex::new(2.0, 1.0), Complex::from(1.0)]);
+        let roots = sorted(p.roots().unwrap());
+        assert!((roots[0] - i).abs() < 1e-12);
+        assert!((roots[1] - Complex::from(2.0)).abs() < 1e-12);
+    }
+}
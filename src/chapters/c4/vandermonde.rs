@@ -0,0 +1,203 @@
+use std::ops::{Add, Mul};
+
+use crate::chapters::c1::definitions::{Divide, List, One, Subtract};
+use crate::chapters::c3::matrix::Matrix;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VandermondeError {
+    // points[i] == points[j], the Vandermonde matrix is singular
+    DuplicatePoints(usize, usize),
+}
+
+impl<T, const N: usize> Matrix<T, N, N>
+where
+    T: Mul<Output = T> + One + Copy,
+{
+    // The Vandermonde matrix of the points, row i is (1, x_i, x_i^2, ..., x_i^(N - 1)).
+    // Multiplying it with the coefficients of a polynomial evaluates it at every point.
+    pub fn vandermonde(points: &List<T, N>) -> Self {
+        let mut powers = [T::ONE; N];
+        Matrix::from_rows(core::array::from_fn(|i| {
+            List::from_fn(|j| {
+                if j > 0 {
+                    powers[i] = powers[i] * points[i];
+                }
+                powers[i]
+            })
+        }))
+    }
+}
+
+// Solves V a = values for the Vandermonde matrix V with rows (1, x_i, x_i^2, ...), i.e. finds the
+// coefficients a (lowest degree first) of the polynomial of degree < N through (x_i, values_i).
+// Uses the Bjorck-Pereyra algorithm, O(N^2) and much more accurate than a general LU on these
+// notoriously ill conditioned matrices: first Newton divided differences, then the Newton form
+// is expanded into the monomial basis.
+pub fn solve_vandermonde<T, const N: usize>(
+    points: &List<T, N>,
+    values: &List<T, N>,
+) -> Result<List<T, N>, VandermondeError>
+where
+    T: Add<Output = T> + Mul<Output = T> + Subtract + Divide + PartialEq + Copy,
+{
+    for i in 0..N {
+        for j in i + 1..N {
+            if points[i] == points[j] {
+                return Err(VandermondeError::DuplicatePoints(i, j));
+            }
+        }
+    }
+
+    let x = |i: usize| points[i];
+    let mut a: [T; N] = core::array::from_fn(|i| values[i]);
+
+    for k in 0..N.saturating_sub(1) {
+        for i in (k + 1..N).rev() {
+            a[i] = a[i].subtract(a[i - 1]).divide(x(i).subtract(x(i - k - 1)));
+        }
+    }
+
+    for k in (0..N.saturating_sub(1)).rev() {
+        for i in k..N - 1 {
+            a[i] = a[i].subtract(a[i + 1] * x(k));
+        }
+    }
+
+    Ok(List::from_fn(|i| a[i]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{VandermondeError, solve_vandermonde};
+    use crate::chapters::c1::definitions::List;
+    use crate::chapters::c1::fixed::Fixed32;
+    use crate::chapters::c1::rational::Rational;
+    use crate::chapters::c3::matrix::Matrix;
+    use crate::chapters::c3::solve::solve;
+
+    fn eval(coeffs: &[f64], x: f64) -> f64 {
+        coeffs.iter().rev().fold(0.0, |acc, &c| acc * x + c)
+    }
+
+    #[test]
+    fn recovers_coefficients() {
+        // 2 - 3x + 0.5x^3 at small integer points, every step is exact in f64
+        let coeffs = [2.0, -3.0, 0.0, 0.5];
+        let points = List::new([-1.0, 0.0, 1.0, 2.0]);
+        let values = List::from_fn(|i| eval(&coeffs, points[i]));
+
+        let a = solve_vandermonde(&points, &values).unwrap();
+        for i in 0..4 {
+            assert_eq!(a[i], coeffs[i]);
+        }
+    }
+
+    #[test]
+    fn ill_conditioned() {
+        // Equispaced points on [0, 1] make V badly conditioned (cond ~ 1e9 for n = 12),
+        // the structured solve still reproduces the data
+        let coeffs: [f64; 12] = core::array::from_fn(|i| (i as f64 * 0.7).cos());
+        let points = List::<f64, 12>::from_fn(|i| i as f64 / 11.0);
+        let values = List::from_fn(|i| eval(&coeffs, points[i]));
+
+        let a = solve_vandermonde(&points, &values).unwrap();
+        let a: Vec<f64> = (0..12).map(|i| a[i]).collect();
+        for i in 0..12 {
+            assert!((eval(&a, points[i]) - values[i]).abs() < 1e-12);
+            assert!((a[i] - coeffs[i]).abs() < 1e-4, "{} vs {}", a[i], coeffs[i]);
+        }
+    }
+
+    #[test]
+    fn matrix() {
+        let v = Matrix::vandermonde(&List::new([2.0, -1.0, 3.0]));
+        assert_eq!(
+            v,
+            Matrix::new([[1.0, 2.0, 4.0], [1.0, -1.0, 1.0], [1.0, 3.0, 9.0]])
+        );
+
+        // V a evaluates the polynomial with coefficients a at the points
+        let coeffs = [2.0, -3.0, 0.5];
+        let values = v * List::new(coeffs);
+        for (i, x) in [2.0, -1.0, 3.0].into_iter().enumerate() {
+            assert_eq!(values[i], eval(&coeffs, x));
+        }
+    }
+
+    #[test]
+    fn determinant() {
+        // det V = product over i < j of (x_j - x_i)
+        let points = List::new([3, -1, 4, 1, -5].map(|n| Rational::new(n, 2)));
+        let mut expected = Rational::from(1);
+        for j in 0..5 {
+            for i in 0..j {
+                expected = expected * (points[j] - points[i]);
+            }
+        }
+        assert_eq!(Matrix::vandermonde(&points).determinant(), expected);
+
+        let points = List::new([1.0, 2.0, 2.0]);
+        assert_eq!(Matrix::vandermonde(&points).determinant(), 0.0);
+    }
+
+    #[test]
+    fn exact_in_rationals() {
+        // The same answer as elimination, with no rounding anywhere
+        let points = List::<Rational, 6>::from_fn(|i| Rational::new(i as i64 * 2 - 5, 3));
+        let values = List::from_fn(|i| Rational::new((i * i) as i64 - 7, i as i64 + 1));
+        let a = solve_vandermonde(&points, &values).unwrap();
+        assert_eq!(Ok(a), solve(Matrix::vandermonde(&points), values));
+        assert_eq!(Matrix::vandermonde(&points) * a, values);
+    }
+
+    #[test]
+    fn beats_elimination() {
+        // For increasing positive points and alternating data every component comes out with
+        // a relative error of a few ulps, while LU loses digits to the condition number
+        const N: usize = 10;
+        let points = List::<f64, N>::from_fn(|i| (i + 1) as f64 / N as f64);
+        let values = List::from_fn(|i| if i % 2 == 0 { 1.0 } else { -1.0 });
+        let exact = solve_vandermonde(
+            &List::<_, N>::from_fn(|i| Rational::new(i as i64 + 1, N as i64)),
+            &List::from_fn(|i| Rational::from(if i % 2 == 0 { 1 } else { -1 })),
+        )
+        .unwrap();
+        let relative_error = |a: List<f64, N>| {
+            (0..N)
+                .map(|i| (a[i] / exact[i].to_f64() - 1.0).abs())
+                .fold(0.0, f64::max)
+        };
+
+        let structured = solve_vandermonde(&points, &values).unwrap();
+        let elimination = solve(Matrix::vandermonde(&points), values).unwrap();
+        assert!(relative_error(structured) < 1e-14);
+        assert!(relative_error(elimination) > 1e-12);
+    }
+
+    #[test]
+    fn exact_in_fixed_point() {
+        // 1 + x^2 through x = 0, 1, 2
+        let points = List::new([0, 1, 2].map(Fixed32::from_int));
+        let values = List::new([1, 2, 5].map(Fixed32::from_int));
+        let a = solve_vandermonde(&points, &values).unwrap();
+        assert_eq!(a[0], Fixed32::from_int(1));
+        assert_eq!(a[1], Fixed32::from_int(0));
+        assert_eq!(a[2], Fixed32::from_int(1));
+    }
+
+    #[test]
+    fn small_sizes() {
+        let a = solve_vandermonde(&List::new([3.0]), &List::new([7.0])).unwrap();
+        assert_eq!(a[0], 7.0);
+        assert!(solve_vandermonde::<f64, 0>(&List::new([]), &List::new([])).is_ok());
+    }
+
+    #[test]
+    fn duplicate_points() {
+        let points = List::new([1.0, 2.0, 3.0, 2.0]);
+        assert_eq!(
+            solve_vandermonde(&points, &List::from_fn(|i| i as f64)).err(),
+            Some(VandermondeError::DuplicatePoints(1, 3))
+        );
+    }
+}
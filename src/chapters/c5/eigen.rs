@@ -0,0 +1,222 @@
+use crate::chapters::c1::definitions::{Complex, List};
+use crate::chapters::c2::span::null_space;
+use crate::chapters::c3::matrix::Matrix;
+use crate::chapters::c4::polynomial::Polynomial;
+
+// Every operator on a finite dimensional complex vector space has an eigenvalue (5.19 in the
+// book), the roots of the characteristic polynomial det(A - x I). For these small sizes its
+// coefficients are known in closed form, so the eigenvalues follow from Polynomial::roots.
+// Eigenvalues are returned with multiplicity, real matrices can have conjugate pairs.
+
+// x^2 - tr(A) x + det(A)
+fn eigenvalues_2x2(a: Matrix<Complex<f64>, 2, 2>) -> [Complex<f64>; 2] {
+    let trace = a[(0, 0)] + a[(1, 1)];
+    let det = a[(0, 0)] * a[(1, 1)] - a[(0, 1)] * a[(1, 0)];
+    let roots = Polynomial::new(vec![det, -trace, Complex::from(1.0)]).roots();
+    roots.unwrap().try_into().unwrap()
+}
+
+// x^3 - tr(A) x^2 + m x - det(A), where m is the sum of the principal 2 x 2 minors
+fn eigenvalues_3x3(a: Matrix<Complex<f64>, 3, 3>) -> [Complex<f64>; 3] {
+    let minor = |i: usize, j: usize| a[(i, i)] * a[(j, j)] - a[(i, j)] * a[(j, i)];
+    let trace = a[(0, 0)] + a[(1, 1)] + a[(2, 2)];
+    let m = minor(0, 1) + minor(0, 2) + minor(1, 2);
+    let det = a[(0, 0)] * minor(1, 2) - a[(0, 1)] * (a[(1, 0)] * a[(2, 2)] - a[(1, 2)] * a[(2, 0)])
+        + a[(0, 2)] * (a[(1, 0)] * a[(2, 1)] - a[(1, 1)] * a[(2, 0)]);
+    let roots = Polynomial::new(vec![-det, m, -trace, Complex::from(1.0)]).roots();
+    roots.unwrap().try_into().unwrap()
+}
+
+fn complexify<const N: usize>(a: &Matrix<f64, N, N>) -> Matrix<Complex<f64>, N, N> {
+    Matrix::from_fn(|i, j| Complex::from(a[(i, j)]))
+}
+
+// A basis of null(A - lambda I), empty when lambda is not an eigenvalue.
+// Entries up to eps count as zero while reducing the rows of A - lambda I.
+fn eigenspace<const N: usize>(
+    a: &Matrix<Complex<f64>, N, N>,
+    lambda: Complex<f64>,
+    eps: f64,
+) -> Vec<List<Complex<f64>, N>> {
+    let rows: [List<_, N>; N] = core::array::from_fn(|i| {
+        List::from_fn(|j| {
+            if i == j {
+                a[(i, j)] - lambda
+            } else {
+                a[(i, j)]
+            }
+        })
+    });
+    null_space(&rows, eps)
+}
+
+// The first basis vector of the eigenspace, scaled to length 1
+fn eigenvector<const N: usize>(
+    a: &Matrix<Complex<f64>, N, N>,
+    lambda: Complex<f64>,
+    eps: f64,
+) -> Option<List<Complex<f64>, N>> {
+    let v = *eigenspace(a, lambda, eps).first()?;
+    let norm = v.iter().map(|x| x.norm_squared()).sum::<f64>().sqrt();
+    Some(v / Complex::from(norm))
+}
+
+impl Matrix<f64, 2, 2> {
+    pub fn eigenvalues(&self) -> [Complex<f64>; 2] {
+        eigenvalues_2x2(complexify(self))
+    }
+}
+
+impl Matrix<f64, 3, 3> {
+    pub fn eigenvalues(&self) -> [Complex<f64>; 3] {
+        eigenvalues_3x3(complexify(self))
+    }
+}
+
+impl Matrix<Complex<f64>, 2, 2> {
+    pub fn eigenvalues(&self) -> [Complex<f64>; 2] {
+        eigenvalues_2x2(*self)
+    }
+}
+
+impl<const N: usize> Matrix<f64, N, N> {
+    pub fn eigenspace(&self, lambda: Complex<f64>, eps: f64) -> Vec<List<Complex<f64>, N>> {
+        eigenspace(&complexify(self), lambda, eps)
+    }
+
+    pub fn eigenvector_for(&self, lambda: Complex<f64>, eps: f64) -> Option<List<Complex<f64>, N>> {
+        eigenvector(&complexify(self), lambda, eps)
+    }
+}
+
+impl<const N: usize> Matrix<Complex<f64>, N, N> {
+    pub fn eigenspace(&self, lambda: Complex<f64>, eps: f64) -> Vec<List<Complex<f64>, N>> {
+        eigenspace(self, lambda, eps)
+    }
+
+    pub fn eigenvector_for(&self, lambda: Complex<f64>, eps: f64) -> Option<List<Complex<f64>, N>> {
+        eigenvector(self, lambda, eps)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::complexify;
+    use crate::chapters::c1::definitions::{Complex, List};
+    use crate::chapters::c3::matrix::Matrix;
+
+    const EPS: f64 = 1e-9;
+
+    fn assert_close(z: Complex<f64>, w: Complex<f64>) {
+        assert!((z - w).abs() < EPS, "{z} != {w}");
+    }
+
+    // The eigenvalues come in no particular order
+    fn sorted<const N: usize>(mut eigenvalues: [Complex<f64>; N]) -> [Complex<f64>; N] {
+        eigenvalues.sort_by(|z, w| z.re().total_cmp(w.re()).then(z.im().total_cmp(w.im())));
+        eigenvalues
+    }
+
+    // A v = lambda v for every eigenvalue, with v a unit vector
+    fn assert_eigenpairs<const N: usize>(
+        a: &Matrix<Complex<f64>, N, N>,
+        eigenvalues: &[Complex<f64>],
+    ) {
+        for &lambda in eigenvalues {
+            let v = a.eigenvector_for(lambda, EPS).unwrap();
+            let norm = v.iter().map(|x| x.norm_squared()).sum::<f64>();
+            assert!((norm - 1.0).abs() < EPS);
+
+            let av = *a * v;
+            for i in 0..N {
+                assert_close(av[i], lambda * v[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn symmetric() {
+        // Eigenvalues 1 and 3 with eigenvectors (1, -1) and (1, 1)
+        let a = Matrix::new([[2.0, 1.0], [1.0, 2.0]]);
+        let [small, large] = sorted(a.eigenvalues());
+        assert_close(small, Complex::from(1.0));
+        assert_close(large, Complex::from(3.0));
+        assert_eigenpairs(&complexify(&a), &[small, large]);
+
+        let v = a.eigenvector_for(large, EPS).unwrap();
+        assert_close(v[0], v[1]);
+        assert_eq!(a.eigenvector_for(Complex::from(2.0), EPS), None);
+    }
+
+    #[test]
+    fn rotation() {
+        // A quarter turn fixes no direction in R^2, over C it has eigenvalues -i and i
+        let theta = std::f64::consts::FRAC_PI_2;
+        let a = Matrix::new([[theta.cos(), -theta.sin()], [theta.sin(), theta.cos()]]);
+        let [minus_i, plus_i] = sorted(a.eigenvalues());
+        assert_close(minus_i, Complex::new(0.0, -1.0));
+        assert_close(plus_i, Complex::new(0.0, 1.0));
+        assert_eq!(minus_i, plus_i.conjugate());
+        assert_eigenpairs(&complexify(&a), &[minus_i, plus_i]);
+    }
+
+    #[test]
+    fn defective() {
+        // 2 is a double root of the characteristic polynomial, but its eigenspace is only 1-dimensional
+        let a = Matrix::new([[2.0, 1.0], [0.0, 2.0]]);
+        assert_eq!(a.eigenvalues(), [Complex::from(2.0); 2]);
+
+        let v = a.eigenvector_for(Complex::from(2.0), EPS).unwrap();
+        assert_eq!(v, List::new([Complex::from(1.0), Complex::from(0.0)]));
+        assert_eq!(a.eigenspace(Complex::from(2.0), EPS), vec![v]);
+
+        // 2I has the same characteristic polynomial, but all of C^2 as eigenspace
+        let scaled = Matrix::new([[2.0, 0.0], [0.0, 2.0]]);
+        assert_eq!(scaled.eigenspace(Complex::from(2.0), EPS).len(), 2);
+    }
+
+    #[test]
+    fn three_by_three() {
+        // Upper triangular, the eigenvalues are on the diagonal
+        let a = Matrix::new([[1.0, 2.0, 3.0], [0.0, -2.0, 1.0], [0.0, 0.0, 4.0]]);
+        let eigenvalues = sorted(a.eigenvalues());
+        for (lambda, expected) in eigenvalues.iter().zip([-2.0, 1.0, 4.0]) {
+            assert_close(*lambda, Complex::from(expected));
+        }
+        assert_eigenpairs(&complexify(&a), &eigenvalues);
+
+        // A rotation about the z axis has eigenvalues 1 and e^(+-i theta)
+        let (s, c) = 0.7f64.sin_cos();
+        let r = Matrix::new([[c, -s, 0.0], [s, c, 0.0], [0.0, 0.0, 1.0]]);
+        let eigenvalues = r.eigenvalues();
+        for expected in [
+            Complex::from(1.0),
+            Complex::from_polar(1.0, 0.7),
+            Complex::from_polar(1.0, -0.7),
+        ] {
+            assert!(
+                eigenvalues.iter().any(|z| (*z - expected).abs() < EPS),
+                "{expected}"
+            );
+        }
+        assert_eigenpairs(&complexify(&r), &eigenvalues);
+    }
+
+    #[test]
+    fn complex_entries() {
+        // Eigenvalues i and 2, upper triangular again
+        let i = Complex::new(0.0, 1.0);
+        let a = Matrix::new([
+            [i, Complex::from(1.0)],
+            [Complex::from(0.0), Complex::from(2.0)],
+        ]);
+        let eigenvalues = a.eigenvalues();
+        assert!(eigenvalues.iter().any(|z| (*z - i).abs() < EPS));
+        assert!(
+            eigenvalues
+                .iter()
+                .any(|z| (*z - Complex::from(2.0)).abs() < EPS)
+        );
+        assert_eigenpairs(&a, &eigenvalues);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::chapters::c3::matrix::Matrix;
+
+impl<const N: usize> Matrix<f64, N, N> {
+    // (H, Q) with A = Q H Q^T, Q orthogonal and H zero below the first subdiagonal.
+    // Step k reflects rows and columns k + 1.. so that column k ends at row k + 1. Working from
+    // both sides keeps the eigenvalues, and a symmetric A stays symmetric, so its H is
+    // tridiagonal. This is where eigenvalue iterations start, a QR step on H costs O(N^2).
+    pub fn hessenberg(&self) -> (Matrix<f64, N, N>, Matrix<f64, N, N>) {
+        let mut h = *self;
+        let mut q = Matrix::identity();
+        for k in 0..N.saturating_sub(2) {
+            let norm = (k + 1..N).fold(0.0, |acc: f64, i| acc.hypot(h[(i, k)]));
+            if norm == 0.0 {
+                continue;
+            }
+
+            // v = x - alpha e_{k+1} with alpha having the sign opposite to x_{k+1},
+            // the reflector is I - 2 v v^T / v^T v
+            let x0 = h[(k + 1, k)];
+            let alpha = if x0 > 0.0 { -norm } else { norm };
+            let mut v = [0.0; N];
+            for i in k + 1..N {
+                v[i] = h[(i, k)];
+            }
+            v[k + 1] -= alpha;
+            let vv: f64 = v.iter().map(|x| x * x).sum();
+
+            // h <- P h, then h <- h P and q <- q P
+            for j in 0..N {
+                let w = 2.0 * (k + 1..N).map(|i| v[i] * h[(i, j)]).sum::<f64>() / vv;
+                for i in k + 1..N {
+                    h[(i, j)] -= w * v[i];
+                }
+            }
+            for m in [&mut h, &mut q] {
+                for i in 0..N {
+                    let w = 2.0 * (k + 1..N).map(|j| m[(i, j)] * v[j]).sum::<f64>() / vv;
+                    for j in k + 1..N {
+                        m[(i, j)] -= w * v[j];
+                    }
+                }
+            }
+
+            // What's left below the subdiagonal is rounding error
+            h[(k + 1, k)] = alpha;
+            for i in k + 2..N {
+                h[(i, k)] = 0.0;
+            }
+        }
+        (h, q)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::chapters::c1::definitions::Complex;
+    use crate::chapters::c3::matrix::Matrix;
+
+    fn max_difference<const N: usize>(a: &Matrix<f64, N, N>, b: &Matrix<f64, N, N>) -> f64 {
+        let mut difference: f64 = 0.0;
+        for i in 0..N {
+            for j in 0..N {
+                difference = difference.max((a[(i, j)] - b[(i, j)]).abs());
+            }
+        }
+        difference
+    }
+
+    fn sample() -> Matrix<f64, 5, 5> {
+        Matrix::new([
+            [4.0, -1.0, 2.0, 0.5, 1.0],
+            [1.0, 3.0, -2.0, 1.0, 0.0],
+            [0.5, 2.0, 1.0, -1.0, 3.0],
+            [-2.0, 0.0, 1.5, 2.0, 1.0],
+            [1.0, 1.0, 0.0, -0.5, 5.0],
+        ])
+    }
+
+    #[test]
+    fn reconstructs() {
+        let a = sample();
+        let (h, q) = a.hessenberg();
+        assert!(max_difference(&(q * h * q.transpose()), &a) < 1e-13);
+        assert!(max_difference(&(q.transpose() * q), &Matrix::identity()) < 1e-15);
+        for i in 2..5 {
+            for j in 0..i - 1 {
+                assert_eq!(h[(i, j)], 0.0);
+            }
+        }
+        // The first row and column are left alone
+        assert_eq!(q[(0, 0)], 1.0);
+        assert_eq!(h[(0, 0)], a[(0, 0)]);
+    }
+
+    #[test]
+    fn symmetric_becomes_tridiagonal() {
+        let a = sample();
+        let s = a + a.transpose();
+        let (h, q) = s.hessenberg();
+        assert!(max_difference(&(q * h * q.transpose()), &s) < 1e-13);
+        for i in 0..5 {
+            for j in i + 2..5 {
+                assert!(h[(i, j)].abs() < 1e-14);
+            }
+        }
+        assert!(max_difference(&h, &h.transpose()) < 1e-14);
+    }
+
+    #[test]
+    fn keeps_eigenvalues() {
+        let a = Matrix::new([[2.0, -1.0, 3.0], [1.0, 0.5, -2.0], [4.0, 1.0, 1.0]]);
+        let (h, _) = a.hessenberg();
+        let sorted = |mut z: [Complex<f64>; 3]| {
+            z.sort_by(|z, w| z.re().total_cmp(w.re()).then(z.im().total_cmp(w.im())));
+            z
+        };
+        let (expected, found) = (sorted(a.eigenvalues()), sorted(h.eigenvalues()));
+        for k in 0..3 {
+            assert!((expected[k] - found[k]).abs() < 1e-9);
+        }
+
+        // Sizes without anything to reduce
+        let small = Matrix::new([[1.0, 2.0], [3.0, 4.0]]);
+        assert_eq!(small.hessenberg(), (small, Matrix::identity()));
+    }
+}
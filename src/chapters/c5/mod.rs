@@ -0,0 +1,4 @@
+pub mod eigen;
+pub mod hessenberg;
+pub mod qr_algorithm;
+pub mod symmetric_eigen;
@@ -0,0 +1,255 @@
+use crate::chapters::c1::definitions::Complex;
+use crate::chapters::c3::matrix::Matrix;
+
+// The iteration ran out of steps before every eigenvalue was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoConvergence;
+
+impl<const N: usize> Matrix<f64, N, N> {
+    // All N eigenvalues with multiplicity, in no particular order, complex ones as conjugate pairs.
+    //
+    // The practical QR algorithm: reduce to Hessenberg form, then run implicit double shift
+    // (Francis) QR steps on the part of H that hasn't split off yet. The two shifts are the
+    // eigenvalues of the trailing 2 x 2 block, which are either both real or a conjugate pair,
+    // so the arithmetic stays real. A subdiagonal entry below tol times its two diagonal
+    // neighbours counts as zero, and a trailing 1 x 1 or 2 x 2 block that splits off like that
+    // gives one real eigenvalue or two. Every tenth step without progress uses an ad hoc shift
+    // instead, which breaks the cycles the standard shifts can get stuck in.
+    // Fails after max_iter QR steps.
+    pub fn eigenvalues_qr(
+        &self,
+        tol: f64,
+        max_iter: usize,
+    ) -> Result<Vec<Complex<f64>>, NoConvergence> {
+        let (mut h, _) = self.hessenberg();
+        let mut eigenvalues = Vec::with_capacity(N);
+        let mut hi = N;
+        let mut steps = 0;
+        let mut stuck = 0;
+        while hi > 0 {
+            // The active block is lo..hi, everything below has been found already
+            let mut lo = hi - 1;
+            while lo > 0 {
+                let scale = h[(lo - 1, lo - 1)].abs() + h[(lo, lo)].abs();
+                if h[(lo, lo - 1)].abs() <= tol * scale {
+                    h[(lo, lo - 1)] = 0.0;
+                    break;
+                }
+                lo -= 1;
+            }
+
+            if hi - lo == 1 {
+                eigenvalues.push(Complex::from(h[(lo, lo)]));
+                hi -= 1;
+                stuck = 0;
+                continue;
+            }
+            if hi - lo == 2 {
+                let block = Matrix::<f64, 2, 2>::from_fn(|i, j| h[(lo + i, lo + j)]);
+                eigenvalues.extend(block.eigenvalues());
+                hi -= 2;
+                stuck = 0;
+                continue;
+            }
+
+            if steps == max_iter {
+                return Err(NoConvergence);
+            }
+            steps += 1;
+            stuck += 1;
+            double_shift_step(&mut h, lo, hi, stuck % 10 == 0);
+        }
+        Ok(eigenvalues)
+    }
+}
+
+// One Francis step on rows and columns lo..hi (at least 3 of them) of the Hessenberg matrix h.
+// (H - s1 I)(H - s2 I) has a first column with only three nonzeros, the reflector for it makes
+// a bulge below the subdiagonal that the following reflectors chase down and out of the block.
+fn double_shift_step<const N: usize>(
+    h: &mut Matrix<f64, N, N>,
+    lo: usize,
+    hi: usize,
+    exceptional: bool,
+) {
+    let (a, b, c, d) = (
+        h[(hi - 2, hi - 2)],
+        h[(hi - 2, hi - 1)],
+        h[(hi - 1, hi - 2)],
+        h[(hi - 1, hi - 1)],
+    );
+    // s1 + s2 and s1 s2
+    let (sum, product) = if exceptional {
+        let e = h[(hi - 1, hi - 2)].abs() + h[(hi - 2, hi - 3)].abs();
+        (1.5 * e, e * e)
+    } else {
+        (a + d, a * d - b * c)
+    };
+
+    let (h00, h01, h10, h11, h21) = (
+        h[(lo, lo)],
+        h[(lo, lo + 1)],
+        h[(lo + 1, lo)],
+        h[(lo + 1, lo + 1)],
+        h[(lo + 2, lo + 1)],
+    );
+    let mut x = [
+        h00 * h00 + h01 * h10 - sum * h00 + product,
+        h10 * (h00 + h11 - sum),
+        h10 * h21,
+    ];
+    for k in lo..hi - 1 {
+        let m = if k + 3 <= hi { 3 } else { 2 };
+        let norm = x[..m].iter().fold(0.0, |acc: f64, &t| acc.hypot(t));
+        if norm > 0.0 {
+            let alpha = if x[0] > 0.0 { -norm } else { norm };
+            let mut v = x;
+            v[0] -= alpha;
+            let vv: f64 = v[..m].iter().map(|t| t * t).sum();
+
+            let first = if k > lo { k - 1 } else { lo };
+            for j in first..hi {
+                let w = 2.0 * (0..m).map(|i| v[i] * h[(k + i, j)]).sum::<f64>() / vv;
+                for i in 0..m {
+                    h[(k + i, j)] -= w * v[i];
+                }
+            }
+            for i in lo..(k + m + 1).min(hi) {
+                let w = 2.0 * (0..m).map(|j| h[(i, k + j)] * v[j]).sum::<f64>() / vv;
+                for j in 0..m {
+                    h[(i, k + j)] -= w * v[j];
+                }
+            }
+            // The bulge left column k - 1
+            if k > lo {
+                h[(k, k - 1)] = alpha;
+                for i in 1..m {
+                    h[(k + i, k - 1)] = 0.0;
+                }
+            }
+        }
+
+        if k + 2 < hi {
+            x = [
+                h[(k + 1, k)],
+                h[(k + 2, k)],
+                if k + 3 < hi { h[(k + 3, k)] } else { 0.0 },
+            ];
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NoConvergence;
+    use crate::chapters::c1::definitions::Complex;
+    use crate::chapters::c3::matrix::Matrix;
+
+    // The same values with multiplicity, in any order
+    fn assert_same(found: Vec<Complex<f64>>, expected: &[Complex<f64>], eps: f64) {
+        assert_eq!(found.len(), expected.len());
+        let mut left = found;
+        for &w in expected {
+            let closest = (0..left.len())
+                .min_by(|&i, &j| (left[i] - w).abs().total_cmp(&(left[j] - w).abs()))
+                .unwrap();
+            assert!((left[closest] - w).abs() < eps, "{w} missing from {left:?}");
+            left.swap_remove(closest);
+        }
+    }
+
+    // The matrix whose characteristic polynomial is prod (x - root)
+    fn companion<const N: usize>(roots: [f64; N]) -> Matrix<f64, N, N> {
+        // Coefficients of the monic polynomial, lowest first
+        let mut coeffs = vec![1.0];
+        for root in roots {
+            let mut next = vec![0.0; coeffs.len() + 1];
+            for (k, c) in coeffs.iter().enumerate() {
+                next[k + 1] += c;
+                next[k] -= root * c;
+            }
+            coeffs = next;
+        }
+        Matrix::from_fn(|i, j| {
+            if j == N - 1 {
+                -coeffs[i]
+            } else if i == j + 1 {
+                1.0
+            } else {
+                0.0
+            }
+        })
+    }
+
+    #[test]
+    fn companion_matrices() {
+        let roots = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let found = companion(roots).eigenvalues_qr(1e-14, 500).unwrap();
+        assert_same(found, &roots.map(Complex::from), 1e-8);
+
+        let roots = [-2.5, -0.5, 0.25, 1.0, 3.0, 7.0, 10.0];
+        let found = companion(roots).eigenvalues_qr(1e-14, 500).unwrap();
+        assert_same(found, &roots.map(Complex::from), 1e-8);
+    }
+
+    #[test]
+    fn complex_pairs() {
+        // Rotations by different angles with a scaling around a real eigenvalue, hidden by a
+        // change of basis
+        let (c, s) = (0.6, 0.8);
+        let d = Matrix::new([
+            [c, -s, 0.0, 0.0, 0.0],
+            [s, c, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 2.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0, -3.0],
+            [0.0, 0.0, 0.0, 3.0, 1.0],
+        ]);
+        let p =
+            Matrix::<f64, 5, 5>::from_fn(|i, j| if i <= j { 1.0 + (i + j) as f64 } else { 0.0 });
+        let a = p * d * p.invert().unwrap();
+
+        let found = a.eigenvalues_qr(1e-14, 500).unwrap();
+        let expected = [
+            Complex::new(c, -s),
+            Complex::new(c, s),
+            Complex::new(1.0, -3.0),
+            Complex::new(1.0, 3.0),
+            Complex::from(2.0),
+        ];
+        assert_same(found, &expected, 1e-9);
+    }
+
+    #[test]
+    fn trace_and_determinant() {
+        let a = Matrix::<f64, 6, 6>::from_fn(|i, j| {
+            ((3 * i + 5 * j) % 7) as f64 - 2.5 + (i == j) as i32 as f64
+        });
+        let found = a.eigenvalues_qr(1e-14, 500).unwrap();
+        let sum = found.iter().fold(Complex::from(0.0), |acc, &z| acc + z);
+        let product = found.iter().fold(Complex::from(1.0), |acc, &z| acc * z);
+        let trace: f64 = (0..6).map(|i| a[(i, i)]).sum();
+        assert!((sum - Complex::from(trace)).abs() < 1e-10);
+        assert!(
+            (product - Complex::from(a.determinant())).abs()
+                < 1e-8 * a.determinant().abs().max(1.0)
+        );
+    }
+
+    #[test]
+    fn no_convergence() {
+        // A cyclic permutation, the standard shifts leave it unchanged and only the
+        // exceptional shift gets the iteration going
+        let cycle = Matrix::<f64, 4, 4>::from_fn(|i, j| if i == (j + 1) % 4 { 1.0 } else { 0.0 });
+        assert_eq!(cycle.eigenvalues_qr(1e-14, 1), Err(NoConvergence));
+        assert_eq!(cycle.eigenvalues_qr(1e-14, 9), Err(NoConvergence));
+
+        let found = cycle.eigenvalues_qr(1e-14, 500).unwrap();
+        let expected = [
+            Complex::from(-1.0),
+            Complex::new(0.0, -1.0),
+            Complex::new(0.0, 1.0),
+            Complex::from(1.0),
+        ];
+        assert_same(found, &expected, 1e-9);
+    }
+}
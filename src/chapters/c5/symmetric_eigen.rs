@@ -0,0 +1,113 @@
+use crate::chapters::c1::definitions::List;
+use crate::chapters::c3::matrix::Matrix;
+use crate::chapters::c3::symmetric::SymmetricMatrix;
+use crate::chapters::c7::givens::Givens;
+
+const MAX_SWEEPS: usize = 100;
+
+impl<const N: usize> Matrix<f64, N, N> {
+    // Eigenvalues from largest to smallest, with the orthonormal eigenvectors as the columns of
+    // the matrix in the same order, so A = V diag(lambda) V^T. Only meaningful for symmetric A,
+    // which by the spectral theorem has real eigenvalues and an orthonormal eigenbasis.
+    //
+    // Cyclic Jacobi: every pair p < q in turn gets the rotation that zeroes a_pq on both sides.
+    // Later rotations bring some of it back, but the sum of squares off the diagonal drops by
+    // 2 a_pq^2 each time, and the convergence ends up quadratic. Slower than QR on big matrices,
+    // but the eigenvalues come out to high relative accuracy and the vectors orthogonal.
+    pub fn symmetric_eigen(&self) -> (List<f64, N>, Matrix<f64, N, N>) {
+        let mut a = *self;
+        let mut v = Matrix::<f64, N, N>::identity();
+        let total: f64 = (0..N * N).map(|k| a[(k / N, k % N)].powi(2)).sum();
+        for _ in 0..MAX_SWEEPS {
+            let off: f64 = (0..N * N)
+                .filter(|k| k / N != k % N)
+                .map(|k| a[(k / N, k % N)].powi(2))
+                .sum();
+            if off <= f64::EPSILON * f64::EPSILON * total {
+                break;
+            }
+            for p in 0..N {
+                for q in p + 1..N {
+                    if a[(p, q)] == 0.0 {
+                        continue;
+                    }
+                    // G A G^T has (c^2 - s^2) a_pq + c s (a_qq - a_pp) at (p, q), t = s / c is
+                    // the smaller root of t^2 - 2 theta t - 1 with theta = (a_qq - a_pp) / 2 a_pq
+                    let theta = (a[(q, q)] - a[(p, p)]) / (2.0 * a[(p, q)]);
+                    let t = -theta.signum() / (theta.abs() + theta.hypot(1.0));
+                    let c = 1.0 / t.hypot(1.0);
+                    let g = Givens::new(c, t * c, p, q);
+                    g.apply_left(&mut a);
+                    g.apply_right(&mut a);
+                    g.apply_right(&mut v);
+                    a[(p, q)] = 0.0;
+                    a[(q, p)] = 0.0;
+                }
+            }
+        }
+
+        let mut order: [usize; N] = core::array::from_fn(|i| i);
+        order.sort_by(|&i, &j| a[(j, j)].total_cmp(&a[(i, i)]));
+        (
+            List::from_fn(|k| a[(order[k], order[k])]),
+            Matrix::from_fn(|i, k| v[(i, order[k])]),
+        )
+    }
+}
+
+impl<const N: usize> SymmetricMatrix<f64, N> {
+    pub fn symmetric_eigen(&self) -> (List<f64, N>, Matrix<f64, N, N>) {
+        self.to_matrix().symmetric_eigen()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::chapters::c1::definitions::List;
+    use crate::chapters::c3::matrix::Matrix;
+    use crate::chapters::c3::symmetric::SymmetricMatrix;
+
+    #[test]
+    fn decomposes() {
+        let a = Matrix::new([
+            [4.0, 1.0, -2.0, 0.5],
+            [1.0, 3.0, 0.0, 1.0],
+            [-2.0, 0.0, 5.0, -1.0],
+            [0.5, 1.0, -1.0, 2.0],
+        ]);
+        let (lambda, v) = a.symmetric_eigen();
+        assert!((0..3).all(|k| lambda[k] >= lambda[k + 1]));
+        let d = Matrix::<f64, 4, 4>::from_fn(|i, j| if i == j { lambda[i] } else { 0.0 });
+        let (vdvt, vtv) = (v * d * v.transpose(), v.transpose() * v);
+        let identity = Matrix::<f64, 4, 4>::identity();
+        for i in 0..4 {
+            for j in 0..4 {
+                assert!((vdvt[(i, j)] - a[(i, j)]).abs() < 1e-13);
+                assert!((vtv[(i, j)] - identity[(i, j)]).abs() < 1e-14);
+            }
+        }
+        let trace: f64 = (0..4).map(|i| a[(i, i)]).sum();
+        assert!((lambda.iter().sum::<f64>() - trace).abs() < 1e-13);
+
+        let packed = SymmetricMatrix::from_matrix(&a).unwrap();
+        assert_eq!(packed.symmetric_eigen(), (lambda, v));
+    }
+
+    #[test]
+    fn known_spectrum() {
+        // Eigenvalues 1 and 3 with eigenvectors (1, -1) and (1, 1)
+        let (lambda, v) = Matrix::new([[2.0, 1.0], [1.0, 2.0]]).symmetric_eigen();
+        assert!((lambda[0] - 3.0).abs() < 1e-15 && (lambda[1] - 1.0).abs() < 1e-15);
+        assert!((v[(0, 0)] - v[(1, 0)]).abs() < 1e-15);
+        assert!((v[(0, 1)] + v[(1, 1)]).abs() < 1e-15);
+
+        // Diagonal matrices need no rotations
+        let (lambda, v) =
+            Matrix::new([[1.0, 0.0, 0.0], [0.0, 3.0, 0.0], [0.0, 0.0, 2.0]]).symmetric_eigen();
+        assert_eq!(lambda, List::new([3.0, 2.0, 1.0]));
+        assert_eq!(
+            v,
+            Matrix::new([[0.0, 0.0, 1.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]])
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use std::ops::{Add, Neg};
+
+use crate::chapters::c1::definitions::{Complex, List, MulScalar};
+use crate::chapters::c1::modular::Zp;
+use crate::chapters::c1::rational::Rational;
+use crate::chapters::c3::matrix::Matrix;
+
+// |x| of a scalar, as a real number
+pub trait Modulus {
+    // Arithmetic without rounding, so a computed zero is a true zero and eliminations need no
+    // tolerance
+    const EXACT: bool = false;
+
+    fn modulus(self) -> f64;
+}
+
+impl Modulus for f64 {
+    fn modulus(self) -> f64 {
+        self.abs()
+    }
+}
+
+impl Modulus for Complex<f64> {
+    fn modulus(self) -> f64 {
+        self.abs()
+    }
+}
+
+// Only used to compare sizes, e.g. to pick pivots, so rounding to f64 is fine
+impl Modulus for Rational {
+    const EXACT: bool = true;
+
+    fn modulus(self) -> f64 {
+        self.to_f64().abs()
+    }
+}
+
+// Z/P has no sizes, elimination only needs to tell zero from the rest
+impl<const P: u32> Modulus for Zp<P> {
+    const EXACT: bool = true;
+
+    fn modulus(self) -> f64 {
+        if self.value() == 0 { 0.0 } else { 1.0 }
+    }
+}
+
+// An inner product <u, v> with values in F, linear in the first argument and
+// conjugate symmetric, <u, v> = conj(<v, u>)
+pub trait InnerProduct<F>: Copy
+where
+    F: Modulus + From<f64>,
+{
+    fn inner(&self, rhs: &Self) -> F;
+
+    // <v, v> is real and non negative, so its modulus is the value itself
+    fn norm(&self) -> f64 {
+        self.inner(self).modulus().sqrt()
+    }
+
+    // None for the zero vector
+    fn normalize(&self) -> Option<Self>
+    where
+        Self: MulScalar<F>,
+    {
+        let norm = self.norm();
+        if norm == 0.0 || !norm.is_finite() {
+            return None;
+        }
+        Some(MulScalar::mul(*self, F::from(1.0 / norm)))
+    }
+
+    fn distance(&self, rhs: &Self) -> f64
+    where
+        Self: Add<Output = Self> + Neg<Output = Self>,
+    {
+        (*self + -*rhs).norm()
+    }
+
+    fn is_orthogonal_to(&self, rhs: &Self, eps: f64) -> bool {
+        self.inner(rhs).modulus() <= eps
+    }
+}
+
+// u . v
+impl<const N: usize> InnerProduct<f64> for List<f64, N> {
+    fn inner(&self, rhs: &Self) -> f64 {
+        self.iter().zip(rhs.iter()).map(|(a, b)| a * b).sum()
+    }
+}
+
+// sum u_i conj(v_i)
+impl<const N: usize> InnerProduct<Complex<f64>> for List<Complex<f64>, N> {
+    fn inner(&self, rhs: &Self) -> Complex<f64> {
+        self.iter()
+            .zip(rhs.iter())
+            .fold(Complex::from(0.0), |acc, (a, b)| acc + *a * b.conjugate())
+    }
+}
+
+// Gram-Schmidt, each vector minus its projection on the previous ones, normalized.
+// Vectors whose remainder has a norm of at most eps are in the span of the previous
+// ones and are left out, so the result is an orthonormal basis of the span.
+pub fn orthonormalize<V, F>(vectors: &[V], eps: f64) -> Vec<V>
+where
+    V: InnerProduct<F> + MulScalar<F> + Add<Output = V> + Neg<Output = V>,
+    F: Modulus + From<f64>,
+{
+    let mut basis: Vec<V> = Vec::new();
+    for v in vectors {
+        let mut w = *v;
+        for e in &basis {
+            w = w + -MulScalar::mul(*e, w.inner(e));
+        }
+        if w.norm() > eps {
+            basis.extend(w.normalize());
+        }
+    }
+    basis
+}
+
+impl<const R: usize, const C: usize> Matrix<Complex<f64>, R, C> {
+    // A*, the adjoint for the standard inner product
+    pub fn conjugate_transpose(&self) -> Matrix<Complex<f64>, C, R> {
+        Matrix::from_fn(|i, j| self[(j, i)].conjugate())
+    }
+}
+
+impl<const N: usize> Matrix<Complex<f64>, N, N> {
+    // A* A = I up to eps in every entry, i.e. the columns are orthonormal
+    pub fn is_unitary(&self, eps: f64) -> bool {
+        let product = self.conjugate_transpose() * *self;
+        (0..N).all(|i| {
+            (0..N).all(|j| {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                (product[(i, j)] - Complex::from(expected)).abs() <= eps
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{InnerProduct, Modulus, orthonormalize};
+    use crate::chapters::c1::definitions::{Complex, List, MulScalar, Subtract, Zero};
+    use crate::chapters::c3::matrix::Matrix;
+
+    fn real_samples() -> Vec<List<f64, 3>> {
+        vec![
+            List::new([1.0, 2.0, 3.0]),
+            List::new([-4.0, 0.5, 2.0]),
+            List::new([0.0, 0.0, 1e-3]),
+            List::new([2.0, 4.0, 6.0]),
+            List::new([-1.0, 1.0, -1.0]),
+        ]
+    }
+
+    fn complex_samples() -> Vec<List<Complex<f64>, 2>> {
+        vec![
+            List::new([Complex::new(1.0, 2.0), Complex::new(-1.0, 0.5)]),
+            List::new([Complex::new(0.0, -3.0), Complex::new(2.0, 2.0)]),
+            List::new([Complex::new(0.5, 0.0), Complex::new(0.0, 1.0)]),
+        ]
+    }
+
+    #[test]
+    fn cauchy_schwarz() {
+        for u in real_samples() {
+            for v in real_samples() {
+                assert!(u.inner(&v).abs() <= u.norm() * v.norm() + 1e-12);
+            }
+        }
+        for u in complex_samples() {
+            for v in complex_samples() {
+                assert!(u.inner(&v).modulus() <= u.norm() * v.norm() + 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn conjugate_symmetry() {
+        for u in complex_samples() {
+            for v in complex_samples() {
+                let uv = u.inner(&v);
+                let vu = v.inner(&u).conjugate();
+                assert!(uv.subtract(vu).modulus() < 1e-12, "{uv} != {vu}");
+            }
+            // <u, u> is real
+            assert_eq!(*u.inner(&u).im(), 0.0);
+        }
+
+        let u = List::new([Complex::new(0.0, 1.0)]);
+        assert_eq!(u.inner(&u), Complex::from(1.0));
+    }
+
+    #[test]
+    fn normalize() {
+        for v in real_samples() {
+            assert!((v.normalize().unwrap().norm() - 1.0).abs() < 1e-12);
+        }
+        for v in complex_samples() {
+            assert!((v.normalize().unwrap().norm() - 1.0).abs() < 1e-12);
+        }
+        assert!(List::<f64, 3>::ZERO.normalize().is_none());
+        assert!(List::<Complex<f64>, 2>::ZERO.normalize().is_none());
+    }
+
+    #[test]
+    fn distance_and_orthogonality() {
+        let u = List::new([1.0, 1.0, 0.0]);
+        let v = List::new([1.0, -1.0, 5.0]);
+        assert!(u.is_orthogonal_to(&v, 0.0));
+        assert!(!u.is_orthogonal_to(&u, 1e-12));
+        assert_eq!(u.distance(&List::new([4.0, 5.0, 0.0])), 5.0);
+        assert_eq!(u.distance(&u), 0.0);
+    }
+
+    #[test]
+    fn gram_schmidt() {
+        let samples = real_samples();
+        let basis = orthonormalize(&samples, 1e-9);
+        // (2, 4, 6) depends on (1, 2, 3) and the fifth vector can't add a fourth dimension
+        assert_eq!(basis.len(), 3);
+        for (i, e) in basis.iter().enumerate() {
+            assert!((e.norm() - 1.0).abs() < 1e-12);
+            for f in &basis[i + 1..] {
+                assert!(e.is_orthogonal_to(f, 1e-12));
+            }
+        }
+
+        let basis = orthonormalize(&complex_samples(), 1e-9);
+        assert_eq!(basis.len(), 2);
+        assert!(
+            basis[0]
+                .inner(&basis[0])
+                .subtract(Complex::from(1.0))
+                .modulus()
+                < 1e-12
+        );
+        assert!(basis[0].is_orthogonal_to(&basis[1], 1e-12));
+    }
+
+    #[test]
+    fn unitary() {
+        // A rotation times a diagonal of phases
+        let (c, s) = (0.6, 0.8);
+        let u = Matrix::new([
+            [Complex::new(c, 0.0), Complex::new(0.0, -s)],
+            [Complex::new(s, 0.0), Complex::new(0.0, c)],
+        ]);
+        assert_eq!(u.conjugate_transpose()[(1, 0)], Complex::new(0.0, s));
+        assert!(u.is_unitary(1e-15));
+        assert!(!MulScalar::mul(u, Complex::new(1.0, 1e-3)).is_unitary(1e-6));
+
+        let not = Matrix::new([
+            [Complex::new(1.0, 0.0), Complex::new(1.0, 0.0)],
+            [Complex::new(0.0, 0.0), Complex::new(1.0, 0.0)],
+        ]);
+        assert!(!not.is_unitary(0.5));
+    }
+}
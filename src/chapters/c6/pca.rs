@@ -0,0 +1,175 @@
+use crate::chapters::c1::definitions::{List, Zero};
+use crate::chapters::c3::matrix::Matrix;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleError {
+    // There is nothing to average
+    NoSamples,
+    // The unbiased estimate divides by the number of samples minus one
+    TooFewSamples,
+}
+
+pub fn mean<const N: usize>(samples: &[List<f64, N>]) -> Result<List<f64, N>, SampleError> {
+    if samples.is_empty() {
+        return Err(SampleError::NoSamples);
+    }
+    let n = samples.len() as f64;
+    Ok(List::from_fn(|i| {
+        samples.iter().map(|x| x[i]).sum::<f64>() / n
+    }))
+}
+
+// The average of (x - mean)(x - mean)^T over the samples. unbiased divides the sum by the
+// number of samples minus one instead, which makes it the unbiased estimate of the covariance
+// of the distribution the samples were drawn from.
+pub fn covariance<const N: usize>(
+    samples: &[List<f64, N>],
+    unbiased: bool,
+) -> Result<Matrix<f64, N, N>, SampleError> {
+    let mean = mean(samples)?;
+    let divisor = if unbiased {
+        if samples.len() < 2 {
+            return Err(SampleError::TooFewSamples);
+        }
+        samples.len() - 1
+    } else {
+        samples.len()
+    } as f64;
+
+    let mut sum = Matrix::<f64, N, N>::ZERO;
+    for x in samples {
+        for i in 0..N {
+            for j in 0..N {
+                sum[(i, j)] += (x[i] - mean[i]) * (x[j] - mean[j]);
+            }
+        }
+    }
+    Ok(Matrix::from_fn(|i, j| sum[(i, j)] / divisor))
+}
+
+// The K orthonormal directions along which the samples vary most, the rows of directions, with
+// the variance of the samples along each. They are the top eigenvectors of the covariance,
+// and together span the K dimensional affine subspace through the mean closest to the samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pca<const N: usize, const K: usize> {
+    pub mean: List<f64, N>,
+    pub directions: Matrix<f64, K, N>,
+    pub variances: List<f64, K>,
+}
+
+// Principal component analysis using the unbiased covariance
+pub fn pca<const N: usize, const K: usize>(
+    samples: &[List<f64, N>],
+) -> Result<Pca<N, K>, SampleError> {
+    const {
+        assert!(
+            K <= N,
+            "can't have more principal directions than dimensions"
+        )
+    };
+    let (lambda, v) = covariance(samples, true)?.symmetric_eigen();
+    Ok(Pca {
+        mean: mean(samples)?,
+        directions: Matrix::from_fn(|k, i| v[(i, k)]),
+        variances: List::from_fn(|k| lambda[k].max(0.0)),
+    })
+}
+
+impl<const N: usize, const K: usize> Pca<N, K> {
+    // Coordinates of x - mean along the directions
+    pub fn project(&self, x: &List<f64, N>) -> List<f64, K> {
+        self.directions * List::from_fn(|i| x[i] - self.mean[i])
+    }
+
+    pub fn project_samples(&self, samples: &[List<f64, N>]) -> Vec<List<f64, K>> {
+        samples.iter().map(|x| self.project(x)).collect()
+    }
+
+    // The point of the subspace with the given coordinates
+    pub fn reconstruct(&self, y: &List<f64, K>) -> List<f64, N> {
+        let offset = self.directions.transpose() * *y;
+        List::from_fn(|i| self.mean[i] + offset[i])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SampleError, covariance, mean, pca};
+    use crate::chapters::c1::definitions::List;
+
+    // Deterministic numbers spread over [-1, 1)
+    fn noise(k: usize) -> f64 {
+        ((k * 7919 + 13) % 1000) as f64 / 500.0 - 1.0
+    }
+
+    #[test]
+    fn axis_aligned() {
+        // Independent coordinates with spreads 3, 1 and 0.1
+        let samples: Vec<List<f64, 3>> = [-1.0, 1.0]
+            .into_iter()
+            .flat_map(|x| [-1.0, 1.0].map(move |y| (x, y)))
+            .flat_map(|(x, y)| [-1.0, 1.0].map(move |z| List::new([3.0 * x, y, 0.1 * z + 5.0])))
+            .collect();
+        let c = covariance(&samples, false).unwrap();
+        for i in 0..3 {
+            for j in 0..3 {
+                if i != j {
+                    assert_eq!(c[(i, j)], 0.0);
+                }
+            }
+        }
+        assert!((c[(0, 0)] - 9.0).abs() < 1e-14);
+        assert!((c[(2, 2)] - 0.01).abs() < 1e-14);
+        assert_eq!(mean(&samples).unwrap()[2], 5.0);
+
+        let unbiased = covariance(&samples, true).unwrap();
+        assert!((unbiased[(1, 1)] - 8.0 / 7.0).abs() < 1e-14);
+    }
+
+    #[test]
+    fn recovers_a_line() {
+        // Points along (1, 2, 2) / 3 through (1, 0, -1), with a little noise on top
+        let direction = [1.0 / 3.0, 2.0 / 3.0, 2.0 / 3.0];
+        let samples: Vec<List<f64, 3>> = (0..50)
+            .map(|k| {
+                let t = k as f64 / 5.0 - 5.0;
+                let base = [1.0, 0.0, -1.0];
+                List::from_fn(|i| base[i] + t * direction[i] + 1e-3 * noise(3 * k + i))
+            })
+            .collect();
+
+        let p = pca::<3, 1>(&samples).unwrap();
+        let found = p.directions.row(0);
+        let alignment: f64 = (0..3).map(|i| found[i] * direction[i]).sum();
+        assert!((alignment.abs() - 1.0).abs() < 1e-6);
+
+        // The explained variances add up to the total variance, the trace of the covariance
+        let all = pca::<3, 3>(&samples).unwrap();
+        let c = covariance(&samples, true).unwrap();
+        let total: f64 = (0..3).map(|i| c[(i, i)]).sum();
+        assert!((all.variances.iter().sum::<f64>() - total).abs() < 1e-12);
+        assert!(all.variances[0] > 1e5 * all.variances[1]);
+
+        // Projecting on the line and back lands close to every point
+        for (x, y) in samples.iter().zip(p.project_samples(&samples)) {
+            let back = p.reconstruct(&y);
+            for i in 0..3 {
+                assert!((back[i] - x[i]).abs() < 1e-2);
+            }
+        }
+    }
+
+    #[test]
+    fn edge_cases() {
+        let none: [List<f64, 2>; 0] = [];
+        assert_eq!(covariance(&none, false), Err(SampleError::NoSamples));
+        assert_eq!(pca::<2, 1>(&none), Err(SampleError::NoSamples));
+
+        let one = [List::new([1.0, 2.0])];
+        assert_eq!(covariance(&one, true), Err(SampleError::TooFewSamples));
+        assert_eq!(pca::<2, 1>(&one), Err(SampleError::TooFewSamples));
+        let biased = covariance(&one, false).unwrap();
+        assert_eq!(biased[(0, 0)], 0.0);
+        assert_eq!(biased[(0, 1)], 0.0);
+    }
+}
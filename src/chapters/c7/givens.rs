@@ -0,0 +1,236 @@
+use crate::chapters::c1::definitions::List;
+use crate::chapters::c3::matrix::Matrix;
+
+// Rotation in the (i, j) plane, acting on x as
+//  x_i <-  c x_i + s x_j
+//  x_j <- -s x_i + c x_j
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Givens {
+    pub c: f64,
+    pub s: f64,
+    pub i: usize,
+    pub j: usize,
+}
+
+impl Givens {
+    pub fn new(c: f64, s: f64, i: usize, j: usize) -> Self {
+        assert!(
+            i != j,
+            "a Givens rotation needs two different coordinates, got {i} twice"
+        );
+        Self { c, s, i, j }
+    }
+
+    // The rotation in the (i, j) plane that maps (a, b) to (r, 0) with r = |(a, b)|.
+    // hypot avoids the overflow and underflow of sqrt(a^2 + b^2)
+    pub fn rotation_to_zero(i: usize, j: usize, a: f64, b: f64) -> Self {
+        if b == 0.0 {
+            // Nothing to zero, but a negative a still has to be turned around by pi
+            let c = if a < 0.0 { -1.0 } else { 1.0 };
+            return Self::new(c, 0.0, i, j);
+        }
+        let r = a.hypot(b);
+        Self::new(a / r, b / r, i, j)
+    }
+
+    // Rotation over the opposite angle
+    pub fn transpose(&self) -> Self {
+        Self::new(self.c, -self.s, self.i, self.j)
+    }
+
+    // The rotation as an N x N matrix, the identity outside rows and columns i and j
+    pub fn matrix<const N: usize>(&self) -> Matrix<f64, N, N> {
+        let mut g = Matrix::identity();
+        g[(self.i, self.i)] = self.c;
+        g[(self.i, self.j)] = self.s;
+        g[(self.j, self.i)] = -self.s;
+        g[(self.j, self.j)] = self.c;
+        g
+    }
+
+    // a <- G a, only rows i and j change
+    pub fn apply_left<const R: usize, const C: usize>(&self, a: &mut Matrix<f64, R, C>) {
+        for k in 0..C {
+            let (ai, aj) = (a[(self.i, k)], a[(self.j, k)]);
+            a[(self.i, k)] = self.c * ai + self.s * aj;
+            a[(self.j, k)] = -self.s * ai + self.c * aj;
+        }
+    }
+
+    // a <- a G^T, only columns i and j change. Undoes apply_left when used on the other side,
+    // so rotating both sides is a similarity transformation
+    pub fn apply_right<const R: usize, const C: usize>(&self, a: &mut Matrix<f64, R, C>) {
+        for k in 0..R {
+            let (ai, aj) = (a[(k, self.i)], a[(k, self.j)]);
+            a[(k, self.i)] = self.c * ai + self.s * aj;
+            a[(k, self.j)] = -self.s * ai + self.c * aj;
+        }
+    }
+
+    pub fn apply_to_list<const N: usize>(&self, x: &mut List<f64, N>) {
+        let (xi, xj) = (x[self.i], x[self.j]);
+        let rotated = List::from_fn(|k| {
+            if k == self.i {
+                self.c * xi + self.s * xj
+            } else if k == self.j {
+                -self.s * xi + self.c * xj
+            } else {
+                x[k]
+            }
+        });
+        *x = rotated;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Givens;
+    use crate::chapters::c1::definitions::List;
+    use crate::chapters::c3::matrix::Matrix;
+
+    fn norm<const N: usize>(x: &List<f64, N>) -> f64 {
+        (0..N).map(|k| x[k] * x[k]).sum::<f64>().sqrt()
+    }
+
+    #[test]
+    fn zeroes_target() {
+        for (a, b) in [
+            (3.0, 4.0),
+            (-1.0, 2.0),
+            (0.0, -5.0),
+            (-3.0, 0.0),
+            (1e300, 1e300),
+            (1e-300, -2e-300),
+        ] {
+            let mut x = List::<f64, 4>::from_fn(|k| match k {
+                1 => a,
+                3 => b,
+                _ => k as f64,
+            });
+            let g = Givens::rotation_to_zero(1, 3, a, b);
+            g.apply_to_list(&mut x);
+
+            let r = a.hypot(b);
+            assert!(x[3].abs() <= 1e-15 * r);
+            assert!((x[1] - r).abs() <= 1e-15 * r);
+            assert_eq!(x[0], 0.0);
+            assert_eq!(x[2], 2.0);
+        }
+
+        // Already zero, only the sign of a still has to be fixed
+        assert_eq!(
+            Givens::rotation_to_zero(0, 1, 2.0, 0.0),
+            Givens::new(1.0, 0.0, 0, 1)
+        );
+        let g = Givens::rotation_to_zero(0, 1, -2.0, 0.0);
+        assert_eq!(g, Givens::new(-1.0, 0.0, 0, 1));
+        let mut x = List::new([-2.0, 0.0]);
+        g.apply_to_list(&mut x);
+        assert_eq!(x, List::new([2.0, 0.0]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn same_coordinates() {
+        Givens::rotation_to_zero(1, 1, 3.0, 4.0);
+    }
+
+    #[test]
+    fn matrices() {
+        let a = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 10.0]]);
+        let g = Givens::rotation_to_zero(2, 0, 10.0, 3.0);
+        let gm = g.matrix::<3>();
+
+        let mut left = a;
+        g.apply_left(&mut left);
+        let mut right = a;
+        g.apply_right(&mut right);
+        let (expected_left, expected_right) = (gm * a, a * gm.transpose());
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((left[(i, j)] - expected_left[(i, j)]).abs() < 1e-14);
+                assert!((right[(i, j)] - expected_right[(i, j)]).abs() < 1e-14);
+            }
+        }
+        assert!(left[(0, 2)].abs() < 1e-15);
+
+        // Rectangular matrices rotate the same way, G A with A 3 x 2 and A^T G^T with A^T 2 x 3
+        let mut b = Matrix::new([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+        let mut bt = b.transpose();
+        g.apply_left(&mut b);
+        g.apply_right(&mut bt);
+        assert_eq!(b.transpose(), bt);
+
+        // G^T G = I
+        let product = gm.transpose() * gm;
+        let identity = Matrix::<f64, 3, 3>::identity();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((product[(i, j)] - identity[(i, j)]).abs() < 1e-15);
+            }
+        }
+    }
+
+    #[test]
+    fn orthogonal() {
+        let g = Givens::rotation_to_zero(0, 2, 1.5, -0.7);
+        assert!((g.c * g.c + g.s * g.s - 1.0).abs() < 1e-15);
+
+        // The images of the basis vectors are orthonormal
+        let mut e0 = List::<f64, 3>::from_fn(|k| if k == 0 { 1.0 } else { 0.0 });
+        let mut e2 = List::<f64, 3>::from_fn(|k| if k == 2 { 1.0 } else { 0.0 });
+        g.apply_to_list(&mut e0);
+        g.apply_to_list(&mut e2);
+        assert!((norm(&e0) - 1.0).abs() < 1e-15);
+        assert!((norm(&e2) - 1.0).abs() < 1e-15);
+        assert!((e0[0] * e2[0] + e0[1] * e2[1] + e0[2] * e2[2]).abs() < 1e-15);
+
+        // Norms are preserved and the transpose undoes the rotation
+        let original = List::<f64, 3>::from_fn(|k| k as f64 - 0.5);
+        let mut x = original;
+        g.apply_to_list(&mut x);
+        assert!((norm(&x) - norm(&original)).abs() < 1e-15);
+        g.transpose().apply_to_list(&mut x);
+        for k in 0..3 {
+            assert!((x[k] - original[k]).abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn qr_matches_householder() {
+        let a = Matrix::new([
+            [2.0, -1.0, 0.5],
+            [1.0, 3.0, -2.0],
+            [0.0, 1.0, 4.0],
+            [-1.0, 0.5, 1.0],
+        ]);
+
+        // Zero column by column from the bottom up, G_m ... G_1 A = R and Q = G_1^T ... G_m^T
+        let mut r = a;
+        let mut q = Matrix::<f64, 4, 4>::identity();
+        for j in 0..3 {
+            for i in (j + 1..4).rev() {
+                let g = Givens::rotation_to_zero(i - 1, i, r[(i - 1, j)], r[(i, j)]);
+                g.apply_left(&mut r);
+                g.apply_right(&mut q);
+            }
+        }
+
+        // The QR of a full rank matrix is unique up to the signs of the rows of R
+        // and the matching columns of Q
+        let householder = a.qr_householder();
+        let (hq, hr) = (householder.q(), householder.r());
+        for i in 0..3 {
+            let sign = (r[(i, i)] * hr[(i, i)]).signum();
+            for j in 0..3 {
+                assert!((r[(i, j)] - sign * hr[(i, j)]).abs() < 1e-14);
+            }
+            for k in 0..4 {
+                assert!((q[(k, i)] - sign * hq[(k, i)]).abs() < 1e-14);
+            }
+        }
+        for j in 0..3 {
+            assert!(r[(3, j)].abs() < 1e-15);
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use crate::chapters::c1::definitions::List;
+use crate::chapters::c3::matrix::Matrix;
+use crate::chapters::c3::solve::SolveError;
+
+// A P = Q R with Q = H_0 H_1 ... H_{K-1} a product of reflectors H_k = I - tau_k v_k v_k^T.
+// v_k is zero above row k and one in row k, so it fits below the diagonal of R:
+//  qr[(i, j)] is r_ij for i <= j and entry i of v_j for i > j.
+// P permutes the columns, column j of A P is column perm[j] of A.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HouseholderQr<const R: usize, const C: usize> {
+    qr: Matrix<f64, R, C>,
+    tau: [f64; C],
+    perm: [usize; C],
+}
+
+impl<const R: usize, const C: usize> Matrix<f64, R, C> {
+    // QR with a reflector per column. Unlike Gram-Schmidt, Q comes out orthogonal to working
+    // precision however close the columns of A are to being dependent.
+    pub fn qr_householder(&self) -> HouseholderQr<R, C> {
+        HouseholderQr::decompose(*self, false)
+    }
+
+    // QR that moves the remaining column with the largest norm to the front at every step, so the
+    // diagonal of R decreases and a small r_kk shows that A has rank about k
+    pub fn qr_householder_pivoted(&self) -> HouseholderQr<R, C> {
+        HouseholderQr::decompose(*self, true)
+    }
+
+    // The x minimizing |A x - b|, see HouseholderQr::lstsq
+    pub fn lstsq(&self, b: &List<f64, R>) -> Result<List<f64, C>, SolveError> {
+        self.qr_householder().lstsq(b)
+    }
+}
+
+impl<const R: usize, const C: usize> HouseholderQr<R, C> {
+    fn decompose(mut qr: Matrix<f64, R, C>, pivot: bool) -> Self {
+        let mut tau = [0.0; C];
+        let mut perm = core::array::from_fn(|j| j);
+        for k in 0..R.min(C) {
+            if pivot {
+                let norm = |j: usize| (k..R).map(|i| qr[(i, j)] * qr[(i, j)]).sum::<f64>();
+                let best = (k..C).max_by(|&i, &j| norm(i).total_cmp(&norm(j))).unwrap();
+                if best != k {
+                    for i in 0..R {
+                        let t = qr[(i, k)];
+                        qr[(i, k)] = qr[(i, best)];
+                        qr[(i, best)] = t;
+                    }
+                    perm.swap(k, best);
+                }
+            }
+
+            // The reflector mapping x = column k below the diagonal to alpha e_k. alpha gets the
+            // sign opposite to x_k so that x_k - alpha doesn't cancel.
+            let x0 = qr[(k, k)];
+            let norm = (k..R).fold(0.0, |acc: f64, i| acc.hypot(qr[(i, k)]));
+            if norm == 0.0 {
+                continue;
+            }
+            let alpha = if x0 > 0.0 { -norm } else { norm };
+            let v0 = x0 - alpha;
+            for i in k + 1..R {
+                qr[(i, k)] /= v0;
+            }
+            tau[k] = -v0 / alpha;
+            qr[(k, k)] = alpha;
+
+            for j in k + 1..C {
+                let w = tau[k]
+                    * (qr[(k, j)] + (k + 1..R).map(|i| qr[(i, k)] * qr[(i, j)]).sum::<f64>());
+                qr[(k, j)] -= w;
+                for i in k + 1..R {
+                    qr[(i, j)] -= qr[(i, k)] * w;
+                }
+            }
+        }
+        HouseholderQr { qr, tau, perm }
+    }
+
+    // x <- H_k x
+    fn reflect(&self, k: usize, x: &mut List<f64, R>) {
+        let w = self.tau[k] * (x[k] + (k + 1..R).map(|i| self.qr[(i, k)] * x[i]).sum::<f64>());
+        x[k] -= w;
+        for i in k + 1..R {
+            x[i] -= self.qr[(i, k)] * w;
+        }
+    }
+
+    // Q^T b = H_{K-1} ... H_0 b in O(R C), without forming Q
+    pub fn apply_qt(&self, b: &List<f64, R>) -> List<f64, R> {
+        let mut x = *b;
+        for k in 0..R.min(C) {
+            self.reflect(k, &mut x);
+        }
+        x
+    }
+
+    // Q b = H_0 ... H_{K-1} b
+    pub fn apply_q(&self, b: &List<f64, R>) -> List<f64, R> {
+        let mut x = *b;
+        for k in (0..R.min(C)).rev() {
+            self.reflect(k, &mut x);
+        }
+        x
+    }
+
+    // The full R x R orthogonal factor, column j is Q e_j
+    pub fn q(&self) -> Matrix<f64, R, R> {
+        let columns: [List<f64, R>; R] = core::array::from_fn(|j| {
+            self.apply_q(&List::from_fn(|i| if i == j { 1.0 } else { 0.0 }))
+        });
+        Matrix::from_fn(|i, j| columns[j][i])
+    }
+
+    pub fn r(&self) -> Matrix<f64, R, C> {
+        Matrix::from_fn(|i, j| if i <= j { self.qr[(i, j)] } else { 0.0 })
+    }
+
+    pub fn permutation(&self) -> [usize; C] {
+        self.perm
+    }
+
+    // For R >= C: |A x - b| = |R x - Q^T b|, and only the first C rows of R x depend on x, so the
+    // minimum comes from back substitution with the top of R. The conditioning is that of A,
+    // the normal equations A^T A x = A^T b square it. Fails when a diagonal entry of R
+    // vanishes next to the largest one, i.e. when the columns of A are dependent. The cutoff
+    // max(R, C) eps |r|_max is the usual one for rounding errors in a QR of that size.
+    pub fn lstsq(&self, b: &List<f64, R>) -> Result<List<f64, C>, SolveError> {
+        assert!(
+            R >= C,
+            "least squares needs at least as many rows as columns"
+        );
+        let largest = (0..C).map(|k| self.qr[(k, k)].abs()).fold(0.0, f64::max);
+        if (0..C).any(|k| self.qr[(k, k)].abs() <= largest * R.max(C) as f64 * f64::EPSILON) {
+            return Err(SolveError::Singular);
+        }
+
+        let y = self.apply_qt(b);
+        let mut z = List::<f64, C>::from_fn(|i| y[i]);
+        for i in (0..C).rev() {
+            for j in i + 1..C {
+                z[i] -= self.qr[(i, j)] * z[j];
+            }
+            z[i] /= self.qr[(i, i)];
+        }
+        let mut x = List::from_fn(|_| 0.0);
+        for j in 0..C {
+            x[self.perm[j]] = z[j];
+        }
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::chapters::c1::definitions::List;
+    use crate::chapters::c3::matrix::Matrix;
+    use crate::chapters::c3::solve::{SolveError, solve};
+    use crate::chapters::c6::inner_product::orthonormalize;
+
+    // max |(Q^T Q - I)_ij|
+    fn orthogonality_error<const R: usize, const C: usize>(q: &Matrix<f64, R, C>) -> f64 {
+        let qtq = q.transpose() * *q;
+        let mut error: f64 = 0.0;
+        for i in 0..C {
+            for j in 0..C {
+                let target = if i == j { 1.0 } else { 0.0 };
+                error = error.max((qtq[(i, j)] - target).abs());
+            }
+        }
+        error
+    }
+
+    fn max_difference<const R: usize, const C: usize>(
+        a: &Matrix<f64, R, C>,
+        b: &Matrix<f64, R, C>,
+    ) -> f64 {
+        let mut difference: f64 = 0.0;
+        for i in 0..R {
+            for j in 0..C {
+                difference = difference.max((a[(i, j)] - b[(i, j)]).abs());
+            }
+        }
+        difference
+    }
+
+    fn relative_error<const N: usize>(x: &List<f64, N>, exact: &List<f64, N>) -> f64 {
+        let norm = |v: List<f64, N>| (0..N).map(|i| v[i] * v[i]).sum::<f64>().sqrt();
+        norm(List::from_fn(|i| x[i] - exact[i])) / norm(*exact)
+    }
+
+    // Columns that are all close to (1, 1, 1, 1, 1)
+    fn nearly_dependent() -> Matrix<f64, 5, 3> {
+        let d = 1e-7;
+        Matrix::new([
+            [1.0, 1.0, 1.0],
+            [d, 0.0, 0.0],
+            [0.0, d, 0.0],
+            [0.0, 0.0, d],
+            [1.0, 1.0 + d, 1.0 - d],
+        ])
+    }
+
+    #[test]
+    fn reconstructs() {
+        let a = Matrix::new([
+            [2.0, -1.0, 0.5],
+            [1.0, 3.0, -2.0],
+            [0.0, 1.0, 4.0],
+            [-1.0, 0.5, 1.0],
+        ]);
+        for qr in [a.qr_householder(), a.qr_householder_pivoted()] {
+            let (q, r) = (qr.q(), qr.r());
+            let p = qr.permutation();
+            let ap = Matrix::<f64, 4, 3>::from_fn(|i, j| a[(i, p[j])]);
+            assert!(max_difference(&(q * r), &ap) < 1e-14);
+            assert!(orthogonality_error(&q) < 1e-15);
+            for i in 0..4 {
+                for j in 0..i.min(3) {
+                    assert_eq!(r[(i, j)], 0.0);
+                }
+            }
+
+            let b = List::new([1.0, -2.0, 0.5, 3.0]);
+            let qtb = qr.apply_qt(&b);
+            let explicit = q.transpose() * b;
+            let back = qr.apply_q(&qtb);
+            for i in 0..4 {
+                assert!((qtb[i] - explicit[i]).abs() < 1e-14);
+                assert!((back[i] - b[i]).abs() < 1e-14);
+            }
+        }
+
+        // Pivoting puts the largest column first and makes the diagonal of R decrease
+        let r = a.qr_householder_pivoted().r();
+        assert_eq!(a.qr_householder_pivoted().permutation()[0], 2);
+        assert!(r[(0, 0)].abs() >= r[(1, 1)].abs() && r[(1, 1)].abs() >= r[(2, 2)].abs());
+    }
+
+    #[test]
+    fn orthogonal_where_gram_schmidt_is_not() {
+        let a = nearly_dependent();
+        let householder = orthogonality_error(&a.qr_householder().q());
+
+        let columns: Vec<List<f64, 5>> = (0..3).map(|j| List::from_fn(|i| a[(i, j)])).collect();
+        let basis = orthonormalize(&columns, 0.0);
+        let gram_schmidt = orthogonality_error(&Matrix::<f64, 5, 3>::from_fn(|i, j| basis[j][i]));
+
+        assert!(householder < 1e-15);
+        assert!(gram_schmidt > 1e4 * householder);
+    }
+
+    #[test]
+    fn least_squares() {
+        // A line through noisy points, well conditioned
+        let a = Matrix::<f64, 6, 2>::from_fn(|i, j| if j == 0 { 1.0 } else { i as f64 });
+        let b = List::new([0.9, 3.1, 4.8, 7.2, 9.1, 10.8]);
+        let x = a.lstsq(&b).unwrap();
+        let normal = solve(a.transpose() * a, a.transpose() * b).unwrap();
+        assert!(relative_error(&x, &normal) < 1e-14);
+
+        // Polynomial fit with a solution that is known exactly, ill conditioned
+        let a = Matrix::<f64, 10, 6>::from_fn(|i, j| (i as f64 / 9.0).powi(j as i32));
+        let exact = List::new([1.0, -1.0, 2.0, 0.5, -2.0, 1.0]);
+        let b = a * exact;
+        let householder = relative_error(&a.lstsq(&b).unwrap(), &exact);
+        let normal = solve(a.transpose() * a, a.transpose() * b).unwrap();
+        let normal = relative_error(&normal, &exact);
+        assert!(householder < 1e-10);
+        assert!(normal > 1e3 * householder);
+
+        let dependent = Matrix::new([[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]]);
+        assert_eq!(
+            dependent.lstsq(&List::new([1.0, 2.0, 3.0])),
+            Err(SolveError::Singular)
+        );
+    }
+}
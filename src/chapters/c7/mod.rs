@@ -0,0 +1,4 @@
+pub mod givens;
+pub mod householder;
+pub mod rank;
+pub mod svd;
@@ -0,0 +1,201 @@
+use super::householder::HouseholderQr;
+use crate::chapters::c1::definitions::List;
+use crate::chapters::c3::matrix::Matrix;
+use crate::chapters::c6::inner_product::orthonormalize;
+
+// The numerical rank of A from a column pivoted QR, A P = Q R. Pivoting makes |r_kk| decrease,
+// and |r_11| is within a factor sqrt(C) of the largest singular value, so
+//  A P = Q ( R11 R12 )
+//          (  0  R22 )
+// with R11 the leading rank x rank block and R22 no larger than rounding errors.
+// Unlike c2::span::rank, which compares eliminated entries to an absolute eps, the cutoff
+// |r_kk| <= max(R, C) eps |r_11| scales with A, so multiplying A by any factor doesn't change the
+// answer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RankInfo<const R: usize, const C: usize> {
+    qr: HouseholderQr<R, C>,
+    r: Matrix<f64, R, C>,
+    rank: usize,
+    tolerance: f64,
+}
+
+impl<const R: usize, const C: usize> Matrix<f64, R, C> {
+    pub fn rank_revealing(&self) -> RankInfo<R, C> {
+        let qr = self.qr_householder_pivoted();
+        let r = qr.r();
+        let largest = if R.min(C) > 0 { r[(0, 0)].abs() } else { 0.0 };
+        let tolerance = R.max(C) as f64 * f64::EPSILON * largest;
+        let rank = (0..R.min(C))
+            .take_while(|&k| r[(k, k)].abs() > tolerance)
+            .count();
+        RankInfo {
+            qr,
+            r,
+            rank,
+            tolerance,
+        }
+    }
+}
+
+impl<const R: usize, const C: usize> RankInfo<R, C> {
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    // |r_kk| above this counts as non zero
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+
+    // |r_kk| for k < min(R, C), decreasing
+    pub fn diagonal(&self) -> Vec<f64> {
+        (0..R.min(C)).map(|k| self.r[(k, k)].abs()).collect()
+    }
+
+    // Column j of A P is column perm[j] of A, the first rank of them are independent
+    pub fn permutation(&self) -> [usize; C] {
+        self.qr.permutation()
+    }
+
+    pub fn qr(&self) -> &HouseholderQr<R, C> {
+        &self.qr
+    }
+
+    // An orthonormal basis of the column space, the first rank columns of Q
+    pub fn range_basis(&self) -> Vec<List<f64, R>> {
+        (0..self.rank)
+            .map(|j| {
+                self.qr
+                    .apply_q(&List::from_fn(|i| if i == j { 1.0 } else { 0.0 }))
+            })
+            .collect()
+    }
+
+    // An orthonormal basis of the x with A x = 0, with R22 taken as zero.
+    // Every column j past the rank gives the vector z with R11 z = -R12 e_j, placed above e_j,
+    // which A P maps to Q (R11 z + R12 e_j) = 0. Those are independent and get orthonormalized.
+    pub fn null_space_basis(&self) -> Vec<List<f64, C>> {
+        let perm = self.permutation();
+        let vectors: Vec<_> = (self.rank..C)
+            .map(|free| {
+                let mut z = [0.0; C];
+                z[free] = 1.0;
+                for i in (0..self.rank).rev() {
+                    let sum = (i + 1..self.rank)
+                        .map(|j| self.r[(i, j)] * z[j])
+                        .sum::<f64>()
+                        + self.r[(i, free)];
+                    z[i] = -sum / self.r[(i, i)];
+                }
+                let mut x = List::from_fn(|_| 0.0);
+                for j in 0..C {
+                    x[perm[j]] = z[j];
+                }
+                x
+            })
+            .collect();
+        orthonormalize(&vectors, 0.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::chapters::c1::definitions::{List, MulScalar};
+    use crate::chapters::c2::span::rank;
+    use crate::chapters::c3::matrix::Matrix;
+    use crate::chapters::c6::inner_product::InnerProduct;
+
+    // B C with an inner dimension of 2
+    fn rank_two() -> Matrix<f64, 5, 4> {
+        let b = Matrix::new([
+            [1.0, 2.0],
+            [-0.5, 1.0],
+            [3.0, 0.25],
+            [1.5, -2.0],
+            [0.1, 0.7],
+        ]);
+        let c = Matrix::new([[0.3, -1.0, 2.0, 1.1], [1.7, 0.4, -0.6, 2.2]]);
+        b * c
+    }
+
+    fn rows<const R: usize, const C: usize>(a: &Matrix<f64, R, C>) -> Vec<List<f64, C>> {
+        (0..R).map(|i| *a.row(i)).collect()
+    }
+
+    fn max_abs<const N: usize>(v: &List<f64, N>) -> f64 {
+        v.iter().fold(0.0, |acc: f64, x| acc.max(x.abs()))
+    }
+
+    #[test]
+    fn product_ranks() {
+        let a = rank_two();
+        for scale in [1e-200, 1e-30, 1e-8, 1.0, 1e8, 1e30, 1e200] {
+            let info = MulScalar::mul(a, scale).rank_revealing();
+            assert_eq!(info.rank(), 2, "scale {scale}");
+            let diagonal = info.diagonal();
+            assert!(diagonal[1] > info.tolerance() && diagonal[2] <= info.tolerance());
+        }
+
+        // Rank 1, 3 and full
+        let u = List::new([1.0, -2.0, 0.5]);
+        let v = List::new([3.0, 1.0, 4.0, -1.0]);
+        let outer = Matrix::<f64, 3, 4>::from_fn(|i, j| u[i] * v[j]);
+        assert_eq!(outer.rank_revealing().rank(), 1);
+        let a = rank_two().transpose() * rank_two() + Matrix::from_fn(|i, j| (i * j) as f64);
+        assert_eq!(a.rank_revealing().rank(), 3);
+        assert_eq!(Matrix::<f64, 4, 4>::identity().rank_revealing().rank(), 4);
+        assert_eq!(
+            Matrix::<f64, 3, 2>::from_fn(|_, _| 0.0)
+                .rank_revealing()
+                .rank(),
+            0
+        );
+    }
+
+    #[test]
+    fn where_elimination_fails() {
+        // The absolute eps the row echelon rank uses sees entries of 1e-12 as zero ...
+        let small = MulScalar::mul(Matrix::<f64, 3, 3>::identity(), 1e-12);
+        assert_eq!(rank(&rows(&small), 1e-9), 0);
+        assert_eq!(small.rank_revealing().rank(), 3);
+
+        // ... and the cancellation errors of a large rank deficient matrix as pivots
+        let large = MulScalar::mul(rank_two(), 1e10);
+        assert!(rank(&rows(&large), 1e-9) > 2);
+        assert_eq!(large.rank_revealing().rank(), 2);
+    }
+
+    #[test]
+    fn bases() {
+        let a = rank_two();
+        let info = a.rank_revealing();
+
+        let range = info.range_basis();
+        let null = info.null_space_basis();
+        assert_eq!(range.len(), 2);
+        assert_eq!(null.len(), 2);
+
+        // Orthonormal, and A maps the null space to zero
+        assert!((range[0].norm() - 1.0).abs() < 1e-12);
+        assert!(range[0].inner(&range[1]).abs() < 1e-12);
+        assert!((null[0].norm() - 1.0).abs() < 1e-12);
+        assert!(null[0].inner(&null[1]).abs() < 1e-12);
+        for x in &null {
+            assert!(max_abs(&(a * *x)) < 1e-12);
+        }
+
+        // Every column of A is in the range
+        for j in 0..4 {
+            let col = a.col(j);
+            let projection = range.iter().fold(List::from_fn(|_| 0.0), |acc, e| {
+                acc + MulScalar::mul(*e, col.inner(e))
+            });
+            assert!(max_abs(&List::<f64, 5>::from_fn(|i| col[i] - projection[i])) < 1e-12);
+        }
+
+        // Full column rank leaves nothing for the null space
+        let full = Matrix::new([[1.0, 2.0], [3.0, 4.0], [5.0, 7.0]]).rank_revealing();
+        assert!(full.null_space_basis().is_empty());
+        assert_eq!(full.range_basis().len(), 2);
+    }
+}
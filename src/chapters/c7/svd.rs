@@ -0,0 +1,249 @@
+use crate::chapters::c1::definitions::List;
+use crate::chapters::c3::matrix::Matrix;
+use crate::chapters::c7::givens::Givens;
+
+const MAX_SWEEPS: usize = 100;
+
+// A = U diag(sigma) V^T with the singular values in decreasing order, V orthogonal and the
+// columns of U orthonormal where sigma is non zero. There are C singular values, so for C > R
+// at least C - R of them are zero, and the columns of U for zero singular values are zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Svd<const R: usize, const C: usize> {
+    u: Matrix<f64, R, C>,
+    sigma: List<f64, C>,
+    v: Matrix<f64, C, C>,
+}
+
+impl<const R: usize, const C: usize> Matrix<f64, R, C> {
+    // One sided Jacobi: rotate pairs of columns of W = A V until they are all orthogonal, then
+    // sigma_j = |w_j| and u_j = w_j / sigma_j. The rotation for columns p and q is the Jacobi
+    // rotation that diagonalises their 2 x 2 Gram matrix, as in symmetric_eigen on A^T A, but
+    // A^T A is never formed, so small singular values keep their relative accuracy.
+    // Columns below eps |A| are rounding noise and are left alone, that is where it stops when
+    // A has more columns than rows.
+    pub fn svd(&self) -> Svd<R, C> {
+        let mut w = *self;
+        let mut v = Matrix::<f64, C, C>::identity();
+        let column = |w: &Matrix<f64, R, C>, p: usize, q: usize| -> f64 {
+            (0..R).map(|i| w[(i, p)] * w[(i, q)]).sum()
+        };
+        let total: f64 = (0..C).map(|j| column(&w, j, j)).sum();
+        let noise = f64::EPSILON * f64::EPSILON * total;
+
+        for _ in 0..MAX_SWEEPS {
+            let mut rotated = false;
+            for p in 0..C {
+                for q in p + 1..C {
+                    let (alpha, beta, gamma) =
+                        (column(&w, p, p), column(&w, q, q), column(&w, p, q));
+                    if alpha <= noise
+                        || beta <= noise
+                        || gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt()
+                    {
+                        continue;
+                    }
+                    let theta = (beta - alpha) / (2.0 * gamma);
+                    let t = -theta.signum() / (theta.abs() + theta.hypot(1.0));
+                    let c = 1.0 / t.hypot(1.0);
+                    let g = Givens::new(c, t * c, p, q);
+                    g.apply_right(&mut w);
+                    g.apply_right(&mut v);
+                    rotated = true;
+                }
+            }
+            if !rotated {
+                break;
+            }
+        }
+
+        let norms: [f64; C] = core::array::from_fn(|j| column(&w, j, j).sqrt());
+        let mut order: [usize; C] = core::array::from_fn(|j| j);
+        order.sort_by(|&i, &j| norms[j].total_cmp(&norms[i]));
+        let sigma = List::from_fn(|k| norms[order[k]]);
+        Svd {
+            u: Matrix::from_fn(|i, k| {
+                let s = norms[order[k]];
+                if s > 0.0 { w[(i, order[k])] / s } else { 0.0 }
+            }),
+            sigma,
+            v: Matrix::from_fn(|i, k| v[(i, order[k])]),
+        }
+    }
+
+    // The Moore-Penrose pseudoinverse A+ = V diag(1 / sigma) U^T, where singular values of at
+    // most tol times the largest one count as zero and stay zero. It is the inverse of A for
+    // invertible A, and A+ b is the least squares solution of A x = b with the smallest norm.
+    pub fn pinv(&self, tol: f64) -> Matrix<f64, C, R> {
+        self.svd().pinv(tol)
+    }
+
+    // A+ b with the cutoff max(R, C) eps for the rounding errors of the decomposition. Works for
+    // any shape and rank, where solve and lstsq fail on a singular A.
+    pub fn solve_pinv(&self, b: &List<f64, R>) -> List<f64, C> {
+        self.pinv(R.max(C) as f64 * f64::EPSILON) * *b
+    }
+}
+
+impl<const R: usize, const C: usize> Svd<R, C> {
+    pub fn u(&self) -> &Matrix<f64, R, C> {
+        &self.u
+    }
+
+    pub fn singular_values(&self) -> &List<f64, C> {
+        &self.sigma
+    }
+
+    pub fn v(&self) -> &Matrix<f64, C, C> {
+        &self.v
+    }
+
+    pub fn pinv(&self, tol: f64) -> Matrix<f64, C, R> {
+        let largest = if C > 0 { self.sigma[0] } else { 0.0 };
+        let inverted = List::<f64, C>::from_fn(|k| {
+            let s = self.sigma[k];
+            if s > tol * largest { 1.0 / s } else { 0.0 }
+        });
+        Matrix::from_fn(|i, j| {
+            (0..C)
+                .map(|k| self.v[(i, k)] * inverted[k] * self.u[(j, k)])
+                .sum()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::chapters::c1::definitions::List;
+    use crate::chapters::c3::matrix::Matrix;
+    use crate::chapters::c3::solve::solve;
+    use crate::chapters::c6::inner_product::InnerProduct;
+
+    fn max_difference<const R: usize, const C: usize>(
+        a: &Matrix<f64, R, C>,
+        b: &Matrix<f64, R, C>,
+    ) -> f64 {
+        let mut difference: f64 = 0.0;
+        for i in 0..R {
+            for j in 0..C {
+                difference = difference.max((a[(i, j)] - b[(i, j)]).abs());
+            }
+        }
+        difference
+    }
+
+    // Rank 2, from a B C product
+    fn deficient() -> Matrix<f64, 4, 3> {
+        let b = Matrix::new([[1.0, 2.0], [-0.5, 1.0], [3.0, 0.25], [1.5, -2.0]]);
+        let c = Matrix::new([[0.3, -1.0, 2.0], [1.7, 0.4, -0.6]]);
+        b * c
+    }
+
+    fn check_decomposition<const R: usize, const C: usize>(a: &Matrix<f64, R, C>, rank: usize) {
+        let svd = a.svd();
+        let (u, sigma, v) = (svd.u(), svd.singular_values(), svd.v());
+        let usv = Matrix::<f64, R, C>::from_fn(|i, j| {
+            (0..C).map(|k| u[(i, k)] * sigma[k] * v[(j, k)]).sum()
+        });
+        assert!(max_difference(&usv, a) < 1e-13);
+        assert!(max_difference(&(v.transpose() * *v), &Matrix::identity()) < 1e-14);
+        for k in 1..C {
+            assert!(sigma[k - 1] >= sigma[k]);
+        }
+        for k in 0..C {
+            if k < rank {
+                assert!((u.col(k).norm() - 1.0).abs() < 1e-13);
+                for l in 0..k {
+                    assert!(u.col(k).inner(&u.col(l)).abs() < 1e-13);
+                }
+            } else {
+                assert!(sigma[k] <= 1e-14 * sigma[0]);
+            }
+        }
+
+        // sigma^2 are the eigenvalues of A^T A
+        let (lambda, _) = (a.transpose() * *a).symmetric_eigen();
+        for k in 0..C {
+            assert!((sigma[k] * sigma[k] - lambda[k]).abs() <= 1e-12 * lambda[0]);
+        }
+    }
+
+    #[test]
+    fn decomposition() {
+        let a = Matrix::new([
+            [2.0, -1.0, 0.5],
+            [1.0, 3.0, -2.0],
+            [0.0, 1.0, 4.0],
+            [-1.0, 0.5, 1.0],
+        ]);
+        check_decomposition(&a, 3);
+        check_decomposition(&a.transpose(), 3);
+        check_decomposition(&deficient(), 2);
+        check_decomposition(&deficient().transpose(), 2);
+        check_decomposition(&Matrix::<f64, 2, 3>::from_fn(|_, _| 0.0), 0);
+
+        // A diagonal matrix only needs sorting
+        let d = Matrix::new([[1.0, 0.0, 0.0], [0.0, -3.0, 0.0], [0.0, 0.0, 2.0]]);
+        assert_eq!(d.svd().singular_values(), &List::new([3.0, 2.0, 1.0]));
+    }
+
+    fn check_penrose<const R: usize, const C: usize>(a: &Matrix<f64, R, C>) {
+        let x = a.pinv(1e-12);
+        let (ax, xa) = (*a * x, x * *a);
+        assert!(max_difference(&(ax * *a), a) < 1e-12);
+        assert!(max_difference(&(xa * x), &x) < 1e-12);
+        assert!(max_difference(&ax.transpose(), &ax) < 1e-12);
+        assert!(max_difference(&xa.transpose(), &xa) < 1e-12);
+    }
+
+    #[test]
+    fn penrose_conditions() {
+        check_penrose(&deficient());
+        check_penrose(&deficient().transpose());
+        check_penrose(&Matrix::new([[1.0, 2.0], [2.0, 4.0]]));
+        check_penrose(&Matrix::new([[1.0, 0.5, -1.0, 2.0], [0.0, 3.0, 1.0, 1.0]]));
+    }
+
+    #[test]
+    fn invertible() {
+        let a = Matrix::new([[4.0, -2.0, 1.0], [3.0, 6.0, -4.0], [2.0, 1.0, 8.0]]);
+        let columns: [List<f64, 3>; 3] = core::array::from_fn(|j| {
+            solve(a, List::from_fn(|i| if i == j { 1.0 } else { 0.0 })).unwrap()
+        });
+        let inverse = Matrix::from_fn(|i, j| columns[j][i]);
+        assert!(max_difference(&a.pinv(1e-12), &inverse) < 1e-14);
+    }
+
+    #[test]
+    fn minimum_norm() {
+        // x + y + z = 3 and x - y = 1, every solution is (2, 1, 0) + t (1, 1, -2)
+        let a = Matrix::new([[1.0, 1.0, 1.0], [1.0, -1.0, 0.0]]);
+        let b = List::new([3.0, 1.0]);
+        let x = a.solve_pinv(&b);
+        let residual = a * x + -b;
+        assert!(residual.norm() < 1e-14);
+
+        // The minimum norm one is orthogonal to the null space
+        let null = List::new([1.0, 1.0, -2.0]);
+        assert!(x.inner(&null).abs() < 1e-14);
+        for t in [-1.0, -0.1, 0.1, 0.5, 2.0] {
+            let other = List::new([2.0 + t, 1.0 + t, -2.0 * t]);
+            assert!((a * other + -b).norm() < 1e-14);
+            assert!(x.norm() < other.norm());
+        }
+    }
+
+    #[test]
+    fn rank_deficient_least_squares() {
+        // Two copies of the column c: only x_1 + x_2 matters, it is c . b / c . c at the least
+        // squares minimum, and the minimum norm splits it evenly
+        let c = [1.0, 2.0, -1.0, 0.5];
+        let a = Matrix::<f64, 4, 2>::from_fn(|i, _| c[i]);
+        let b = List::new([1.0, 0.0, 2.0, -1.0]);
+        let sum =
+            (0..4).map(|i| c[i] * b[i]).sum::<f64>() / (0..4).map(|i| c[i] * c[i]).sum::<f64>();
+        let x = a.solve_pinv(&b);
+        assert!((x[0] - sum / 2.0).abs() < 1e-14);
+        assert!((x[1] - sum / 2.0).abs() < 1e-14);
+        assert!(a.lstsq(&b).is_err());
+    }
+}
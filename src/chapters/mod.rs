@@ -1 +1,7 @@
-pub mod c1;
\ No newline at end of file
+pub mod c1;
+pub mod c2;
+pub mod c3;
+pub mod c4;
+pub mod c5;
+pub mod c6;
+pub mod c7;
\ No newline at end of file
@@ -0,0 +1,946 @@
+use std::ops::{Add, Index, Mul, Neg, Sub};
+
+use crate::chapters::c1::definitions::{AddInverse, Divide, List, MulScalar, One, Zero};
+use crate::chapters::c3::matrix::Matrix;
+use crate::chapters::c3::solve::SolveError;
+use crate::chapters::c6::inner_product::Modulus;
+
+// Points and displacement vectors of affine space.
+// Points can be subtracted and moved by a vector, but adding two points has no meaning:
+/// ```compile_fail
+/// use linear_algebra::chapters::c1::definitions::List;
+/// use linear_algebra::geometry::Point;
+///
+/// let p = Point::new(List::<f64, 2>::from_fn(|i| i as f64));
+/// let _ = p + p;
+/// ```
+#[derive(Clone, Copy)]
+pub struct Point<T, const N: usize>(List<T, N>);
+
+#[derive(Clone, Copy)]
+pub struct Vector<T, const N: usize>(List<T, N>);
+
+impl<T, const N: usize> Point<T, N> {
+    pub fn new(coords: List<T, N>) -> Self {
+        Self(coords)
+    }
+
+    pub fn coords(&self) -> &List<T, N> {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> Vector<T, N> {
+    pub fn new(coords: List<T, N>) -> Self {
+        Self(coords)
+    }
+
+    pub fn coords(&self) -> &List<T, N> {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Point<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Vector<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+// Point - Point = Vector
+impl<T, const N: usize> Sub for Point<T, N>
+where
+    T: Add<Output = T> + Neg<Output = T> + Copy,
+{
+    type Output = Vector<T, N>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector(self.0 + -rhs.0)
+    }
+}
+
+// Point + Vector = Point
+impl<T, const N: usize> Add<Vector<T, N>> for Point<T, N>
+where
+    T: Add<Output = T> + Copy,
+{
+    type Output = Point<T, N>;
+
+    fn add(self, rhs: Vector<T, N>) -> Self::Output {
+        Point(self.0 + rhs.0)
+    }
+}
+
+// Point - Vector = Point
+impl<T, const N: usize> Sub<Vector<T, N>> for Point<T, N>
+where
+    T: Add<Output = T> + Neg<Output = T> + Copy,
+{
+    type Output = Point<T, N>;
+
+    fn sub(self, rhs: Vector<T, N>) -> Self::Output {
+        Point(self.0 + -rhs.0)
+    }
+}
+
+// Vector + Vector = Vector
+impl<T, const N: usize> Add for Vector<T, N>
+where
+    T: Add<Output = T> + Copy,
+{
+    type Output = Vector<T, N>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Vector(self.0 + rhs.0)
+    }
+}
+
+impl<T, const N: usize> Sub for Vector<T, N>
+where
+    T: Add<Output = T> + Neg<Output = T> + Copy,
+{
+    type Output = Vector<T, N>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector(self.0 + -rhs.0)
+    }
+}
+
+impl<T, const N: usize> Neg for Vector<T, N>
+where
+    T: Neg<Output = T> + Copy,
+{
+    type Output = Vector<T, N>;
+
+    fn neg(self) -> Self::Output {
+        Vector(-self.0)
+    }
+}
+
+impl<T, const N: usize> MulScalar<T> for Vector<T, N>
+where
+    T: Mul<Output = T> + Copy,
+{
+    fn mul(self, rhs: T) -> Self {
+        Vector(MulScalar::mul(self.0, rhs))
+    }
+}
+
+// Homogeneous coordinates, points get a 1 appended and vectors a 0,
+// so a translation in a homogeneous transform only moves points
+impl<T> Point<T, 2>
+where
+    T: One + Copy,
+{
+    pub fn to_homogeneous(&self) -> List<T, 3> {
+        List::from_fn(|i| if i < 2 { self.0[i] } else { T::ONE })
+    }
+}
+
+impl<T> Point<T, 3>
+where
+    T: One + Copy,
+{
+    pub fn to_homogeneous(&self) -> List<T, 4> {
+        List::from_fn(|i| if i < 3 { self.0[i] } else { T::ONE })
+    }
+}
+
+impl<T> Vector<T, 2>
+where
+    T: Zero + Copy,
+{
+    pub fn to_homogeneous(&self) -> List<T, 3> {
+        List::from_fn(|i| if i < 2 { self.0[i] } else { T::ZERO })
+    }
+}
+
+impl<T> Vector<T, 3>
+where
+    T: Zero + Copy,
+{
+    pub fn to_homogeneous(&self) -> List<T, 4> {
+        List::from_fn(|i| if i < 3 { self.0[i] } else { T::ZERO })
+    }
+}
+
+// Barycentric coordinates (l0, l1, l2) of p, so that p = l0 a + l1 b + l2 c and l0 + l1 + l2 = 1.
+// Solves the 2x2 system [b - a, c - a] (l1, l2) = p - a with Cramer's rule.
+// Points outside the triangle get negative coordinates, None when the triangle is degenerate
+pub fn barycentric_coords(p: List<f64, 2>, tri: [List<f64, 2>; 3]) -> Option<List<f64, 3>> {
+    let [a, b, c] = tri;
+    let e0 = [b[0] - a[0], b[1] - a[1]];
+    let e1 = [c[0] - a[0], c[1] - a[1]];
+    let d = [p[0] - a[0], p[1] - a[1]];
+
+    let det = e0[0] * e1[1] - e1[0] * e0[1];
+    let scale = e0[0].hypot(e0[1]) * e1[0].hypot(e1[1]);
+    if det.abs() <= f64::EPSILON * scale || !det.is_finite() {
+        return None;
+    }
+
+    let l1 = (d[0] * e1[1] - e1[0] * d[1]) / det;
+    let l2 = (e0[0] * d[1] - d[0] * e0[1]) / det;
+    Some(List::from_fn(|i| [1.0 - l1 - l2, l1, l2][i]))
+}
+
+// Tetrahedron version of barycentric_coords, solving the 3x3 system with triple products
+pub fn barycentric_coords_tetrahedron(
+    p: List<f64, 3>,
+    tet: [List<f64, 3>; 4],
+) -> Option<List<f64, 4>> {
+    let [a, b, c, d] = tet;
+    let sub = |u: List<f64, 3>| [u[0] - a[0], u[1] - a[1], u[2] - a[2]];
+    let (e0, e1, e2, v) = (sub(b), sub(c), sub(d), sub(p));
+
+    let cross = |x: [f64; 3], y: [f64; 3]| {
+        [
+            x[1] * y[2] - x[2] * y[1],
+            x[2] * y[0] - x[0] * y[2],
+            x[0] * y[1] - x[1] * y[0],
+        ]
+    };
+    let dot = |x: [f64; 3], y: [f64; 3]| x[0] * y[0] + x[1] * y[1] + x[2] * y[2];
+    let norm = |x: [f64; 3]| dot(x, x).sqrt();
+
+    let det = dot(e0, cross(e1, e2));
+    let scale = norm(e0) * norm(e1) * norm(e2);
+    if det.abs() <= f64::EPSILON * scale || !det.is_finite() {
+        return None;
+    }
+
+    let l1 = dot(v, cross(e1, e2)) / det;
+    let l2 = dot(e0, cross(v, e2)) / det;
+    let l3 = dot(e0, cross(e1, v)) / det;
+    Some(List::from_fn(|i| [1.0 - l1 - l2 - l3, l1, l2, l3][i]))
+}
+
+// The point l0 v0 + l1 v1 + ... for barycentric coordinates l and the simplex vertices v
+pub fn from_barycentric<const N: usize, const M: usize>(
+    coords: List<f64, M>,
+    simplex: [List<f64, N>; M],
+) -> List<f64, N> {
+    let mut p = List::ZERO;
+    for (i, vertex) in simplex.iter().enumerate() {
+        p.axpy(coords[i], vertex);
+    }
+    p
+}
+
+fn dot<const N: usize>(u: &List<f64, N>, v: &List<f64, N>) -> f64 {
+    (0..N).map(|i| u[i] * v[i]).sum()
+}
+
+// The points point + t * direction, the direction should not be zero
+#[derive(Clone, Copy)]
+pub struct Line<const N: usize> {
+    pub point: List<f64, N>,
+    pub direction: List<f64, N>,
+}
+
+impl<const N: usize> Line<N> {
+    pub fn new(point: List<f64, N>, direction: List<f64, N>) -> Self {
+        Self { point, direction }
+    }
+
+    pub fn through(a: List<f64, N>, b: List<f64, N>) -> Self {
+        Self::new(a, b + -a)
+    }
+
+    pub fn at(&self, t: f64) -> List<f64, N> {
+        let mut p = self.point;
+        p.axpy(t, &self.direction);
+        p
+    }
+}
+
+// The points x in R^3 with normal . x = offset, the normal should not be zero
+#[derive(Clone, Copy)]
+pub struct Plane {
+    pub normal: List<f64, 3>,
+    pub offset: f64,
+}
+
+impl Plane {
+    pub fn new(normal: List<f64, 3>, offset: f64) -> Self {
+        Self { normal, offset }
+    }
+
+    pub fn from_point_normal(point: List<f64, 3>, normal: List<f64, 3>) -> Self {
+        Self::new(normal, dot(&normal, &point))
+    }
+}
+
+// None when the line is parallel to the plane, also when the line lies inside the plane
+pub fn line_plane_intersection(line: &Line<3>, plane: &Plane) -> Option<List<f64, 3>> {
+    let along = dot(&plane.normal, &line.direction);
+    let scale =
+        dot(&plane.normal, &plane.normal).sqrt() * dot(&line.direction, &line.direction).sqrt();
+    if along.abs() <= f64::EPSILON * scale {
+        return None;
+    }
+
+    let t = (plane.offset - dot(&plane.normal, &line.point)) / along;
+    Some(line.at(t))
+}
+
+// The closest points on both lines and the distance between them.
+// Minimizing |p + s d - q - t e| is the 2x2 system
+//  [ d.d  -d.e ] [s]   [ -d.r ]
+//  [ d.e  -e.e ] [t] = [ -e.r ]   with r = p - q
+// For parallel lines every point is closest, the point of the first line is used then.
+pub fn line_line_closest_points<const N: usize>(
+    l1: &Line<N>,
+    l2: &Line<N>,
+) -> (List<f64, N>, List<f64, N>, f64) {
+    let r = l1.point + -l2.point;
+    let a = dot(&l1.direction, &l1.direction);
+    let b = dot(&l1.direction, &l2.direction);
+    let c = dot(&l2.direction, &l2.direction);
+    let d = dot(&l1.direction, &r);
+    let e = dot(&l2.direction, &r);
+
+    let det = a * c - b * b;
+    let (s, t) = if det <= f64::EPSILON * a * c {
+        (0.0, e / c)
+    } else {
+        ((b * e - c * d) / det, (a * e - b * d) / det)
+    };
+
+    let p1 = l1.at(s);
+    let p2 = l2.at(t);
+    let diff = p1 + -p2;
+    (p1, p2, dot(&diff, &diff).sqrt())
+}
+
+pub fn point_plane_distance(point: &List<f64, 3>, plane: &Plane) -> f64 {
+    (dot(&plane.normal, point) - plane.offset).abs() / dot(&plane.normal, &plane.normal).sqrt()
+}
+
+pub fn project_point_onto_line<const N: usize>(
+    point: &List<f64, N>,
+    line: &Line<N>,
+) -> List<f64, N> {
+    let t = dot(&line.direction, &(*point + -line.point)) / dot(&line.direction, &line.direction);
+    line.at(t)
+}
+
+// Rounding can push the Gram determinant of (nearly) dependent vectors below zero
+fn volume_from_gram_determinant(det: f64) -> f64 {
+    det.max(0.0).sqrt()
+}
+
+// The k dimensional volume of the parallelepiped spanned by k vectors in R^N, sqrt(det(G))
+// with G the Gram matrix of inner products. More than N vectors are always dependent and
+// span nothing of dimension k, so they give 0, no vectors at all give the empty product 1.
+// For k < N the Gram matrix is padded to N x N with the identity, which keeps its determinant.
+pub fn parallelepiped_volume<const N: usize>(vectors: &[List<f64, N>]) -> f64 {
+    match vectors.len() {
+        k if k > N => 0.0,
+        1 => dot(&vectors[0], &vectors[0]).sqrt(),
+        k if k == N => Matrix::<f64, N, N>::from_fn(|i, j| vectors[i][j])
+            .determinant()
+            .abs(),
+        k => {
+            let gram = Matrix::<f64, N, N>::from_fn(|i, j| match (i < k, j < k) {
+                (true, true) => dot(&vectors[i], &vectors[j]),
+                _ if i == j => 1.0,
+                _ => 0.0,
+            });
+            volume_from_gram_determinant(gram.determinant())
+        }
+    }
+}
+
+// x -> linear x + translation. Keeps the two parts apart instead of an (N + 1) x (N + 1)
+// homogeneous matrix, so there is no last row that can go wrong and applying it costs N^2
+// multiplications instead of (N + 1)^2.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Affine<T, const N: usize> {
+    linear: Matrix<T, N, N>,
+    translation: List<T, N>,
+}
+
+impl<T, const N: usize> Affine<T, N> {
+    pub fn new(linear: Matrix<T, N, N>, translation: List<T, N>) -> Self {
+        Self {
+            linear,
+            translation,
+        }
+    }
+
+    pub fn linear(&self) -> &Matrix<T, N, N> {
+        &self.linear
+    }
+
+    pub fn translation(&self) -> &List<T, N> {
+        &self.translation
+    }
+
+    // A rigid motion when rotation is orthogonal, this isn't checked
+    pub fn from_rotation_translation(rotation: Matrix<T, N, N>, translation: List<T, N>) -> Self {
+        Self::new(rotation, translation)
+    }
+}
+
+impl<T, const N: usize> Affine<T, N>
+where
+    T: Zero + One + Copy,
+{
+    pub fn identity() -> Self {
+        Self::new(Matrix::identity(), List::ZERO)
+    }
+
+    // Scales coordinate i by factors[i] around the origin
+    pub fn from_scale(factors: List<T, N>) -> Self {
+        Self::new(
+            Matrix::from_fn(|i, j| if i == j { factors[i] } else { T::ZERO }),
+            List::ZERO,
+        )
+    }
+
+    pub fn from_translation(translation: List<T, N>) -> Self {
+        Self::new(Matrix::identity(), translation)
+    }
+
+    // The homogeneous matrix ( linear translation ) with M = N + 1
+    //                        ( 0      1           )
+    fn homogeneous<const M: usize>(&self) -> Matrix<T, M, M> {
+        const { assert!(M == N + 1) };
+        Matrix::from_fn(|i, j| match (i < N, j < N) {
+            (true, true) => self.linear[(i, j)],
+            (true, false) => self.translation[i],
+            (false, true) => T::ZERO,
+            (false, false) => T::ONE,
+        })
+    }
+
+    // None unless the last row of m is (0, ..., 0, 1)
+    fn from_homogeneous<const M: usize>(m: &Matrix<T, M, M>) -> Option<Self>
+    where
+        T: PartialEq,
+    {
+        const { assert!(M == N + 1) };
+        let affine = (0..N).all(|j| m[(N, j)] == T::ZERO) && m[(N, N)] == T::ONE;
+        affine.then(|| {
+            Self::new(
+                Matrix::from_fn(|i, j| m[(i, j)]),
+                List::from_fn(|i| m[(i, N)]),
+            )
+        })
+    }
+}
+
+impl<T: Zero + One + PartialEq + Copy> Affine<T, 2> {
+    pub fn to_homogeneous(&self) -> Matrix<T, 3, 3> {
+        self.homogeneous()
+    }
+
+    pub fn try_from_homogeneous(m: &Matrix<T, 3, 3>) -> Option<Self> {
+        Self::from_homogeneous(m)
+    }
+}
+
+impl<T: Zero + One + PartialEq + Copy> Affine<T, 3> {
+    pub fn to_homogeneous(&self) -> Matrix<T, 4, 4> {
+        self.homogeneous()
+    }
+
+    pub fn try_from_homogeneous(m: &Matrix<T, 4, 4>) -> Option<Self> {
+        Self::from_homogeneous(m)
+    }
+}
+
+impl<T, const N: usize> Affine<T, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    pub fn apply_point(&self, p: &List<T, N>) -> List<T, N> {
+        self.linear * *p + self.translation
+    }
+
+    // Vectors are differences of points, the translation cancels out
+    pub fn apply_vector(&self, v: &List<T, N>) -> List<T, N> {
+        self.linear * *v
+    }
+}
+
+impl<T, const N: usize> Affine<T, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + AddInverse + Divide + Modulus + Zero + One + Copy,
+{
+    // x = linear^-1 (y - translation), fails when the linear part is singular
+    pub fn inverse(&self) -> Result<Self, SolveError> {
+        let linear = self.linear.invert()?;
+        let moved = linear * self.translation;
+        Ok(Self::new(linear, List::from_fn(|i| moved[i].negate())))
+    }
+}
+
+// (self * rhs) x = self(rhs(x)), rhs is applied first like with matrices
+impl<T, const N: usize> Mul for Affine<T, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(self.linear * rhs.linear, self.apply_point(&rhs.translation))
+    }
+}
+
+// Transforms act on points with their translation and on vectors without it
+impl<T, const N: usize> Mul<Point<T, N>> for Affine<T, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = Point<T, N>;
+
+    fn mul(self, rhs: Point<T, N>) -> Self::Output {
+        Point(self.apply_point(&rhs.0))
+    }
+}
+
+impl<T, const N: usize> Mul<Vector<T, N>> for Affine<T, N>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = Vector<T, N>;
+
+    fn mul(self, rhs: Vector<T, N>) -> Self::Output {
+        Vector(self.apply_vector(&rhs.0))
+    }
+}
+
+// The same for homogeneous matrices. A point comes back with a last coordinate w that is 1 for
+// affine matrices and gets divided out for projective ones. A vector keeps w = 0 under affine
+// matrices, its last coordinate is dropped.
+impl<T> Mul<Point<T, 2>> for Matrix<T, 3, 3>
+where
+    T: Add<Output = T> + Mul<Output = T> + Divide + Zero + One + Copy,
+{
+    type Output = Point<T, 2>;
+
+    fn mul(self, rhs: Point<T, 2>) -> Self::Output {
+        let h = self * rhs.to_homogeneous();
+        Point(List::from_fn(|i| h[i].divide(h[2])))
+    }
+}
+
+impl<T> Mul<Vector<T, 2>> for Matrix<T, 3, 3>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = Vector<T, 2>;
+
+    fn mul(self, rhs: Vector<T, 2>) -> Self::Output {
+        let h = self * rhs.to_homogeneous();
+        Vector(List::from_fn(|i| h[i]))
+    }
+}
+
+impl<T> Mul<Point<T, 3>> for Matrix<T, 4, 4>
+where
+    T: Add<Output = T> + Mul<Output = T> + Divide + Zero + One + Copy,
+{
+    type Output = Point<T, 3>;
+
+    fn mul(self, rhs: Point<T, 3>) -> Self::Output {
+        let h = self * rhs.to_homogeneous();
+        Point(List::from_fn(|i| h[i].divide(h[3])))
+    }
+}
+
+impl<T> Mul<Vector<T, 3>> for Matrix<T, 4, 4>
+where
+    T: Add<Output = T> + Mul<Output = T> + Zero + Copy,
+{
+    type Output = Vector<T, 3>;
+
+    fn mul(self, rhs: Vector<T, 3>) -> Self::Output {
+        let h = self * rhs.to_homogeneous();
+        Vector(List::from_fn(|i| h[i]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Affine, Line, Plane, Point, Vector, barycentric_coords, barycentric_coords_tetrahedron,
+        from_barycentric, line_line_closest_points, line_plane_intersection, parallelepiped_volume,
+        point_plane_distance, project_point_onto_line, volume_from_gram_determinant,
+    };
+    use crate::chapters::c1::definitions::{List, MulScalar, Zero};
+    use crate::chapters::c3::matrix::Matrix;
+    use crate::chapters::c3::solve::SolveError;
+    use std::f64::consts::PI;
+
+    fn list<const N: usize>(elems: [f64; N]) -> List<f64, N> {
+        List::new(elems)
+    }
+
+    fn assert_coords<const N: usize>(actual: &List<f64, N>, expected: [f64; N]) {
+        for i in 0..N {
+            assert_eq!(actual[i], expected[i], "coordinate {i}");
+        }
+    }
+
+    #[test]
+    fn affine_operations() {
+        let p = Point::new(list([1.0, 2.0, 3.0]));
+        let q = Point::new(list([4.0, 0.0, -1.0]));
+        let v = Vector::new(list([0.5, 0.5, 0.5]));
+
+        let d = q - p;
+        assert_coords(d.coords(), [3.0, -2.0, -4.0]);
+        assert_coords((p + d).coords(), [4.0, 0.0, -1.0]);
+        assert_coords((q - d).coords(), [1.0, 2.0, 3.0]);
+        assert_coords((d + v).coords(), [3.5, -1.5, -3.5]);
+        assert_coords((d - v).coords(), [2.5, -2.5, -4.5]);
+        assert_coords((-v).coords(), [-0.5, -0.5, -0.5]);
+        assert_coords(MulScalar::mul(v, 4.0).coords(), [2.0, 2.0, 2.0]);
+        assert_eq!(p[2], 3.0);
+    }
+
+    #[test]
+    fn homogeneous_coordinates() {
+        let p = Point::new(list([1.0, 2.0]));
+        let v = Vector::new(list([1.0, 2.0]));
+        assert_coords(&p.to_homogeneous(), [1.0, 2.0, 1.0]);
+        assert_coords(&v.to_homogeneous(), [1.0, 2.0, 0.0]);
+
+        let p = Point::new(list([1.0, 2.0, 3.0]));
+        let v = Vector::new(list([1.0, 2.0, 3.0]));
+        assert_coords(&p.to_homogeneous(), [1.0, 2.0, 3.0, 1.0]);
+        assert_coords(&v.to_homogeneous(), [1.0, 2.0, 3.0, 0.0]);
+    }
+
+    fn assert_close<const N: usize>(actual: &List<f64, N>, expected: [f64; N]) {
+        for i in 0..N {
+            assert!(
+                (actual[i] - expected[i]).abs() < 1e-12,
+                "coordinate {i}: {} != {}",
+                actual[i],
+                expected[i]
+            );
+        }
+    }
+
+    #[test]
+    fn triangle_barycentric() {
+        let tri = [list([0.0, 0.0]), list([4.0, 0.0]), list([1.0, 3.0])];
+
+        for (i, vertex) in tri.iter().enumerate() {
+            let coords = barycentric_coords(*vertex, tri).unwrap();
+            let mut expected = [0.0; 3];
+            expected[i] = 1.0;
+            assert_close(&coords, expected);
+        }
+
+        let centroid = list([5.0 / 3.0, 1.0]);
+        assert_close(
+            &barycentric_coords(centroid, tri).unwrap(),
+            [1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0],
+        );
+
+        // Midpoint of the edge a-b has an exact zero for c
+        let coords = barycentric_coords(list([2.0, 0.0]), tri).unwrap();
+        assert_close(&coords, [0.5, 0.5, 0.0]);
+
+        // Outside points are not clamped
+        let coords = barycentric_coords(list([2.0, -3.0]), tri).unwrap();
+        assert!(coords[2] < 0.0);
+        assert_close(&from_barycentric(coords, tri), [2.0, -3.0]);
+    }
+
+    #[test]
+    fn triangle_round_trip() {
+        let tri = [list([-1.5, 0.25]), list([2.0, 1.0]), list([0.5, -2.0])];
+        for p in [list([0.1, 0.2]), list([10.0, -7.0]), list([-1.5, 0.25])] {
+            let coords = barycentric_coords(p, tri).unwrap();
+            assert!((coords[0] + coords[1] + coords[2] - 1.0).abs() < 1e-12);
+            assert_close(&from_barycentric(coords, tri), [p[0], p[1]]);
+        }
+    }
+
+    #[test]
+    fn degenerate_triangle() {
+        let collinear = [list([0.0, 0.0]), list([1.0, 1.0]), list([3.0, 3.0])];
+        assert!(barycentric_coords(list([0.5, 0.0]), collinear).is_none());
+
+        let repeated = [list([1.0, 2.0]), list([1.0, 2.0]), list([3.0, 0.0])];
+        assert!(barycentric_coords(list([0.5, 0.0]), repeated).is_none());
+    }
+
+    #[test]
+    fn tetrahedron_barycentric() {
+        let tet = [
+            list([0.0, 0.0, 0.0]),
+            list([2.0, 0.0, 0.0]),
+            list([0.0, 3.0, 0.0]),
+            list([0.0, 0.0, 1.0]),
+        ];
+
+        for (i, vertex) in tet.iter().enumerate() {
+            let coords = barycentric_coords_tetrahedron(*vertex, tet).unwrap();
+            let mut expected = [0.0; 4];
+            expected[i] = 1.0;
+            assert_close(&coords, expected);
+        }
+
+        let centroid = list([0.5, 0.75, 0.25]);
+        assert_close(
+            &barycentric_coords_tetrahedron(centroid, tet).unwrap(),
+            [0.25; 4],
+        );
+
+        let p = list([-1.0, 0.5, 2.0]);
+        let coords = barycentric_coords_tetrahedron(p, tet).unwrap();
+        assert!(coords[0] < 0.0 || coords[1] < 0.0);
+        assert_close(&from_barycentric(coords, tet), [-1.0, 0.5, 2.0]);
+
+        let flat = [tet[0], tet[1], tet[2], list([1.0, 1.0, 0.0])];
+        assert!(barycentric_coords_tetrahedron(p, flat).is_none());
+    }
+
+    #[test]
+    fn line_plane() {
+        let plane = Plane::new(list([0.0, 0.0, 2.0]), 4.0); // z = 2
+        let line = Line::through(list([1.0, 1.0, 0.0]), list([2.0, 3.0, 1.0]));
+        let p = line_plane_intersection(&line, &plane).unwrap();
+        assert_close(&p, [3.0, 5.0, 2.0]);
+        assert_eq!(point_plane_distance(&p, &plane), 0.0);
+
+        // Parallel and contained lines have no single intersection
+        let parallel = Line::new(list([0.0, 0.0, 5.0]), list([1.0, -1.0, 0.0]));
+        assert!(line_plane_intersection(&parallel, &plane).is_none());
+        let contained = Line::new(list([0.0, 0.0, 2.0]), list([1.0, 0.0, 0.0]));
+        assert!(line_plane_intersection(&contained, &plane).is_none());
+    }
+
+    #[test]
+    fn plane_distance() {
+        let plane = Plane::from_point_normal(list([0.0, 0.0, 1.0]), list([0.0, 0.0, -3.0]));
+        assert_eq!(point_plane_distance(&list([5.0, -2.0, 4.0]), &plane), 3.0);
+        assert_eq!(point_plane_distance(&list([5.0, -2.0, -1.0]), &plane), 2.0);
+        assert_eq!(point_plane_distance(&list([7.0, 8.0, 1.0]), &plane), 0.0);
+
+        let tilted = Plane::new(list([1.0, 1.0, 1.0]), 0.0);
+        let d = point_plane_distance(&list([1.0, 1.0, 1.0]), &tilted);
+        assert!((d - 3f64.sqrt()).abs() < 1e-15);
+    }
+
+    #[test]
+    fn skew_lines() {
+        // The x axis and the line y = 1 parallel to z, 1 apart
+        let l1 = Line::new(list([0.0, 0.0, 0.0]), list([1.0, 0.0, 0.0]));
+        let l2 = Line::new(list([3.0, 1.0, 5.0]), list([0.0, 0.0, 2.0]));
+        let (p1, p2, d) = line_line_closest_points(&l1, &l2);
+        assert_close(&p1, [3.0, 0.0, 0.0]);
+        assert_close(&p2, [3.0, 1.0, 0.0]);
+        assert!((d - 1.0).abs() < 1e-12);
+
+        // Intersecting lines are at distance 0
+        let l3 = Line::through(list([0.0, -1.0, 0.0]), list([2.0, 1.0, 0.0]));
+        let (p1, p2, d) = line_line_closest_points(&l1, &l3);
+        assert_close(&p1, [1.0, 0.0, 0.0]);
+        assert_close(&p2, [1.0, 0.0, 0.0]);
+        assert!(d.abs() < 1e-12);
+    }
+
+    #[test]
+    fn parallel_lines() {
+        let l1 = Line::new(list([1.0, 0.0]), list([1.0, 1.0]));
+        let l2 = Line::new(list([0.0, 3.0]), list([-2.0, -2.0]));
+        let (p1, p2, d) = line_line_closest_points(&l1, &l2);
+        assert_close(&p1, [1.0, 0.0]);
+        assert_close(&p2, [-1.0, 2.0]);
+        assert!((d - 8f64.sqrt()).abs() < 1e-12);
+
+        let (_, _, d) = line_line_closest_points(&l1, &l1);
+        assert_eq!(d, 0.0);
+    }
+
+    #[test]
+    fn point_onto_line() {
+        let line = Line::new(list([1.0, 1.0, 1.0]), list([0.0, 2.0, 0.0]));
+        assert_close(
+            &project_point_onto_line(&list([4.0, -3.0, 1.0]), &line),
+            [1.0, -3.0, 1.0],
+        );
+        assert_close(
+            &project_point_onto_line(&list([1.0, 7.0, 1.0]), &line),
+            [1.0, 7.0, 1.0],
+        );
+    }
+
+    #[test]
+    fn unit_cube_volume() {
+        let cube = [
+            list([1.0, 0.0, 0.0]),
+            list([0.0, 1.0, 0.0]),
+            list([0.0, 0.0, 1.0]),
+        ];
+        assert_eq!(parallelepiped_volume(&cube), 1.0);
+        assert_eq!(parallelepiped_volume(&cube[..2]), 1.0);
+        assert_eq!(parallelepiped_volume(&cube[..1]), 1.0);
+        assert_eq!(parallelepiped_volume::<3>(&[]), 1.0);
+
+        let skewed = [
+            list([2.0, 0.0, 0.0]),
+            list([1.0, 3.0, 0.0]),
+            list([5.0, -1.0, 0.5]),
+        ];
+        assert!((parallelepiped_volume(&skewed) - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn parallelogram_area() {
+        let u = list([1.0, 2.0, 3.0]);
+        let v = list([-2.0, 0.5, 4.0]);
+        let cross = [
+            u[1] * v[2] - u[2] * v[1],
+            u[2] * v[0] - u[0] * v[2],
+            u[0] * v[1] - u[1] * v[0],
+        ];
+        let area = cross.iter().map(|x| x * x).sum::<f64>().sqrt();
+        assert!((parallelepiped_volume(&[u, v]) - area).abs() < 1e-12);
+        assert_eq!(parallelepiped_volume(&[list([3.0, 4.0, 0.0])]), 5.0);
+    }
+
+    #[test]
+    fn dependent_vectors() {
+        let u = list([0.1, 0.2, 0.3, 0.7]);
+        for c in [3.0, -1.0 / 3.0, 1e5, 0.1] {
+            let mut v = List::ZERO;
+            v.axpy(c, &u);
+            let volume = parallelepiped_volume(&[u, v]);
+            assert!((0.0..1e-6).contains(&volume), "{volume}");
+        }
+
+        let w = list([1.0, 1.0, 1.0]);
+        let mut sum = w;
+        sum.add_assign_ref(&list([0.0, 1.0, 2.0]));
+        assert!(parallelepiped_volume(&[w, list([0.0, 1.0, 2.0]), sum]).abs() < 1e-12);
+
+        let too_many = [list([1.0, 0.0]), list([0.0, 1.0]), list([1.0, 1.0])];
+        assert_eq!(parallelepiped_volume(&too_many), 0.0);
+    }
+
+    #[test]
+    fn gram_determinant_clamping() {
+        assert_eq!(volume_from_gram_determinant(-1e-18), 0.0);
+        assert_eq!(volume_from_gram_determinant(0.0), 0.0);
+        assert_eq!(volume_from_gram_determinant(4.0), 2.0);
+    }
+
+    fn rotation(angle: f64) -> Matrix<f64, 2, 2> {
+        let (s, c) = angle.sin_cos();
+        Matrix::new([[c, -s], [s, c]])
+    }
+
+    #[test]
+    fn affine_composition() {
+        let rotate = Affine::from_rotation_translation(rotation(PI / 2.0), List::ZERO);
+        let shift = Affine::from_translation(list([1.0, 0.0]));
+        let p = list([1.0, 0.0]);
+
+        // The right factor is applied first
+        assert_close(&(rotate * shift).apply_point(&p), [0.0, 2.0]);
+        assert_close(&(shift * rotate).apply_point(&p), [1.0, 1.0]);
+        let twice = Affine::from_scale(list([2.0, 3.0])) * Affine::from_scale(list([0.5, 2.0]));
+        assert_eq!(twice, Affine::from_scale(list([1.0, 6.0])));
+        assert_eq!(Affine::identity() * shift, shift);
+
+        // Vectors only see the linear part
+        let motion = Affine::from_rotation_translation(rotation(0.3), list([5.0, -2.0]));
+        let v = list([1.0, 2.0]);
+        assert_eq!(motion.apply_vector(&v), *motion.linear() * v);
+        let (a, b) = (list([0.5, 1.0]), list([1.5, 3.0]));
+        let moved = motion.apply_point(&b) + -motion.apply_point(&a);
+        assert_close(&moved, motion.apply_vector(&(b + -a)).into_array());
+    }
+
+    #[test]
+    fn affine_inverse() {
+        let t = Affine::new(Matrix::new([[2.0, 1.0], [0.5, 3.0]]), list([1.0, -4.0]));
+        let inverse = t.inverse().unwrap();
+        for p in [list([0.0, 0.0]), list([1.5, -2.0]), list([10.0, 3.0])] {
+            assert_close(&inverse.apply_point(&t.apply_point(&p)), p.into_array());
+            assert_close(&t.apply_point(&inverse.apply_point(&p)), p.into_array());
+        }
+
+        let flat = Affine::new(Matrix::new([[1.0, 2.0], [2.0, 4.0]]), list([1.0, 1.0]));
+        assert_eq!(flat.inverse(), Err(SolveError::Singular));
+    }
+
+    #[test]
+    fn affine_homogeneous() {
+        let t = Affine::new(Matrix::new([[2.0, 1.0], [0.5, 3.0]]), list([1.0, -4.0]));
+        let h = t.to_homogeneous();
+        assert_eq!(
+            h,
+            Matrix::new([[2.0, 1.0, 1.0], [0.5, 3.0, -4.0], [0.0, 0.0, 1.0]])
+        );
+        assert_eq!(Affine::<_, 2>::try_from_homogeneous(&h), Some(t));
+
+        // The homogeneous product is the composition
+        let s = Affine::from_rotation_translation(rotation(1.0), list([0.0, 2.0]));
+        assert_eq!((s * t).to_homogeneous(), s.to_homogeneous() * h);
+
+        let t = Affine::new(Matrix::<f64, 3, 3>::identity(), list([1.0, 2.0, 3.0]));
+        let h = t.to_homogeneous();
+        assert_eq!(h[(2, 3)], 3.0);
+        assert_eq!(Affine::<_, 3>::try_from_homogeneous(&h), Some(t));
+
+        // A projective matrix has no affine counterpart
+        let mut projective = h;
+        projective[(3, 0)] = 1.0;
+        assert_eq!(Affine::<_, 3>::try_from_homogeneous(&projective), None);
+    }
+
+    #[test]
+    fn transforms_points_and_vectors() {
+        let motion = Affine::from_rotation_translation(rotation(PI / 2.0), list([5.0, -2.0]));
+        let p = Point::new(list([1.0, 0.0]));
+        let v = Vector::new(list([1.0, 0.0]));
+        assert_close((motion * p).coords(), [5.0, -1.0]);
+        assert_close((motion * v).coords(), [0.0, 1.0]);
+
+        // The homogeneous matrix agrees with the affine map
+        let h = motion.to_homogeneous();
+        assert_close((h * p).coords(), [5.0, -1.0]);
+        assert_close((h * v).coords(), [0.0, 1.0]);
+
+        // Differences of points transform like vectors
+        let q = Point::new(list([3.0, 4.0]));
+        assert_close(
+            ((motion * q) - (motion * p)).coords(),
+            (motion * (q - p)).coords().into_array(),
+        );
+
+        let shift = Affine::from_translation(list([1.0, 2.0, 3.0])).to_homogeneous();
+        let p = Point::new(list([1.0, 1.0, 1.0]));
+        let v = Vector::new(list([1.0, 1.0, 1.0]));
+        assert_coords((shift * p).coords(), [2.0, 3.0, 4.0]);
+        assert_coords((shift * v).coords(), [1.0, 1.0, 1.0]);
+
+        // A perspective matrix divides by the last homogeneous coordinate
+        let mut perspective = Matrix::<f64, 4, 4>::identity();
+        perspective[(3, 2)] = 0.5;
+        perspective[(3, 3)] = 0.0;
+        assert_coords(
+            (perspective * Point::new(list([2.0, 4.0, 4.0]))).coords(),
+            [1.0, 2.0, 2.0],
+        );
+    }
+}
@@ -0,0 +1,398 @@
+use std::fmt::{Display, Formatter, LowerExp, Write as _};
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+use crate::chapters::c1::definitions::{List, WrongLength};
+use crate::chapters::c3::matrix::Matrix;
+
+// Numeric tables as exported by spreadsheets, NumPy's savetxt and the like.
+// Everything after a # is a comment, blank lines are skipped and fields are trimmed.
+// A whitespace delimiter splits on any run of spaces and tabs instead of single characters.
+// Writing uses Display, which for floats gives the fewest significant digits that parse back to
+// the same value, so from_csv_str(to_csv_string(m)) == m exactly. It never uses an exponent
+// though, so 1e300 takes 301 characters, to_csv_string_significant is the compact form.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    // Line numbers start at 1 and count the skipped lines too
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    WrongRowCount {
+        expected: usize,
+        found: usize,
+    },
+    WrongLength(WrongLength),
+    InvalidNumber {
+        line: usize,
+        text: String,
+    },
+    Io(std::io::ErrorKind),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(f, "line {line} has {found} fields, expected {expected}"),
+            ParseError::WrongRowCount { expected, found } => {
+                write!(f, "expected {expected} rows, found {found}")
+            }
+            ParseError::WrongLength(e) => e.fmt(f),
+            ParseError::InvalidNumber { line, text } => {
+                write!(f, "line {line}: {text:?} is not a number")
+            }
+            ParseError::Io(kind) => write!(f, "reading failed: {kind}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<std::io::Error> for ParseError {
+    fn from(e: std::io::Error) -> Self {
+        ParseError::Io(e.kind())
+    }
+}
+
+// The non empty lines with their line number, split into fields
+fn records(s: &str, delimiter: u8) -> impl Iterator<Item = (usize, Vec<&str>)> {
+    s.lines().enumerate().filter_map(move |(i, line)| {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            return None;
+        }
+        let fields = if delimiter.is_ascii_whitespace() {
+            line.split_whitespace().collect()
+        } else {
+            line.split(char::from(delimiter)).map(str::trim).collect()
+        };
+        Some((i + 1, fields))
+    })
+}
+
+fn parse_field<T: FromStr>(line: usize, text: &str) -> Result<T, ParseError> {
+    text.parse().map_err(|_| ParseError::InvalidNumber {
+        line,
+        text: text.to_string(),
+    })
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C>
+where
+    T: FromStr,
+{
+    // One row per line, the table has to be exactly R x C
+    pub fn from_csv_str(s: &str, delimiter: u8) -> Result<Self, ParseError> {
+        let mut records = records(s, delimiter);
+        let mut found = 0;
+        let rows = List::try_from_fn(|_| {
+            let (line, fields) = records
+                .next()
+                .ok_or(ParseError::WrongRowCount { expected: R, found })?;
+            found += 1;
+            if fields.len() != C {
+                return Err(ParseError::Ragged {
+                    line,
+                    expected: C,
+                    found: fields.len(),
+                });
+            }
+            List::try_from_fn(|j| parse_field(line, fields[j]))
+        })?;
+
+        let extra = records.count();
+        if extra > 0 {
+            return Err(ParseError::WrongRowCount {
+                expected: R,
+                found: R + extra,
+            });
+        }
+        Ok(Matrix::from_rows(rows.into_array()))
+    }
+
+    pub fn from_reader(mut reader: impl BufRead, delimiter: u8) -> Result<Self, ParseError> {
+        let mut s = String::new();
+        reader.read_to_string(&mut s)?;
+        Self::from_csv_str(&s, delimiter)
+    }
+}
+
+impl<T, const N: usize> List<T, N>
+where
+    T: FromStr,
+{
+    // N numbers separated by any whitespace, newlines included, comments are allowed
+    pub fn from_str_ws(s: &str) -> Result<Self, ParseError> {
+        let mut fields = records(s, b' ')
+            .flat_map(|(line, fields)| fields.into_iter().map(move |text| (line, text)));
+        let mut found = 0;
+        let list = List::try_from_fn(|_| {
+            let (line, text) = fields
+                .next()
+                .ok_or(ParseError::WrongLength(WrongLength { expected: N, found }))?;
+            found += 1;
+            parse_field(line, text)
+        })?;
+        match fields.next() {
+            None => Ok(list),
+            Some(_) => Err(ParseError::WrongLength(WrongLength {
+                expected: N,
+                found: N + 1,
+            })),
+        }
+    }
+}
+
+fn write_rows<T, const R: usize, const C: usize>(
+    m: &Matrix<T, R, C>,
+    delimiter: u8,
+    mut entry: impl FnMut(&mut String, &T) -> std::fmt::Result,
+) -> String {
+    let mut out = String::new();
+    for i in 0..R {
+        for (j, x) in m.row(i).iter().enumerate() {
+            if j > 0 {
+                out.push(char::from(delimiter));
+            }
+            // Writing to a String can't fail
+            entry(&mut out, x).unwrap();
+        }
+        out.push('\n');
+    }
+    out
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C>
+where
+    T: Display,
+{
+    // One line per row, every line ends with a newline
+    pub fn to_csv_string(&self, delimiter: u8) -> String {
+        write_rows(self, delimiter, |out, x| write!(out, "{x}"))
+    }
+
+    pub fn write_csv(&self, mut writer: impl Write, delimiter: u8) -> std::io::Result<()> {
+        writer.write_all(self.to_csv_string(delimiter).as_bytes())
+    }
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C>
+where
+    T: LowerExp,
+{
+    // Every entry rounded to the given number of significant digits, in scientific notation
+    // so all columns look alike. Files written this way diff well, but no longer round trip.
+    pub fn to_csv_string_significant(&self, delimiter: u8, digits: usize) -> String {
+        let precision = digits.max(1) - 1;
+        write_rows(self, delimiter, |out, x| write!(out, "{x:.precision$e}"))
+    }
+}
+
+impl<T, const N: usize> List<T, N>
+where
+    T: Display,
+{
+    // One element per line, a column that from_str_ws and Matrix<T, N, 1> both read back
+    pub fn to_string_ws(&self) -> String {
+        self.iter().map(|x| format!("{x}\n")).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ParseError;
+    use crate::chapters::c1::definitions::{List, WrongLength};
+    use crate::chapters::c3::matrix::Matrix;
+
+    const FIXTURE: &str = "\
+# exported by some other tool
+# x, y, z
+
+1, 2.5, -3
+  4,5 ,6   # trailing comment
+
+-1e-3, 2.5E2, 7
+";
+
+    #[test]
+    fn comments_and_blank_lines() {
+        let m = Matrix::<f64, 3, 3>::from_csv_str(FIXTURE, b',').unwrap();
+        assert_eq!(
+            m,
+            Matrix::new([[1.0, 2.5, -3.0], [4.0, 5.0, 6.0], [-0.001, 250.0, 7.0]])
+        );
+
+        let ints = Matrix::<i32, 2, 3>::from_csv_str("1\t2  3\n\n 4 5\t\t6\n", b' ').unwrap();
+        assert_eq!(ints, Matrix::new([[1, 2, 3], [4, 5, 6]]));
+        let tabs = Matrix::<i32, 2, 3>::from_csv_str("1\t2\t3\n4\t5\t6", b'\t').unwrap();
+        assert_eq!(tabs, ints);
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let s = "1, 2, 3\n# comment\n\n4, 5\n6, 7, 8";
+        assert_eq!(
+            Matrix::<f64, 3, 3>::from_csv_str(s, b','),
+            Err(ParseError::Ragged {
+                line: 4,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            Matrix::<f64, 3, 3>::from_csv_str(s, b',')
+                .unwrap_err()
+                .to_string(),
+            "line 4 has 2 fields, expected 3"
+        );
+    }
+
+    #[test]
+    fn wrong_shape() {
+        assert_eq!(
+            Matrix::<f64, 4, 3>::from_csv_str(FIXTURE, b','),
+            Err(ParseError::WrongRowCount {
+                expected: 4,
+                found: 3
+            })
+        );
+        assert_eq!(
+            Matrix::<f64, 2, 3>::from_csv_str(FIXTURE, b','),
+            Err(ParseError::WrongRowCount {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            Matrix::<f64, 1, 2>::from_csv_str("1,x", b','),
+            Err(ParseError::InvalidNumber {
+                line: 1,
+                text: "x".to_string()
+            })
+        );
+        // An empty field is not a zero
+        assert!(Matrix::<f64, 1, 3>::from_csv_str("1,,3", b',').is_err());
+    }
+
+    #[test]
+    fn lists() {
+        let v = List::<f64, 5>::from_str_ws("1 2.5e-3\n# comment\n\t-4E1   6\n7 # done").unwrap();
+        assert_eq!(v, List::new([1.0, 0.0025, -40.0, 6.0, 7.0]));
+        assert_eq!(
+            List::<f64, 6>::from_str_ws("1 2 3\n4 5"),
+            Err(ParseError::WrongLength(WrongLength {
+                expected: 6,
+                found: 5
+            }))
+        );
+        assert_eq!(
+            List::<f64, 2>::from_str_ws("1 2 3"),
+            Err(ParseError::WrongLength(WrongLength {
+                expected: 2,
+                found: 3
+            }))
+        );
+        assert_eq!(
+            List::<i64, 2>::from_str_ws("1\n2.5"),
+            Err(ParseError::InvalidNumber {
+                line: 2,
+                text: "2.5".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn readers() {
+        let m = Matrix::<f64, 3, 3>::from_reader(FIXTURE.as_bytes(), b',').unwrap();
+        assert_eq!(m, Matrix::from_csv_str(FIXTURE, b',').unwrap());
+
+        // Invalid UTF-8 is a read error
+        let bytes: &[u8] = &[b'1', 0xff, b'\n'];
+        assert_eq!(
+            Matrix::<f64, 1, 1>::from_reader(bytes, b','),
+            Err(ParseError::Io(std::io::ErrorKind::InvalidData))
+        );
+    }
+
+    // xorshift64, deterministic and good enough to hit all kinds of bit patterns
+    fn random_bits(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..200 {
+            // Any finite f64, subnormals and huge exponents included
+            let m = Matrix::<f64, 4, 3>::from_fn(|_, _| {
+                loop {
+                    let x = f64::from_bits(random_bits(&mut state));
+                    if x.is_finite() {
+                        break x;
+                    }
+                }
+            });
+            for delimiter in [b',', b' ', b'\t', b';'] {
+                let text = m.to_csv_string(delimiter);
+                assert_eq!(Matrix::from_csv_str(&text, delimiter), Ok(m), "{text}");
+            }
+
+            let v = List::<f64, 5>::from_fn(|_| f64::from_bits(random_bits(&mut state) >> 2));
+            assert_eq!(List::from_str_ws(&v.to_string_ws()), Ok(v));
+            let column = Matrix::<f64, 5, 1>::from_csv_str(&v.to_string_ws(), b',').unwrap();
+            assert_eq!(List::from_fn(|i| column[(i, 0)]), v);
+        }
+
+        let mut written = Vec::new();
+        let m = Matrix::new([[1, -2], [30, 4]]);
+        m.write_csv(&mut written, b',').unwrap();
+        assert_eq!(Matrix::from_reader(written.as_slice(), b','), Ok(m));
+    }
+
+    #[test]
+    fn extreme_magnitudes() {
+        let m = Matrix::new([[1e300, -1e-300], [f64::MAX, 5e-324]]);
+        let text = m.to_csv_string(b',');
+        assert_eq!(Matrix::from_csv_str(&text, b','), Ok(m));
+
+        // All the digits are written out, 1 and 300 zeros
+        let first = text.split(',').next().unwrap();
+        assert_eq!(first.len(), 301);
+        assert!(first.starts_with('1') && first[1..].bytes().all(|b| b == b'0'));
+        let second = text.lines().next().unwrap().split(',').nth(1).unwrap();
+        assert_eq!(second.len(), "-0.".len() + 299 + 1);
+
+        assert_eq!(
+            m.to_csv_string_significant(b',', 2),
+            "1.0e300,-1.0e-300\n1.8e308,4.9e-324\n"
+        );
+    }
+
+    #[test]
+    fn formatted_output() {
+        let m = Matrix::new([[1.0, -0.1, 1e-7], [123456.789, 0.0, -2.5e10]]);
+        assert_eq!(
+            m.to_csv_string(b','),
+            "1,-0.1,0.0000001\n123456.789,0,-25000000000\n"
+        );
+        assert_eq!(
+            m.to_csv_string_significant(b' ', 3),
+            "1.00e0 -1.00e-1 1.00e-7\n1.23e5 0.00e0 -2.50e10\n"
+        );
+        assert_eq!(
+            Matrix::new([[2, 3]]).to_csv_string_significant(b',', 1),
+            "2e0,3e0\n"
+        );
+        assert_eq!(List::new([1.5, -2.0]).to_string_ws(), "1.5\n-2\n");
+        assert_eq!(Matrix::<f64, 0, 2>::new([]).to_csv_string(b','), "");
+    }
+}
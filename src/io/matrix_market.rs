@@ -0,0 +1,351 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::io::{BufRead, Write};
+use std::ops::Add;
+use std::str::FromStr;
+
+use crate::chapters::c1::definitions::Zero;
+use crate::chapters::c3::matrix::Matrix;
+
+// The coordinate format of Matrix Market (.mtx) files, as used by SciPy's mmread/mmwrite and
+// the SuiteSparse collection:
+//  %%MatrixMarket matrix coordinate real general
+//  % comments
+//  rows cols entries
+//  i j value            <- one line per entry, indices start at 1
+// Entries that are not listed are zero. A symmetric file only lists the lower triangle.
+// Only matrices of the declared R x C fit, there is no sparse matrix type to read into.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MtxError {
+    // The first line is not a %%MatrixMarket banner
+    MissingHeader,
+    // A valid banner for something other than a real or integer coordinate matrix,
+    // e.g. the dense array format, complex or pattern entries
+    Unsupported(String),
+    // Nothing but comments and blank lines after the banner
+    MissingSize,
+    WrongSize {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    WrongEntryCount {
+        expected: usize,
+        found: usize,
+    },
+    // Line numbers start at 1 and count the header and comments
+    Malformed {
+        line: usize,
+        text: String,
+    },
+    IndexOutOfRange {
+        line: usize,
+        index: (usize, usize),
+    },
+    Io(std::io::ErrorKind),
+}
+
+impl Display for MtxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MtxError::MissingHeader => write!(f, "missing %%MatrixMarket header"),
+            MtxError::Unsupported(header) => write!(f, "unsupported Matrix Market file: {header}"),
+            MtxError::MissingSize => write!(f, "missing size line"),
+            MtxError::WrongSize { expected, found } => write!(
+                f,
+                "expected a {} x {} matrix, found {} x {}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            MtxError::WrongEntryCount { expected, found } => {
+                write!(f, "expected {expected} entries, found {found}")
+            }
+            MtxError::Malformed { line, text } => write!(f, "line {line}: malformed {text:?}"),
+            MtxError::IndexOutOfRange { line, index } => {
+                write!(
+                    f,
+                    "line {line}: index ({}, {}) out of range",
+                    index.0, index.1
+                )
+            }
+            MtxError::Io(kind) => write!(f, "reading failed: {kind}"),
+        }
+    }
+}
+
+impl std::error::Error for MtxError {}
+
+impl From<std::io::Error> for MtxError {
+    fn from(e: std::io::Error) -> Self {
+        MtxError::Io(e.kind())
+    }
+}
+
+// The field name written into the header
+pub trait MtxField {
+    const FIELD: &'static str;
+}
+
+impl MtxField for f64 {
+    const FIELD: &'static str = "real";
+}
+
+impl MtxField for f32 {
+    const FIELD: &'static str = "real";
+}
+
+impl MtxField for i32 {
+    const FIELD: &'static str = "integer";
+}
+
+impl MtxField for i64 {
+    const FIELD: &'static str = "integer";
+}
+
+fn parse<T: FromStr>(line: usize, text: &str) -> Result<T, MtxError> {
+    text.parse().map_err(|_| MtxError::Malformed {
+        line,
+        text: text.to_string(),
+    })
+}
+
+// Checks the banner, returns whether the matrix is symmetric
+fn parse_header(header: &str) -> Result<bool, MtxError> {
+    let words: Vec<String> = header.split_whitespace().map(str::to_lowercase).collect();
+    if words.first().map(String::as_str) != Some("%%matrixmarket") {
+        return Err(MtxError::MissingHeader);
+    }
+    match words[1..].iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["matrix", "coordinate", "real" | "integer", "general"] => Ok(false),
+        ["matrix", "coordinate", "real" | "integer", "symmetric"] => Ok(true),
+        _ => Err(MtxError::Unsupported(header.trim().to_string())),
+    }
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C>
+where
+    T: FromStr + Add<Output = T> + Zero + Copy,
+{
+    // Repeated entries are added up, like SciPy does
+    pub fn read_matrix_market(reader: impl BufRead) -> Result<Self, MtxError> {
+        let mut lines = reader.lines().enumerate();
+        let symmetric = match lines.next() {
+            Some((_, header)) => parse_header(&header?)?,
+            None => return Err(MtxError::MissingHeader),
+        };
+
+        let mut lines = lines.filter_map(|(i, line)| match line {
+            Ok(line) if line.trim().is_empty() || line.starts_with('%') => None,
+            line => Some((i + 1, line)),
+        });
+
+        let (line, size) = lines.next().ok_or(MtxError::MissingSize)?;
+        let size = size?;
+        let numbers = size
+            .split_whitespace()
+            .map(|text| parse::<usize>(line, text))
+            .collect::<Result<Vec<_>, _>>()?;
+        let [rows, cols, entries] = numbers[..] else {
+            return Err(MtxError::Malformed { line, text: size });
+        };
+        if (rows, cols) != (R, C) {
+            return Err(MtxError::WrongSize {
+                expected: (R, C),
+                found: (rows, cols),
+            });
+        }
+
+        let mut m = Matrix::ZERO;
+        let mut found = 0;
+        for (line, text) in lines {
+            let text = text?;
+            let fields: Vec<&str> = text.split_whitespace().collect();
+            let [i, j, value] = fields[..] else {
+                return Err(MtxError::Malformed { line, text });
+            };
+            let index = (parse::<usize>(line, i)?, parse::<usize>(line, j)?);
+            if !(1..=R).contains(&index.0) || !(1..=C).contains(&index.1) {
+                return Err(MtxError::IndexOutOfRange { line, index });
+            }
+            let (i, j) = (index.0 - 1, index.1 - 1);
+            let value: T = parse(line, value)?;
+
+            m[(i, j)] = m[(i, j)] + value;
+            if symmetric && i != j {
+                m[(j, i)] = m[(j, i)] + value;
+            }
+            found += 1;
+        }
+
+        if found != entries {
+            return Err(MtxError::WrongEntryCount {
+                expected: entries,
+                found,
+            });
+        }
+        Ok(m)
+    }
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C>
+where
+    T: MtxField + Debug + Zero + PartialEq,
+{
+    // Every non zero entry in the general coordinate format, row by row.
+    // Debug switches floats to exponents when they get very small or large, unlike Display
+    pub fn write_matrix_market(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let entries: Vec<(usize, usize)> = (0..R)
+            .flat_map(|i| (0..C).map(move |j| (i, j)))
+            .filter(|&(i, j)| self[(i, j)] != T::ZERO)
+            .collect();
+
+        writeln!(
+            writer,
+            "%%MatrixMarket matrix coordinate {} general",
+            T::FIELD
+        )?;
+        writeln!(writer, "{R} {C} {}", entries.len())?;
+        for (i, j) in entries {
+            writeln!(writer, "{} {} {:?}", i + 1, j + 1, self[(i, j)])?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MtxError;
+    use crate::chapters::c3::matrix::Matrix;
+
+    const GENERAL: &str = "\
+%%MatrixMarket matrix coordinate real general
+% A 3 x 4 matrix with 5 entries
+%
+3 4 5
+1 1 1.5
+2 3 -2e-3
+3 4 7
+1 4 -1
+
+3 1 0.25
+";
+
+    const SYMMETRIC: &str = "\
+%%MatrixMarket matrix coordinate integer symmetric
+3 3 4
+1 1 2
+2 1 -1
+3 2 -1
+3 3 2
+";
+
+    fn read<const R: usize, const C: usize>(s: &str) -> Result<Matrix<f64, R, C>, MtxError> {
+        Matrix::read_matrix_market(s.as_bytes())
+    }
+
+    #[test]
+    fn general() {
+        assert_eq!(
+            read(GENERAL),
+            Ok(Matrix::new([
+                [1.5, 0.0, 0.0, -1.0],
+                [0.0, 0.0, -0.002, 0.0],
+                [0.25, 0.0, 0.0, 7.0]
+            ]))
+        );
+    }
+
+    #[test]
+    fn symmetric() {
+        let m = read::<3, 3>(SYMMETRIC).unwrap();
+        assert_eq!(
+            m,
+            Matrix::new([[2.0, -1.0, 0.0], [-1.0, 0.0, -1.0], [0.0, -1.0, 2.0]])
+        );
+        assert_eq!(m, m.transpose());
+
+        let ints = Matrix::<i64, 3, 3>::read_matrix_market(SYMMETRIC.as_bytes()).unwrap();
+        assert_eq!(ints[(1, 0)], -1);
+    }
+
+    #[test]
+    fn round_trip() {
+        let m = Matrix::new([[0.0, 1e-300, 0.0], [-3.5, 0.0, 1.0 / 3.0]]);
+        let mut written = Vec::new();
+        m.write_matrix_market(&mut written).unwrap();
+        assert_eq!(
+            String::from_utf8(written.clone()).unwrap(),
+            "%%MatrixMarket matrix coordinate real general\n2 3 3\n1 2 1e-300\n2 1 -3.5\n\
+             2 3 0.3333333333333333\n"
+        );
+        assert_eq!(read(&String::from_utf8(written).unwrap()), Ok(m));
+
+        let ints = Matrix::new([[0, 4], [-2, 0]]);
+        let mut written = Vec::new();
+        ints.write_matrix_market(&mut written).unwrap();
+        assert!(written.starts_with(b"%%MatrixMarket matrix coordinate integer general\n"));
+        assert_eq!(Matrix::read_matrix_market(written.as_slice()), Ok(ints));
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(read::<1, 1>(""), Err(MtxError::MissingHeader));
+        assert_eq!(read::<1, 1>("1 1 1\n1 1 2\n"), Err(MtxError::MissingHeader));
+        assert_eq!(
+            read::<1, 1>("%%MatrixMarket matrix array real general\n1 1\n2\n"),
+            Err(MtxError::Unsupported(
+                "%%MatrixMarket matrix array real general".to_string()
+            ))
+        );
+        assert!(matches!(
+            read::<1, 1>("%%MatrixMarket matrix coordinate complex general\n"),
+            Err(MtxError::Unsupported(_))
+        ));
+        // The banner is case insensitive
+        assert_eq!(
+            read::<1, 1>("%%MATRIXMARKET Matrix Coordinate Real General\n1 1 1\n1 1 2\n"),
+            Ok(Matrix::new([[2.0]]))
+        );
+
+        assert_eq!(
+            read::<3, 3>(GENERAL),
+            Err(MtxError::WrongSize {
+                expected: (3, 3),
+                found: (3, 4)
+            })
+        );
+        let header = "%%MatrixMarket matrix coordinate real general\n";
+        assert_eq!(read::<1, 1>(header), Err(MtxError::MissingSize));
+        assert_eq!(
+            read::<1, 1>(&format!("{header}% only a comment\n\n")),
+            Err(MtxError::MissingSize)
+        );
+        assert_eq!(MtxError::MissingSize.to_string(), "missing size line");
+        assert_eq!(
+            read::<2, 2>(&format!("{header}2 2 2\n1 1 1\n")),
+            Err(MtxError::WrongEntryCount {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            read::<2, 2>(&format!("{header}2 2 1\n0 1 1\n")),
+            Err(MtxError::IndexOutOfRange {
+                line: 3,
+                index: (0, 1)
+            })
+        );
+        assert_eq!(
+            read::<2, 2>(&format!("{header}2 2 1\n1 1\n")),
+            Err(MtxError::Malformed {
+                line: 3,
+                text: "1 1".to_string()
+            })
+        );
+        assert_eq!(
+            read::<2, 2>(&format!("{header}2 2\n")),
+            Err(MtxError::Malformed {
+                line: 2,
+                text: "2 2".to_string()
+            })
+        );
+    }
+}
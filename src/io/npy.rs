@@ -0,0 +1,370 @@
+use std::fmt::{Display, Formatter};
+use std::io::{Read, Write};
+
+use crate::chapters::c1::definitions::List;
+use crate::chapters::c3::matrix::Matrix;
+
+// NumPy's .npy format, version 1.0: the magic string \x93NUMPY, the version bytes 1 0, a
+// little endian u16 header length and a header that is a Python dict literal like
+//  {'descr': '<f8', 'fortran_order': False, 'shape': (2, 3), }
+// padded with spaces and a newline to a multiple of 64 bytes, followed by the raw entries.
+// Reading accepts little endian float64, float32 and int64 data in either order and converts
+// it to f64, int64 values past 2^53 get rounded. Writing always produces float64 in C order.
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NpyError {
+    BadMagic,
+    UnsupportedVersion(u8, u8),
+    // The header is not a dict with descr, fortran_order and shape, the string says what's off
+    BadHeader(String),
+    UnsupportedDtype(String),
+    WrongShape {
+        expected: Vec<usize>,
+        found: Vec<usize>,
+    },
+    // Includes UnexpectedEof when the data is shorter than the shape says
+    Io(std::io::ErrorKind),
+}
+
+impl Display for NpyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NpyError::BadMagic => write!(f, "not a .npy file"),
+            NpyError::UnsupportedVersion(major, minor) => {
+                write!(f, "unsupported .npy version {major}.{minor}")
+            }
+            NpyError::BadHeader(reason) => write!(f, "bad .npy header: {reason}"),
+            NpyError::UnsupportedDtype(descr) => write!(f, "unsupported dtype {descr:?}"),
+            NpyError::WrongShape { expected, found } => {
+                write!(f, "expected shape {expected:?}, found {found:?}")
+            }
+            NpyError::Io(kind) => write!(f, "reading failed: {kind}"),
+        }
+    }
+}
+
+impl std::error::Error for NpyError {}
+
+impl From<std::io::Error> for NpyError {
+    fn from(e: std::io::Error) -> Self {
+        NpyError::Io(e.kind())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dtype {
+    F8,
+    F4,
+    I8,
+}
+
+impl Dtype {
+    fn size(self) -> usize {
+        match self {
+            Dtype::F8 | Dtype::I8 => 8,
+            Dtype::F4 => 4,
+        }
+    }
+
+    fn decode(self, bytes: &[u8]) -> f64 {
+        match self {
+            Dtype::F8 => f64::from_le_bytes(bytes.try_into().unwrap()),
+            Dtype::F4 => f32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            Dtype::I8 => i64::from_le_bytes(bytes.try_into().unwrap()) as f64,
+        }
+    }
+}
+
+struct Header {
+    dtype: Dtype,
+    fortran_order: bool,
+    shape: Vec<usize>,
+}
+
+// The text after 'key': up to the end of the value, which is a quoted string, a tuple or a
+// bare word like True
+fn dict_value<'a>(dict: &'a str, key: &str) -> Result<&'a str, NpyError> {
+    let missing = || NpyError::BadHeader(format!("no {key}"));
+    let start = [format!("'{key}'"), format!("\"{key}\"")]
+        .iter()
+        .find_map(|quoted| dict.find(quoted.as_str()).map(|i| i + quoted.len()))
+        .ok_or_else(missing)?;
+    let rest = dict[start..].trim_start();
+    let rest = rest.strip_prefix(':').ok_or_else(missing)?.trim_start();
+
+    let end = match rest.chars().next() {
+        Some(quote @ ('\'' | '"')) => rest[1..].find(quote).map(|i| i + 2),
+        Some('(') => rest.find(')').map(|i| i + 1),
+        Some(_) => rest.find([',', '}']),
+        None => None,
+    };
+    let end = end.ok_or_else(|| NpyError::BadHeader(format!("unterminated {key}")))?;
+    Ok(rest[..end].trim())
+}
+
+fn parse_header(dict: &str) -> Result<Header, NpyError> {
+    let dict = dict.trim();
+    if !dict.starts_with('{') || !dict.ends_with('}') {
+        return Err(NpyError::BadHeader("not a dict".to_string()));
+    }
+
+    let descr = dict_value(dict, "descr")?;
+    let descr = descr.trim_matches(['\'', '"']);
+    let dtype = match descr {
+        "<f8" => Dtype::F8,
+        "<f4" => Dtype::F4,
+        "<i8" => Dtype::I8,
+        _ => return Err(NpyError::UnsupportedDtype(descr.to_string())),
+    };
+
+    let fortran_order = match dict_value(dict, "fortran_order")? {
+        "True" => true,
+        "False" => false,
+        other => return Err(NpyError::BadHeader(format!("fortran_order {other}"))),
+    };
+
+    let shape = dict_value(dict, "shape")?;
+    let shape = shape
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .ok_or_else(|| NpyError::BadHeader(format!("shape {shape}")))?;
+    let shape = shape
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse())
+        .collect::<Result<_, _>>()
+        .map_err(|_| NpyError::BadHeader(format!("shape ({shape})")))?;
+
+    Ok(Header {
+        dtype,
+        fortran_order,
+        shape,
+    })
+}
+
+// The header and the entries in file order
+fn read_entries(mut reader: impl Read, expected: &[usize]) -> Result<(Header, Vec<f64>), NpyError> {
+    let mut preamble = [0; 10];
+    reader.read_exact(&mut preamble)?;
+    if &preamble[..6] != MAGIC {
+        return Err(NpyError::BadMagic);
+    }
+    if preamble[6..8] != [1, 0] {
+        return Err(NpyError::UnsupportedVersion(preamble[6], preamble[7]));
+    }
+
+    let mut dict = vec![0; u16::from_le_bytes([preamble[8], preamble[9]]) as usize];
+    reader.read_exact(&mut dict)?;
+    let dict = String::from_utf8(dict).map_err(|_| NpyError::BadHeader("not ASCII".to_string()))?;
+    let header = parse_header(&dict)?;
+    if header.shape != expected {
+        return Err(NpyError::WrongShape {
+            expected: expected.to_vec(),
+            found: header.shape,
+        });
+    }
+
+    let mut data = vec![0; expected.iter().product::<usize>() * header.dtype.size()];
+    reader.read_exact(&mut data)?;
+    let entries = data
+        .chunks_exact(header.dtype.size())
+        .map(|bytes| header.dtype.decode(bytes))
+        .collect();
+    Ok((header, entries))
+}
+
+fn write_entries(
+    mut writer: impl Write,
+    shape: &str,
+    entries: impl Iterator<Item = f64>,
+) -> std::io::Result<()> {
+    let mut dict = format!("{{'descr': '<f8', 'fortran_order': False, 'shape': {shape}, }}");
+    // The newline ends the header, the spaces pad it to a multiple of 64
+    let unpadded = MAGIC.len() + 4 + dict.len() + 1;
+    dict.extend(std::iter::repeat_n(
+        ' ',
+        unpadded.next_multiple_of(64) - unpadded,
+    ));
+    dict.push('\n');
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&(dict.len() as u16).to_le_bytes())?;
+    writer.write_all(dict.as_bytes())?;
+    for x in entries {
+        writer.write_all(&x.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+impl<const R: usize, const C: usize> Matrix<f64, R, C> {
+    // A 2-D array of shape (R, C)
+    pub fn read_npy(reader: impl Read) -> Result<Self, NpyError> {
+        let (header, entries) = read_entries(reader, &[R, C])?;
+        Ok(if header.fortran_order {
+            Matrix::from_fn(|i, j| entries[j * R + i])
+        } else {
+            Matrix::from_fn(|i, j| entries[i * C + j])
+        })
+    }
+
+    pub fn write_npy(&self, writer: impl Write) -> std::io::Result<()> {
+        let entries = (0..R).flat_map(|i| (0..C).map(move |j| (i, j)));
+        write_entries(writer, &format!("({R}, {C})"), entries.map(|ij| self[ij]))
+    }
+}
+
+impl<const N: usize> List<f64, N> {
+    // A 1-D array of shape (N,)
+    pub fn read_npy(reader: impl Read) -> Result<Self, NpyError> {
+        let (_, entries) = read_entries(reader, &[N])?;
+        Ok(List::from_fn(|i| entries[i]))
+    }
+
+    pub fn write_npy(&self, writer: impl Write) -> std::io::Result<()> {
+        write_entries(writer, &format!("({N},)"), self.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NpyError;
+    use crate::chapters::c1::definitions::List;
+    use crate::chapters::c3::matrix::Matrix;
+
+    // The bytes np.save writes for small arrays: a 118 byte header, the dict padded with
+    // spaces and a newline
+    fn fixture(dict: &[u8], data: &[u8]) -> Vec<u8> {
+        let preamble = b"\x93NUMPY\x01\x00v\x00";
+        let padding = vec![b' '; 117 - dict.len()];
+        [&preamble[..], dict, &padding, b"\n", data].concat()
+    }
+
+    // np.array([[1.0, -2.5, 3.0], [0.5, 0.0, 1e-300]])
+    fn float64() -> Vec<u8> {
+        fixture(
+            b"{'descr': '<f8', 'fortran_order': False, 'shape': (2, 3), }",
+            b"\x00\x00\x00\x00\x00\x00\xf0?\x00\x00\x00\x00\x00\x00\x04\xc0\
+              \x00\x00\x00\x00\x00\x00\x08@\x00\x00\x00\x00\x00\x00\xe0?\
+              \x00\x00\x00\x00\x00\x00\x00\x00Y\xf3\xf8\xc2\x1fn\xa5\x01",
+        )
+    }
+
+    #[test]
+    fn numpy_fixtures() {
+        assert_eq!(
+            Matrix::read_npy(float64().as_slice()),
+            Ok(Matrix::new([[1.0, -2.5, 3.0], [0.5, 0.0, 1e-300]]))
+        );
+
+        // np.array([0, 1, -2, 2**40])
+        let int64 = fixture(
+            b"{'descr': '<i8', 'fortran_order': False, 'shape': (4,), }",
+            b"\x00\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\
+              \xfe\xff\xff\xff\xff\xff\xff\xff\x00\x00\x00\x00\x00\x01\x00\x00",
+        );
+        assert_eq!(
+            List::read_npy(int64.as_slice()),
+            Ok(List::new([0.0, 1.0, -2.0, 2f64.powi(40)]))
+        );
+
+        // np.asfortranarray(np.array([[1, 2], [3, 4]], dtype=np.float32)), stored by columns
+        let fortran = fixture(
+            b"{'descr': '<f4', 'fortran_order': True, 'shape': (2, 2), }",
+            b"\x00\x00\x80?\x00\x00@@\x00\x00\x00@\x00\x00\x80@",
+        );
+        assert_eq!(
+            Matrix::read_npy(fortran.as_slice()),
+            Ok(Matrix::new([[1.0, 2.0], [3.0, 4.0]]))
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        // Byte for byte what np.save produces for the same array
+        let m = Matrix::new([[1.0, -2.5, 3.0], [0.5, 0.0, 1e-300]]);
+        let mut written = Vec::new();
+        m.write_npy(&mut written).unwrap();
+        assert_eq!(written, float64());
+        assert_eq!(written.len() % 8, 0);
+        assert_eq!(Matrix::read_npy(written.as_slice()), Ok(m));
+
+        let v = List::new([f64::MIN_POSITIVE, -0.0, f64::INFINITY, 1.0 / 3.0]);
+        let mut written = Vec::new();
+        v.write_npy(&mut written).unwrap();
+        let header_len = u16::from_le_bytes([written[8], written[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        let read = List::<f64, 4>::read_npy(written.as_slice()).unwrap();
+        assert_eq!(read.map(f64::to_bits), v.map(f64::to_bits));
+    }
+
+    #[test]
+    fn rejected() {
+        let bytes = float64();
+        assert_eq!(
+            Matrix::<f64, 3, 2>::read_npy(bytes.as_slice()),
+            Err(NpyError::WrongShape {
+                expected: vec![3, 2],
+                found: vec![2, 3]
+            })
+        );
+        assert_eq!(
+            List::<f64, 6>::read_npy(bytes.as_slice()),
+            Err(NpyError::WrongShape {
+                expected: vec![6],
+                found: vec![2, 3]
+            })
+        );
+        assert_eq!(
+            Matrix::<f64, 2, 3>::read_npy(&bytes[..bytes.len() - 1]),
+            Err(NpyError::Io(std::io::ErrorKind::UnexpectedEof))
+        );
+        assert_eq!(
+            Matrix::<f64, 2, 3>::read_npy(&b"PK\x03\x04 not an npy file"[..]),
+            Err(NpyError::BadMagic)
+        );
+
+        let mut version_2 = bytes.clone();
+        version_2[6] = 2;
+        assert_eq!(
+            Matrix::<f64, 2, 3>::read_npy(version_2.as_slice()),
+            Err(NpyError::UnsupportedVersion(2, 0))
+        );
+
+        let big_endian = fixture(
+            b"{'descr': '>f8', 'fortran_order': False, 'shape': (1,), }",
+            b"?\xf0\x00\x00\x00\x00\x00\x00",
+        );
+        assert_eq!(
+            List::<f64, 1>::read_npy(big_endian.as_slice()),
+            Err(NpyError::UnsupportedDtype(">f8".to_string()))
+        );
+        let complex = fixture(
+            b"{'descr': '<c16', 'fortran_order': False, 'shape': (1,), }",
+            &[0; 16],
+        );
+        assert_eq!(
+            List::<f64, 1>::read_npy(complex.as_slice())
+                .unwrap_err()
+                .to_string(),
+            "unsupported dtype \"<c16\""
+        );
+
+        let no_shape = fixture(b"{'descr': '<f8', 'fortran_order': False, }", &[0; 8]);
+        assert_eq!(
+            List::<f64, 1>::read_npy(no_shape.as_slice()),
+            Err(NpyError::BadHeader("no shape".to_string()))
+        );
+        let bad_order = fixture(
+            b"{'descr': '<f8', 'fortran_order': 0, 'shape': (1,), }",
+            &[0; 8],
+        );
+        assert_eq!(
+            List::<f64, 1>::read_npy(bad_order.as_slice()),
+            Err(NpyError::BadHeader("fortran_order 0".to_string()))
+        );
+    }
+}
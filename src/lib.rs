@@ -1,3 +1,14 @@
-mod chapters;
+pub mod chapters;
+pub mod geometry;
+pub mod io;
+pub mod quaternion;
 
-pub use chapters::c1::definitions::Complex;
+pub use chapters::c1::definitions::{Complex, List};
+
+// Everything needed to work with the vector space definitions of chapter 1
+pub mod prelude {
+    pub use crate::chapters::c1::definitions::{
+        AddInverse, Associative, Commutative, Complex, Distributive, Divide, Identity, Inverse,
+        List, MulIdent, MulInverse, MulScalar, One, Subtract, VectorSpace, WrongLength, Zero,
+    };
+}
@@ -0,0 +1,330 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::chapters::c1::definitions::List;
+use crate::chapters::c3::matrix::Matrix;
+
+// w + x i + y j + z k with i^2 = j^2 = k^2 = ijk = -1.
+// The unit quaternion cos(a / 2) + sin(a / 2) (u_x i + u_y j + u_z k) rotates by the angle a
+// around the unit axis u, q and -q give the same rotation, and q r rotates by r first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion<T> {
+    pub w: T,
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+// The matrix isn't orthogonal with determinant 1 up to rounding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotARotation;
+
+impl<T> Quaternion<T> {
+    pub fn new(w: T, x: T, y: T, z: T) -> Self {
+        Self { w, x, y, z }
+    }
+}
+
+impl<T: Neg<Output = T> + Copy> Quaternion<T> {
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.w, -self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Quaternion<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.w + rhs.w,
+            self.x + rhs.x,
+            self.y + rhs.y,
+            self.z + rhs.z,
+        )
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Quaternion<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.w, -self.x, -self.y, -self.z)
+    }
+}
+
+// The Hamilton product, not commutative
+impl<T> Mul for Quaternion<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (a, b) = (self, rhs);
+        Self::new(
+            a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+            a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+            a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+            a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w,
+        )
+    }
+}
+
+impl Quaternion<f64> {
+    pub fn dot(&self, rhs: &Self) -> f64 {
+        self.w * rhs.w + self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    fn scale(&self, s: f64) -> Self {
+        Self::new(s * self.w, s * self.x, s * self.y, s * self.z)
+    }
+
+    // None for the zero quaternion
+    pub fn normalize(&self) -> Option<Self> {
+        let norm = self.norm();
+        (norm > 0.0).then(|| self.scale(1.0 / norm))
+    }
+
+    // The rotation by angle (radians, counterclockwise looking against the axis) around axis,
+    // which doesn't have to be a unit vector but can't be zero
+    pub fn from_axis_angle(axis: &List<f64, 3>, angle: f64) -> Self {
+        let norm = axis.iter().fold(0.0, |acc: f64, &t| acc.hypot(t));
+        assert!(norm > 0.0, "the axis of a rotation can't be zero");
+        let (s, c) = (angle / 2.0).sin_cos();
+        let s = s / norm;
+        Self::new(c, s * axis[0], s * axis[1], s * axis[2])
+    }
+
+    // The rotation matrix of q / |q|, panics for the zero quaternion
+    pub fn to_rotation_matrix(&self) -> Matrix<f64, 3, 3> {
+        let q = self
+            .normalize()
+            .expect("the zero quaternion is no rotation");
+        let (w, x, y, z) = (q.w, q.x, q.y, q.z);
+        Matrix::new([
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        ])
+    }
+
+    // Shepperd's method. Each of 1 + tr, 1 + m00 - m11 - m22, ... is four times the square of
+    // one component, and the largest of them is at least 1, so the square root and the division
+    // by it for the other components are well conditioned. Using the trace alone breaks down
+    // when it approaches -1, i.e. for rotations by about 180 degrees.
+    pub fn from_rotation_matrix(m: &Matrix<f64, 3, 3>) -> Result<Self, NotARotation> {
+        let mtm = m.transpose() * *m;
+        let orthogonal = (0..9).all(|k| {
+            let (i, j) = (k / 3, k % 3);
+            (mtm[(i, j)] - if i == j { 1.0 } else { 0.0 }).abs() <= 1e-9
+        });
+        if !orthogonal || m.determinant() <= 0.0 {
+            return Err(NotARotation);
+        }
+
+        let trace = m[(0, 0)] + m[(1, 1)] + m[(2, 2)];
+        let candidates = [
+            trace,
+            2.0 * m[(0, 0)] - trace,
+            2.0 * m[(1, 1)] - trace,
+            2.0 * m[(2, 2)] - trace,
+        ];
+        let largest = (0..4)
+            .max_by(|&i, &j| candidates[i].total_cmp(&candidates[j]))
+            .unwrap();
+        let t = (1.0 + candidates[largest]).sqrt() / 2.0;
+        let f = 1.0 / (4.0 * t);
+        let q = match largest {
+            0 => Self::new(
+                t,
+                f * (m[(2, 1)] - m[(1, 2)]),
+                f * (m[(0, 2)] - m[(2, 0)]),
+                f * (m[(1, 0)] - m[(0, 1)]),
+            ),
+            1 => Self::new(
+                f * (m[(2, 1)] - m[(1, 2)]),
+                t,
+                f * (m[(0, 1)] + m[(1, 0)]),
+                f * (m[(0, 2)] + m[(2, 0)]),
+            ),
+            2 => Self::new(
+                f * (m[(0, 2)] - m[(2, 0)]),
+                f * (m[(0, 1)] + m[(1, 0)]),
+                t,
+                f * (m[(1, 2)] + m[(2, 1)]),
+            ),
+            _ => Self::new(
+                f * (m[(1, 0)] - m[(0, 1)]),
+                f * (m[(0, 2)] + m[(2, 0)]),
+                f * (m[(1, 2)] + m[(2, 1)]),
+                t,
+            ),
+        };
+        Ok(q)
+    }
+
+    // v rotated by the unit quaternion q, the vector part of q v q*
+    pub fn rotate(&self, v: &List<f64, 3>) -> List<f64, 3> {
+        let r = *self * Self::new(0.0, v[0], v[1], v[2]) * self.conjugate();
+        List::new([r.x, r.y, r.z])
+    }
+
+    // Spherical linear interpolation between unit quaternions, rotating at constant speed from
+    // self at t = 0 to other at t = 1 along the shorter of the two arcs. Nearly equal
+    // quaternions fall back to normalized linear interpolation, where sin of the angle
+    // between them would cancel.
+    pub fn slerp(&self, other: &Self, t: f64) -> Self {
+        let mut cos = self.dot(other);
+        let mut other = *other;
+        if cos < 0.0 {
+            cos = -cos;
+            other = -other;
+        }
+        if cos > 0.9995 {
+            let lerp = self.scale(1.0 - t) + other.scale(t);
+            return lerp.normalize().unwrap();
+        }
+        let theta = cos.acos();
+        let sin = theta.sin();
+        self.scale(((1.0 - t) * theta).sin() / sin) + other.scale((t * theta).sin() / sin)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NotARotation, Quaternion};
+    use crate::chapters::c1::definitions::List;
+    use crate::chapters::c3::matrix::Matrix;
+    use std::f64::consts::PI;
+
+    fn assert_matrix_close(a: &Matrix<f64, 3, 3>, b: &Matrix<f64, 3, 3>, eps: f64) {
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((a[(i, j)] - b[(i, j)]).abs() < eps, "{a}\n!=\n{b}");
+            }
+        }
+    }
+
+    // q and -q are the same rotation
+    fn assert_same_rotation(q: &Quaternion<f64>, r: &Quaternion<f64>, eps: f64) {
+        assert!((q.dot(r).abs() - 1.0).abs() < eps, "{q:?} != {r:?}");
+    }
+
+    #[test]
+    fn hamilton_product() {
+        let (i, j, k) = (
+            Quaternion::new(0.0, 1.0, 0.0, 0.0),
+            Quaternion::new(0.0, 0.0, 1.0, 0.0),
+            Quaternion::new(0.0, 0.0, 0.0, 1.0),
+        );
+        let minus_one = Quaternion::new(-1.0, 0.0, 0.0, 0.0);
+        assert_eq!(i * i, minus_one);
+        assert_eq!(i * j * k, minus_one);
+        assert_eq!(i * j, k);
+        assert_eq!(j * i, -k);
+    }
+
+    #[test]
+    fn rotation_matrices() {
+        let q = Quaternion::from_axis_angle(&List::new([1.0, 2.0, -0.5]), 1.2);
+        let m = q.to_rotation_matrix();
+        assert_matrix_close(&(m.transpose() * m), &Matrix::identity(), 1e-15);
+        assert!((m.determinant() - 1.0).abs() < 1e-15);
+
+        let v = List::new([0.3, -1.0, 2.0]);
+        let (rotated, by_matrix) = (q.rotate(&v), m * v);
+        for i in 0..3 {
+            assert!((rotated[i] - by_matrix[i]).abs() < 1e-15);
+        }
+        assert_same_rotation(&Quaternion::from_rotation_matrix(&m).unwrap(), &q, 1e-15);
+
+        // A quarter turn around z takes x to y
+        let quarter = Quaternion::from_axis_angle(&List::new([0.0, 0.0, 1.0]), PI / 2.0);
+        assert_matrix_close(
+            &quarter.to_rotation_matrix(),
+            &Matrix::new([[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]]),
+            1e-15,
+        );
+
+        // Only the direction of q matters
+        let scaled = Quaternion::new(2.0 * q.w, 2.0 * q.x, 2.0 * q.y, 2.0 * q.z);
+        assert_matrix_close(&scaled.to_rotation_matrix(), &m, 1e-15);
+    }
+
+    #[test]
+    fn half_turn() {
+        // tr(M) = -1, where w = 0 and the trace formula divides by zero
+        let axis = List::new([0.48, -0.6, 0.64]);
+        let q = Quaternion::from_axis_angle(&axis, PI);
+        let m = q.to_rotation_matrix();
+        assert!((m[(0, 0)] + m[(1, 1)] + m[(2, 2)] + 1.0).abs() < 1e-15);
+        let back = Quaternion::from_rotation_matrix(&m).unwrap();
+        assert_same_rotation(&back, &q, 1e-15);
+        assert_matrix_close(&back.to_rotation_matrix(), &m, 1e-15);
+
+        // Just short of it
+        let q = Quaternion::from_axis_angle(&axis, PI - 1e-7);
+        let back = Quaternion::from_rotation_matrix(&q.to_rotation_matrix()).unwrap();
+        assert_same_rotation(&back, &q, 1e-15);
+    }
+
+    #[test]
+    fn composition() {
+        let q = Quaternion::from_axis_angle(&List::new([1.0, 0.0, 1.0]), 0.7);
+        let r = Quaternion::from_axis_angle(&List::new([0.0, -2.0, 1.0]), 2.1);
+        assert_matrix_close(
+            &(q * r).to_rotation_matrix(),
+            &(q.to_rotation_matrix() * r.to_rotation_matrix()),
+            1e-15,
+        );
+        assert_same_rotation(
+            &(q * q.conjugate()),
+            &Quaternion::new(1.0, 0.0, 0.0, 0.0),
+            1e-15,
+        );
+    }
+
+    #[test]
+    fn not_rotations() {
+        let reflection = Matrix::new([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, -1.0]]);
+        assert_eq!(
+            Quaternion::from_rotation_matrix(&reflection),
+            Err(NotARotation)
+        );
+        let shear = Matrix::new([[1.0, 0.5, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+        assert_eq!(Quaternion::from_rotation_matrix(&shear), Err(NotARotation));
+    }
+
+    #[test]
+    fn slerp() {
+        let q = Quaternion::from_axis_angle(&List::new([0.0, 0.0, 1.0]), 0.2);
+        let r = Quaternion::from_axis_angle(&List::new([0.0, 0.0, 1.0]), 1.8);
+        assert_eq!(q.slerp(&r, 0.0), q);
+        assert_eq!(q.slerp(&r, 1.0), r);
+
+        // Constant speed along the arc, a quarter of the way is a rotation by 0.6
+        let quarter = Quaternion::from_axis_angle(&List::new([0.0, 0.0, 1.0]), 0.6);
+        assert_same_rotation(&q.slerp(&r, 0.25), &quarter, 1e-15);
+        assert!((q.slerp(&r, 0.4).norm() - 1.0).abs() < 1e-15);
+
+        // The shorter way round, also when r is given as -r
+        assert_same_rotation(&q.slerp(&-r, 0.25), &quarter, 1e-15);
+        let close = Quaternion::from_axis_angle(&List::new([0.0, 0.0, 1.0]), 0.2 + 1e-5);
+        assert!((q.slerp(&close, 0.5).norm() - 1.0).abs() < 1e-15);
+    }
+}
@@ -0,0 +1,54 @@
+use linear_algebra::prelude::*;
+
+fn accept_field<T: VectorSpace<X>, X>() {}
+
+#[test]
+fn root_paths() {
+    let z: linear_algebra::Complex<f64> = Complex::new(1.0, 2.0);
+    let v: linear_algebra::List<f64, 2> = List::new([1.0, 2.0]);
+    assert_eq!(*z.re(), v[0]);
+    assert_eq!(*z.im(), v[1]);
+}
+
+#[test]
+fn vector_space_traits() {
+    accept_field::<f64, f64>();
+    accept_field::<Complex<f64>, Complex<f64>>();
+    accept_field::<Complex<f64>, f64>();
+    accept_field::<List<Complex<f64>, 4>, Complex<f64>>();
+}
+
+#[test]
+fn complex_operators() {
+    let z = Complex::new(3.0, -1.0);
+    let w = Complex::new(1.0, 1.0);
+
+    assert_eq!(z + w, Complex::new(4.0, 0.0));
+    assert_eq!(z - w, Complex::new(2.0, -2.0));
+    assert_eq!(z - w, z.subtract(w));
+    assert_eq!(-z, z.negate());
+    assert_eq!(z * w, Complex::new(4.0, 2.0));
+    assert_eq!(z * Complex::ONE, z);
+    assert_eq!(z + Complex::ZERO, z);
+    assert_eq!((z * w) / w, z);
+    assert_eq!(z.divide(w), z * w.inverse());
+
+    let mut u = z;
+    u += w;
+    u -= w;
+    u *= w;
+    u /= w;
+    assert_eq!(u, z);
+}
+
+#[test]
+fn list_operators() {
+    let u = List::new([1.0, -2.0, 4.0]);
+    let v = List::new([0.5, 0.5, 0.5]);
+
+    assert_eq!(u + v, List::new([1.5, -1.5, 4.5]));
+    assert_eq!(-u, List::new([-1.0, 2.0, -4.0]));
+    assert_eq!(MulScalar::mul(u, 2.0), 2.0 * u);
+    assert_eq!(u + List::ZERO, u);
+    assert_eq!(List::<f64, 3>::ONE, List::new([1.0; 3]));
+}